@@ -25,6 +25,10 @@ pub enum Commands {
         cmd: Vec<String>,
     },
 
+    /// Review and broadcast a raw transaction signed offline
+    #[command(alias = "send-raw")]
+    Broadcast { raw_tx: Option<String> },
+
     #[command(alias = "its", hide = true)]
     InviteCode { code: String },
 
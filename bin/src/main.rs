@@ -1,6 +1,7 @@
 use clap::Parser;
 use gm_tui::pages::{
-    main_menu::MainMenuItem, shell::ShellPage, walletconnect::WalletConnectPage, Page,
+    broadcast::BroadcastPage, main_menu::MainMenuItem, shell::ShellPage,
+    walletconnect::WalletConnectPage, Page,
 };
 
 mod cli;
@@ -45,6 +46,15 @@ async fn main() -> gm_tui::Result<()> {
                 tui_app.insert_page(Page::Shell(run_page));
             }
 
+            Commands::Broadcast { raw_tx } => {
+                let mut broadcast = BroadcastPage::new()?;
+                if let Some(raw_tx) = raw_tx {
+                    broadcast.set_raw_tx(&raw_tx);
+                }
+                main_menu.set_focussed_item(MainMenuItem::BroadcastRawTx);
+                tui_app.insert_page(Page::Broadcast(broadcast));
+            }
+
             Commands::InviteCode { code } => {
                 tui_app.invite_popup.set_invite_code(code);
                 tui_app.invite_popup.open();
@@ -1,10 +1,258 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
 use alloy::{hex, signers::k256::FieldBytes};
 use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Path used by most wallets for the first account of a mnemonic.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Printed in place of secret values by the `Debug` implementations.
+pub const REDACTED: &str = "<redacted>";
 
-#[derive(Clone, Debug)]
+/// Both variants are zeroed when dropped and never print the secret with `Debug`.
 pub enum Secret {
-    Mnemonic(String),
-    PrivateKey(FieldBytes),
+    Mnemonic(MnemonicSecret),
+    PrivateKey(SecretBytes<32>),
+}
+
+impl Secret {
+    pub fn from_private_key(private_key: &FieldBytes) -> Self {
+        Secret::PrivateKey(SecretBytes::from_slice(private_key))
+    }
+}
+
+impl Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Secret::Mnemonic(mnemonic) => f.debug_tuple("Mnemonic").field(mnemonic).finish(),
+            Secret::PrivateKey(private_key) => {
+                f.debug_tuple("PrivateKey").field(private_key).finish()
+            }
+        }
+    }
+}
+
+/// Fixed size key material kept on the heap so that it does not get copied
+/// around when moved. The memory is locked with `mlock` as a best effort to
+/// keep it out of swap, and it is zeroed when dropped.
+pub struct SecretBytes<const N: usize>(Box<[u8; N]>);
+
+impl<const N: usize> SecretBytes<N> {
+    pub fn zero() -> Self {
+        let bytes = Box::new([0u8; N]);
+        lock_memory(bytes.as_ptr(), N);
+        Self(bytes)
+    }
+
+    /// Panics if the length of the slice is not `N`.
+    pub fn from_slice(slice: &[u8]) -> Self {
+        let mut bytes = Self::zero();
+        bytes.0.copy_from_slice(slice);
+        bytes
+    }
+
+    pub fn expose(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    pub fn expose_mut(&mut self) -> &mut [u8; N] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> Clone for SecretBytes<N> {
+    fn clone(&self) -> Self {
+        Self::from_slice(self.expose())
+    }
+}
+
+impl<const N: usize> Drop for SecretBytes<N> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+        unlock_memory(self.0.as_ptr(), N);
+    }
+}
+
+impl<const N: usize> ZeroizeOnDrop for SecretBytes<N> {}
+
+impl<const N: usize> Debug for SecretBytes<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Failures are ignored, e.g. when `RLIMIT_MEMLOCK` is exhausted the secret is
+/// still usable, it just may end up in swap.
+#[cfg(unix)]
+fn lock_memory(ptr: *const u8, len: usize) {
+    // SAFETY: the range belongs to a live allocation of the caller
+    unsafe {
+        libc::mlock(ptr.cast(), len);
+    }
+}
+
+/// Locks are per page, so this can unlock other secrets sharing the page. It
+/// is only a best effort after all.
+#[cfg(unix)]
+fn unlock_memory(ptr: *const u8, len: usize) {
+    // SAFETY: the range belongs to a live allocation of the caller
+    unsafe {
+        libc::munlock(ptr.cast(), len);
+    }
+}
+
+#[cfg(not(unix))]
+fn lock_memory(_ptr: *const u8, _len: usize) {}
+
+#[cfg(not(unix))]
+fn unlock_memory(_ptr: *const u8, _len: usize) {}
+
+/// A mnemonic phrase along with the derivation path of the account, so that
+/// multiple accounts can be derived from a single phrase. The optional BIP-39
+/// passphrase is stored with the phrase since it is needed for every signature.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct MnemonicSecret {
+    pub phrase: String,
+    #[serde(default = "default_derivation_path")]
+    pub derivation_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    #[serde(default, skip_serializing_if = "MnemonicLanguage::is_english")]
+    #[zeroize(skip)]
+    pub language: MnemonicLanguage,
+}
+
+impl Debug for MnemonicSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MnemonicSecret")
+            .field("phrase", &REDACTED)
+            .field("derivation_path", &self.derivation_path)
+            .field("passphrase", &self.passphrase.as_ref().map(|_| REDACTED))
+            .field("language", &self.language)
+            .finish()
+    }
+}
+
+/// Wordlists supported by BIP-39.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MnemonicLanguage {
+    #[default]
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    Czech,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Portuguese,
+    Spanish,
+}
+
+impl MnemonicLanguage {
+    pub const ALL: [MnemonicLanguage; 10] = [
+        MnemonicLanguage::English,
+        MnemonicLanguage::ChineseSimplified,
+        MnemonicLanguage::ChineseTraditional,
+        MnemonicLanguage::Czech,
+        MnemonicLanguage::French,
+        MnemonicLanguage::Italian,
+        MnemonicLanguage::Japanese,
+        MnemonicLanguage::Korean,
+        MnemonicLanguage::Portuguese,
+        MnemonicLanguage::Spanish,
+    ];
+
+    pub fn is_english(&self) -> bool {
+        *self == MnemonicLanguage::English
+    }
+
+    pub fn list() -> Vec<String> {
+        Self::ALL
+            .iter()
+            .map(|language| language.to_string())
+            .collect()
+    }
+}
+
+impl Display for MnemonicLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            MnemonicLanguage::English => "English",
+            MnemonicLanguage::ChineseSimplified => "Chinese (Simplified)",
+            MnemonicLanguage::ChineseTraditional => "Chinese (Traditional)",
+            MnemonicLanguage::Czech => "Czech",
+            MnemonicLanguage::French => "French",
+            MnemonicLanguage::Italian => "Italian",
+            MnemonicLanguage::Japanese => "Japanese",
+            MnemonicLanguage::Korean => "Korean",
+            MnemonicLanguage::Portuguese => "Portuguese",
+            MnemonicLanguage::Spanish => "Spanish",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for MnemonicLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.to_string() == s)
+            .ok_or_else(|| format!("Unknown mnemonic language: {s}"))
+    }
+}
+
+fn default_derivation_path() -> String {
+    DEFAULT_DERIVATION_PATH.to_string()
+}
+
+impl MnemonicSecret {
+    pub fn new(phrase: &str, derivation_path: &str) -> Self {
+        Self {
+            phrase: phrase.to_string(),
+            derivation_path: derivation_path.to_string(),
+            passphrase: None,
+            language: MnemonicLanguage::English,
+        }
+    }
+
+    /// Phrase with the default derivation path, this is how mnemonics were
+    /// stored before derivation paths were supported.
+    pub fn from_phrase(phrase: &str) -> Self {
+        Self::new(phrase, DEFAULT_DERIVATION_PATH)
+    }
+
+    /// Whether this can be stored as just the phrase, which is how mnemonics
+    /// were stored before paths, passphrases and wordlists were supported.
+    pub fn is_phrase_only(&self) -> bool {
+        self.derivation_path == DEFAULT_DERIVATION_PATH
+            && self.passphrase.is_none()
+            && self.language.is_english()
+    }
+
+    /// Encodes to a string for stores which only hold a string. Only the phrase
+    /// is stored when possible so that older entries remain compatible.
+    pub fn encode(&self) -> String {
+        if self.is_phrase_only() {
+            self.phrase.clone()
+        } else {
+            serde_json::to_string(self).expect("serializing strings cannot fail")
+        }
+    }
+
+    pub fn decode(s: &str) -> Result<Self, serde_json::Error> {
+        if s.trim_start().starts_with('{') {
+            serde_json::from_str(s)
+        } else {
+            Ok(Self::from_phrase(s))
+        }
+    }
 }
 
 impl Serialize for Secret {
@@ -13,9 +261,15 @@ impl Serialize for Secret {
         S: serde::Serializer,
     {
         match self {
-            Secret::Mnemonic(mnemonic) => serializer.serialize_str(mnemonic),
+            Secret::Mnemonic(mnemonic) => {
+                if mnemonic.is_phrase_only() {
+                    serializer.serialize_str(&mnemonic.phrase)
+                } else {
+                    mnemonic.serialize(serializer)
+                }
+            }
             Secret::PrivateKey(private_key) => {
-                let hex = hex::encode(private_key);
+                let hex = Zeroizing::new(hex::encode(private_key.expose()));
                 serializer.serialize_str(&hex)
             }
         }
@@ -27,15 +281,26 @@ impl<'de> Deserialize<'de> for Secret {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        if s.len() == 64 {
-            let mut bytes = [0u8; 32];
-            hex::decode_to_slice(&s, &mut bytes).map_err(serde::de::Error::custom)?;
-            Ok(Secret::PrivateKey(*FieldBytes::from_slice(
-                bytes.as_slice(),
-            )))
-        } else {
-            Ok(Secret::Mnemonic(s))
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            String(String),
+            Mnemonic(MnemonicSecret),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::String(s) if s.len() == 64 => {
+                let s = Zeroizing::new(s);
+                let mut bytes = SecretBytes::zero();
+                hex::decode_to_slice(s.as_str(), bytes.expose_mut())
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Secret::PrivateKey(bytes))
+            }
+            Repr::String(s) => {
+                let s = Zeroizing::new(s);
+                Ok(Secret::Mnemonic(MnemonicSecret::from_phrase(&s)))
+            }
+            Repr::Mnemonic(mnemonic) => Ok(Secret::Mnemonic(mnemonic)),
         }
     }
 }
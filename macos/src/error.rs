@@ -7,11 +7,17 @@ pub enum MacosError {
     #[error("Not able to find the account {0} in your keychain. (Error: {1:?})")]
     AccountNotFoundInKeychain(Address, security_framework::base::Error),
 
+    #[error("Not able to find the account {0} in your keychain for deleting.")]
+    AccountNotFoundForDeletion(Address),
+
     #[error("Failed to store account {0} in your keychain. (Error: {1:?})")]
     StoringAccountInKeychainFailed(Address, security_framework::base::Error),
 
-    #[error("Failed to parse string from keychain secret for account {0}. (Error: {1:?})")]
-    ParsingStringFromKeychainSecretFailed(Address, std::string::FromUtf8Error),
+    #[error("Failed to parse string from keychain secret for account {0}. (Error: {1})")]
+    ParsingStringFromKeychainSecretFailed(Address, std::str::Utf8Error),
+
+    #[error("Failed to parse mnemonic from keychain secret for account {0}. (Error: {1:?})")]
+    ParsingMnemonicFromKeychainSecretFailed(Address, serde_json::Error),
 
     #[error("Not able to parse address for the keychain item {0}. (Error: {1:?})")]
     ParsingAddressFromKeychainFailed(String, alloy::hex::FromHexError),
@@ -10,12 +10,13 @@ use core_foundation::{
     dictionary::CFDictionary,
     string::CFString,
 };
-use gm_common::secret::Secret;
+use gm_common::secret::{MnemonicSecret, Secret};
 use security_framework::{
     item::{ItemClass, ItemSearchOptions, SearchResult},
     os::macos::keychain::SecKeychain,
 };
 use std::collections::HashMap;
+use zeroize::Zeroizing;
 
 use crate::auth::authenticate;
 
@@ -26,11 +27,15 @@ fn keychain() -> SecKeychain {
 pub struct Macos;
 
 impl Macos {
-    pub fn store_mnemonic_wallet(phrase: &str, address: Address) -> crate::Result<()> {
+    pub fn store_mnemonic_wallet(mnemonic: &MnemonicSecret, address: Address) -> crate::Result<()> {
         let mnemonic_service = format!("gm:mnemonic:{address}");
 
         keychain()
-            .add_generic_password(&mnemonic_service, &address.to_string(), phrase.as_bytes())
+            .add_generic_password(
+                &mnemonic_service,
+                &address.to_string(),
+                mnemonic.encode().as_bytes(),
+            )
             .map_err(|e| crate::Error::StoringAccountInKeychainFailed(address, e))?;
 
         Ok(())
@@ -50,6 +55,25 @@ impl Macos {
         Ok(())
     }
 
+    pub fn delete_account(address: &Address) -> crate::Result<()> {
+        authenticate(&format!("delete account {address:#}"))?;
+
+        let mut found = false;
+        for service in [format!("gm:mnemonic:{address}"), format!("gm:{address}")] {
+            if let Ok((_, item)) = keychain().find_generic_password(&service, &address.to_string())
+            {
+                item.delete();
+                found = true;
+            }
+        }
+
+        if found {
+            Ok(())
+        } else {
+            Err(crate::Error::AccountNotFoundForDeletion(*address))
+        }
+    }
+
     pub fn get_account_list() -> crate::Result<Vec<Address>> {
         let mut search = ItemSearchOptions::default();
         search.class(ItemClass::generic_password());
@@ -88,10 +112,16 @@ impl Macos {
                 .find_generic_password(&mnemonic_service, &address.to_string())
                 .map_err(|e| crate::Error::AccountNotFoundInKeychain(*address, e))
                 .and_then(|(pswd, _item)| {
-                    String::from_utf8(pswd.to_vec())
+                    // Parsed in place, an error must not carry a copy of the secret
+                    std::str::from_utf8(&pswd)
                         .map_err(|e| {
                             crate::Error::ParsingStringFromKeychainSecretFailed(*address, e)
                         })
+                        .and_then(|s| {
+                            MnemonicSecret::decode(s).map_err(|e| {
+                                crate::Error::ParsingMnemonicFromKeychainSecretFailed(*address, e)
+                            })
+                        })
                         .map(Secret::Mnemonic)
                 })
         };
@@ -101,13 +131,13 @@ impl Macos {
                 .find_generic_password(&pk_service, &address.to_string())
                 .map_err(|e| crate::Error::AccountNotFoundInKeychain(*address, e))
                 .and_then(|(pswd, _item)| {
-                    let raw_bytes = pswd.to_vec();
-                    let hex_decoded = hex::decode(&raw_bytes);
-                    let pk = hex_decoded.unwrap_or(raw_bytes);
+                    let pk = Zeroizing::new(
+                        hex::decode(pswd.as_ref()).unwrap_or_else(|_| pswd.to_vec()),
+                    );
 
                     SigningKey::from_slice(&pk)
                         .map_err(|e| crate::Error::PrivateKeyInvalid(*address, e))
-                        .map(|key| Secret::PrivateKey(key.to_bytes()))
+                        .map(|key| Secret::from_private_key(&key.to_bytes()))
                 })
         };
 
@@ -32,6 +32,11 @@ pub enum FormWidget {
         empty_text: Option<&'static str>,
         currency: Option<String>,
     },
+    /// Input box which renders `*` in place of the characters typed.
+    PasswordInput {
+        label: &'static str,
+        text: String,
+    },
     BooleanInput {
         label: &'static str,
         value: bool,
@@ -58,6 +63,7 @@ impl FormWidget {
     pub fn label(&self) -> Option<&'static str> {
         match self {
             FormWidget::InputBox { label, .. } => Some(label),
+            FormWidget::PasswordInput { label, .. } => Some(label),
             FormWidget::DisplayBox { label, .. } => Some(label),
             FormWidget::BooleanInput { label, .. } => Some(label),
             FormWidget::Button { label } => Some(label),
@@ -72,8 +78,9 @@ impl FormWidget {
     pub fn max_cursor(&self) -> usize {
         match self {
             FormWidget::InputBox { text, .. }
+            | FormWidget::PasswordInput { text, .. }
             | FormWidget::DisplayBox { text, .. }
-            | FormWidget::SelectInput { text, .. } => text.len(),
+            | FormWidget::SelectInput { text, .. } => text.chars().count(),
             FormWidget::BooleanInput { value, .. } => value.to_string().len(),
             FormWidget::Button { .. }
             | FormWidget::Heading(_)
@@ -86,6 +93,7 @@ impl FormWidget {
     pub fn to_value(&self) -> Option<String> {
         match self {
             FormWidget::InputBox { text, .. }
+            | FormWidget::PasswordInput { text, .. }
             | FormWidget::DisplayBox { text, .. }
             | FormWidget::SelectInput { text, .. } => Some(text.clone()),
             FormWidget::BooleanInput { value, .. } => Some(value.to_string()),
@@ -100,6 +108,7 @@ impl FormWidget {
     pub fn height(&self, area: Rect) -> u16 {
         match self {
             FormWidget::InputBox { text, .. }
+            | FormWidget::PasswordInput { text, .. }
             | FormWidget::DisplayBox { text, .. }
             | FormWidget::SelectInput { text, .. } => {
                 let lines = split_string(text, (area.width - 2) as usize);
@@ -231,6 +240,7 @@ impl<
             | FormWidget::ErrorText(_) => false,
 
             FormWidget::InputBox { .. }
+            | FormWidget::PasswordInput { .. }
             | FormWidget::DisplayBox { .. }
             | FormWidget::BooleanInput { .. }
             | FormWidget::SelectInput { .. }
@@ -241,6 +251,7 @@ impl<
     pub fn get_text(&self, idx: T) -> &String {
         match &self.items[idx.index()] {
             FormWidget::InputBox { text, .. } => text,
+            FormWidget::PasswordInput { text, .. } => text,
             FormWidget::DisplayBox { text, .. } => text,
             FormWidget::DisplayText(text) => text,
             FormWidget::ErrorText(text) => text,
@@ -252,6 +263,7 @@ impl<
     pub fn get_text_mut(&mut self, idx: T) -> &mut String {
         match &mut self.items[idx.index()] {
             FormWidget::InputBox { text, .. } => text,
+            FormWidget::PasswordInput { text, .. } => text,
             FormWidget::DisplayBox { text, .. } => text,
             FormWidget::DisplayText(text) => text,
             FormWidget::ErrorText(text) => text,
@@ -339,10 +351,10 @@ impl<
                         KeyCode::Down | KeyCode::Tab => {
                             self.advance_cursor();
                         }
-                        KeyCode::Enter => {
-                            if !self.is_button_focused() && !self.is_select_focused() {
-                                self.advance_cursor();
-                            }
+                        KeyCode::Enter
+                            if !self.is_button_focused() && !self.is_select_focused() =>
+                        {
+                            self.advance_cursor();
                         }
 
                         _ => {}
@@ -352,7 +364,7 @@ impl<
                 let value_before = self.items[self.cursor].to_value();
 
                 match &mut self.items[self.cursor] {
-                    FormWidget::InputBox { text, .. } => {
+                    FormWidget::InputBox { text, .. } | FormWidget::PasswordInput { text, .. } => {
                         InputBox::handle_event(Some(key_event), text, &mut self.text_cursor);
                     }
                     FormWidget::DisplayBox { .. } => {
@@ -372,7 +384,7 @@ impl<
 
                         let popup_result = popup.handle_event(Some(key_event), |selected| {
                             *text = selected.clone();
-                            self.text_cursor = selected.len();
+                            self.text_cursor = selected.chars().count();
                             Ok(())
                         })?;
                         result.merge(popup_result);
@@ -491,6 +503,24 @@ impl<
                     widget.render(area, &mut virtual_buf, &self.text_cursor, theme);
                     area.y += height_used;
                 }
+                FormWidget::PasswordInput { label, text } => {
+                    let masked = if !self.everything_empty {
+                        "*".repeat(text.chars().count())
+                    } else {
+                        String::new()
+                    };
+                    let widget = InputBox {
+                        focus: self.form_focus && self.cursor == i,
+                        label,
+                        text: &masked,
+                        empty_text: None,
+                        currency: None,
+                    };
+                    let height_used = widget.height_used(area); // to see height based on width
+
+                    widget.render(area, &mut virtual_buf, &self.text_cursor, theme);
+                    area.y += height_used;
+                }
                 FormWidget::BooleanInput { label, value } => {
                     let widget = InputBox {
                         focus: self.form_focus && self.cursor == i,
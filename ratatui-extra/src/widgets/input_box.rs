@@ -9,12 +9,21 @@ use crate::{extensions::WidgetHeight, thematize::Thematize};
 
 use gm_utils::text::split_string;
 
+/// Byte offset of the char at `text_cursor`, the cursor counts chars so that
+/// it stays on a char boundary for non-ASCII text.
+fn byte_index(text_input: &str, text_cursor: usize) -> usize {
+    text_input
+        .char_indices()
+        .nth(text_cursor)
+        .map_or(text_input.len(), |(index, _)| index)
+}
+
 fn option_delete(text_input: &mut String, text_cursor: &mut usize) {
     loop {
         if *text_cursor == 0 {
             break;
         }
-        text_input.remove(*text_cursor - 1);
+        text_input.remove(byte_index(text_input, *text_cursor - 1));
         *text_cursor -= 1;
         if *text_cursor == 0 {
             break;
@@ -41,7 +50,7 @@ fn option_left(text_input: &str, text_cursor: &mut usize) {
 
 fn option_right(text_input: &str, text_cursor: &mut usize) {
     loop {
-        if *text_cursor == text_input.len() {
+        if *text_cursor == text_input.chars().count() {
             break;
         }
         *text_cursor += 1;
@@ -79,7 +88,7 @@ impl InputBox<'_> {
                 KeyCode::Right => {
                     if key_event.modifiers == KeyModifiers::ALT {
                         option_right(text_input, text_cursor);
-                    } else if *text_cursor < text_input.len() {
+                    } else if *text_cursor < text_input.chars().count() {
                         *text_cursor += 1
                     }
                     return true;
@@ -91,7 +100,7 @@ impl InputBox<'_> {
                     }
                     // Handle command + delete on macOS
                     else if char == 'u' && key_event.modifiers == KeyModifiers::CONTROL {
-                        let (_, right) = text_input.split_at(*text_cursor);
+                        let (_, right) = text_input.split_at(byte_index(text_input, *text_cursor));
                         *text_input = right.to_string();
                         *text_cursor = 0;
                     }
@@ -101,7 +110,7 @@ impl InputBox<'_> {
                     }
                     // Handle command + right on macOS
                     else if char == 'e' && key_event.modifiers == KeyModifiers::CONTROL {
-                        *text_cursor = text_input.len();
+                        *text_cursor = text_input.chars().count();
                     }
                     // Handle option + delete on macOS
                     else if char == 'w' && key_event.modifiers == KeyModifiers::CONTROL {
@@ -117,7 +126,7 @@ impl InputBox<'_> {
                     }
                     // Simple char press
                     else {
-                        text_input.insert(*text_cursor, char);
+                        text_input.insert(byte_index(text_input, *text_cursor), char);
                         *text_cursor += 1;
                     }
                     return true;
@@ -127,7 +136,7 @@ impl InputBox<'_> {
                         option_delete(text_input, text_cursor);
                     } else if *text_cursor > 0 {
                         *text_cursor -= 1;
-                        text_input.remove(*text_cursor);
+                        text_input.remove(byte_index(text_input, *text_cursor));
                     }
                     return true;
                 }
@@ -161,8 +170,10 @@ impl InputBox<'_> {
         let inner_area = block.inner(area_used);
         block.render(area_used, buf);
 
-        if lines.len() == 1 && !lines.last().unwrap().is_empty() && self.currency.is_some() {
-            let currency = self.currency.unwrap();
+        if let Some(currency) = self
+            .currency
+            .filter(|_| lines.len() == 1 && !lines.last().unwrap().is_empty())
+        {
             Span::from(currency).render(
                 inner_area.offset(Offset {
                     x: lines.last().unwrap().len() as i32 + 1,
@@ -183,8 +194,8 @@ impl InputBox<'_> {
         }
 
         if self.focus {
-            if self.text.is_empty() && self.empty_text.is_some() {
-                self.empty_text.unwrap().render(inner_area, buf);
+            if let Some(empty_text) = self.empty_text.filter(|_| self.text.is_empty()) {
+                empty_text.render(inner_area, buf);
             } else {
                 Span::from("|").render(
                     Rect {
@@ -206,3 +217,31 @@ impl WidgetHeight for InputBox<'_> {
         (2 + lines.len()) as u16
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_edit_non_ascii_text() {
+        let (mut text, mut cursor) = (String::new(), 0);
+        let mut press = |code| {
+            InputBox::handle_event(
+                Some(&KeyEvent::new(code, KeyModifiers::NONE)),
+                &mut text,
+                &mut cursor,
+            );
+        };
+        for char in "pä🔑s".chars() {
+            press(KeyCode::Char(char));
+        }
+        press(KeyCode::Left);
+        press(KeyCode::Backspace);
+        press(KeyCode::Char('x'));
+        press(KeyCode::Right);
+        press(KeyCode::Right);
+
+        assert_eq!(text, "päxs");
+        assert_eq!(cursor, 4);
+    }
+}
@@ -1,10 +1,11 @@
 #[cfg(feature = "demo")]
 use std::time::{Duration, Instant};
 use std::{
+    collections::HashMap,
     io,
     str::FromStr,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         mpsc, Arc, RwLock, RwLockWriteGuard,
     },
 };
@@ -19,6 +20,8 @@ use crate::pages::{
     text::TextPage,
     title::Title,
     trade::TradePage,
+    tx_queue::TxQueue,
+    unlock_popup::UnlockPopup,
     Page,
 };
 use alloy::primitives::Address;
@@ -26,7 +29,13 @@ use gm_ratatui_extra::{
     extensions::RectExt, form::Form, text_popup::TextPopup, thematize::Thematize,
 };
 use gm_utils::{
+    account::{
+        metadata::{AccountMetadataStore, LabeledAddress},
+        mining_jobs::MiningJobResult,
+        AccountManager,
+    },
     assets::{Asset, AssetManager},
+    calldata::CalldataDecoder,
     config::Config,
     disk_storage::DiskStorageInterface,
     network::NetworkStore,
@@ -56,9 +65,12 @@ pub struct SharedState {
     pub testnet_mode: bool,
     pub developer_mode: bool,
     pub current_account: Option<Address>,
+    pub account_labels: HashMap<Address, String>,
     pub alchemy_api_key_available: bool,
     pub eth_price: Option<String>,
     pub theme: Theme,
+    /// Transactions queued from any page to be signed together.
+    pub tx_queue: Arc<RwLock<TxQueue>>,
 }
 
 impl SharedState {
@@ -75,6 +87,12 @@ impl SharedState {
             .cloned())
     }
 
+    pub fn tx_queue(&self) -> crate::Result<RwLockWriteGuard<'_, TxQueue>> {
+        self.tx_queue
+            .write()
+            .map_err(|_| crate::Error::Poisoned("tx_queue".to_string()))
+    }
+
     pub fn assets_mut(&mut self) -> crate::Result<RwLockWriteGuard<'_, AssetManager>> {
         self.asset_manager
             .write()
@@ -85,6 +103,14 @@ impl SharedState {
         self.current_account
             .ok_or_else(|| crate::Error::CurrentAccountNotSet)
     }
+
+    /// Address formatted along with the label given by the user, if any.
+    pub fn labeled_address<'a>(&'a self, address: &'a Address) -> LabeledAddress<'a> {
+        LabeledAddress {
+            address,
+            label: self.account_labels.get(address),
+        }
+    }
 }
 
 pub struct App {
@@ -93,7 +119,9 @@ pub struct App {
     shared_state: SharedState,
 
     fatal_error_popup: TextPopup,
+    notification_popup: TextPopup,
     pub invite_popup: InvitePopup,
+    unlock_popup: UnlockPopup,
     #[cfg(feature = "demo")]
     demo_popup: TextPopup,
 
@@ -101,7 +129,11 @@ pub struct App {
     eth_price_thread: Option<tokio::task::JoinHandle<()>>,
     assets_thread: Option<tokio::task::JoinHandle<()>>,
     recent_addresses_thread: Option<tokio::task::JoinHandle<()>>,
+    history_thread: Option<tokio::task::JoinHandle<()>>,
     helios_thread: Option<tokio::task::JoinHandle<()>>,
+    mining_jobs_thread: Option<tokio::task::JoinHandle<()>>,
+    auto_lock_thread: Option<tokio::task::JoinHandle<()>>,
+    auto_lock_timeout_secs: Arc<AtomicU64>,
 
     #[cfg(feature = "demo")]
     demo_timer: Option<Instant>,
@@ -121,16 +153,20 @@ impl App {
                 asset_manager: Arc::new(RwLock::new(AssetManager::default())),
                 recent_addresses: None,
                 current_account: config.current_account,
+                account_labels: AccountMetadataStore::load()?.labels(),
                 developer_mode: config.developer_mode,
                 alchemy_api_key_available: config.alchemy_api_key.is_some(),
                 online: None,
                 eth_price: None,
                 testnet_mode: config.testnet_mode,
                 theme,
+                tx_queue: Arc::new(RwLock::new(TxQueue::default())),
             },
 
             fatal_error_popup: TextPopup::new("Fatal Error", true),
+            notification_popup: TextPopup::new("Notification", true),
             invite_popup: InvitePopup::default(),
+            unlock_popup: UnlockPopup::default(),
             #[cfg(feature = "demo")]
             demo_popup: TextPopup::new("", false),
 
@@ -138,7 +174,13 @@ impl App {
             eth_price_thread: None,
             assets_thread: None,
             recent_addresses_thread: None,
+            history_thread: None,
             helios_thread: None,
+            mining_jobs_thread: None,
+            auto_lock_thread: None,
+            auto_lock_timeout_secs: Arc::new(AtomicU64::new(events::auto_lock::timeout_secs(
+                &config,
+            ))),
 
             #[cfg(feature = "demo")]
             demo_timer: Some(Instant::now()),
@@ -188,9 +230,7 @@ impl App {
             for event in events {
                 self.handle_event(event, area, &event_tr, &shutdown)
                     .await
-                    .unwrap_or_else(|e| {
-                        self.fatal_error_popup.set_text(e.to_string());
-                    })
+                    .unwrap_or_else(|e| self.handle_error(e))
             }
         }
 
@@ -199,7 +239,7 @@ impl App {
 
             self.handle_event(event_rc.recv()?, area, &event_tr, &shutdown)
                 .await
-                .unwrap_or_else(|e| self.fatal_error_popup.set_text(e.to_string()));
+                .unwrap_or_else(|e| self.handle_error(e));
         }
 
         // final render before exiting
@@ -214,6 +254,18 @@ impl App {
         Ok(())
     }
 
+    /// Errors caused by a locked key store ask for the password instead of
+    /// showing the error, the user can retry the action after unlocking.
+    fn handle_error(&mut self, error: crate::Error) {
+        if error.is_keystore_locked() {
+            self.unlock_popup
+                .open()
+                .unwrap_or_else(|e| self.fatal_error_popup.set_text(e.to_string()));
+        } else {
+            self.fatal_error_popup.set_text(error.to_string());
+        }
+    }
+
     fn draw(&self, terminal: &mut DefaultTerminal) -> io::Result<Rect> {
         let completed_frame = terminal.draw(|frame| {
             frame.render_widget(self, frame.area());
@@ -233,6 +285,25 @@ impl App {
         self.eth_price_thread = Some(tokio::spawn(async move {
             events::eth_price::watch_eth_price_change(tr_eth_price, shutdown_signal).await
         }));
+
+        // Mining uses rayon and blocks, so it gets a thread of its own
+        let tr_mining_jobs = tr.clone();
+        let shutdown_signal = sd.clone();
+        self.mining_jobs_thread = Some(tokio::task::spawn_blocking(move || {
+            events::mining_jobs::watch_mining_jobs(tr_mining_jobs, shutdown_signal)
+        }));
+
+        let tr_auto_lock = tr.clone();
+        let shutdown_signal = sd.clone();
+        let timeout_secs = self.auto_lock_timeout_secs.clone();
+        self.auto_lock_thread = Some(tokio::task::spawn_blocking(move || {
+            events::auto_lock::watch_auto_lock(tr_auto_lock, shutdown_signal, timeout_secs)
+        }));
+
+        // The ABI files are read ahead so that the first tx popup does not wait
+        tokio::task::spawn_blocking(|| {
+            let _ = CalldataDecoder::load();
+        });
     }
 
     fn start_other_threads(&mut self, tr: &mpsc::Sender<Event>, sd: &Arc<AtomicBool>) {
@@ -266,6 +337,13 @@ impl App {
                 .await
             }));
         }
+
+        if self.history_thread.is_none() {
+            let tr_history = tr.clone();
+            self.history_thread = Some(tokio::spawn(async move {
+                events::history::refresh_pending_history(tr_history).await
+            }));
+        }
     }
 
     async fn stop_other_threads(&mut self) {
@@ -278,6 +356,11 @@ impl App {
             thread.abort();
             let _ = thread.await;
         }
+
+        if let Some(thread) = self.history_thread.take() {
+            thread.abort();
+            let _ = thread.await;
+        }
     }
 
     fn set_online(&mut self, tr: &mpsc::Sender<Event>, sd: &Arc<AtomicBool>) {
@@ -302,6 +385,17 @@ impl App {
         if let Some(thread) = self.assets_thread.take() {
             thread.await.unwrap();
         }
+        if let Some(thread) = self.mining_jobs_thread.take() {
+            thread.await.unwrap();
+        }
+        if let Some(thread) = self.auto_lock_thread.take() {
+            thread.await.unwrap();
+        }
+        // A one-off lookup, it does not watch the shutdown signal
+        if let Some(thread) = self.history_thread.take() {
+            thread.abort();
+            let _ = thread.await;
+        }
 
         for page in &mut self.context {
             page.exit_threads().await;
@@ -313,10 +407,13 @@ impl App {
         self.shared_state.testnet_mode = config.testnet_mode;
         self.shared_state.alchemy_api_key_available = config.alchemy_api_key.is_some();
         self.shared_state.current_account = config.current_account;
+        self.shared_state.account_labels = AccountMetadataStore::load()?.labels();
         self.shared_state.developer_mode = config.developer_mode;
         let theme_name = ThemeName::from_str(&config.theme_name)?;
         let theme = Theme::new(theme_name);
         self.shared_state.theme = theme;
+        self.auto_lock_timeout_secs
+            .store(events::auto_lock::timeout_secs(&config), Ordering::Relaxed);
         for page in &mut self.context {
             page.reload(&self.shared_state)?;
         }
@@ -353,6 +450,20 @@ impl App {
     ) -> crate::Result<()> {
         let [_, body_area, _] = self.get_areas(area);
 
+        if let Event::Input(key_event) = &event {
+            if key_event.kind == KeyEventKind::Press {
+                AccountManager::record_activity();
+
+                // Handled before the pages so that it works inside text inputs too
+                if key_event.code == KeyCode::Char('l')
+                    && key_event.modifiers == KeyModifiers::CONTROL
+                {
+                    AccountManager::lock()?;
+                    return Ok(());
+                }
+            }
+        }
+
         #[cfg(feature = "demo")]
         if let Some(demo_timer) = self.demo_timer {
             if demo_timer.elapsed() >= Duration::from_secs(120) {
@@ -369,6 +480,11 @@ impl App {
         let result = if self.fatal_error_popup.is_shown() {
             self.fatal_error_popup
                 .handle_event::<Actions>(event.key_event(), area)
+        } else if self.notification_popup.is_shown() {
+            self.notification_popup
+                .handle_event::<Actions>(event.key_event(), area)
+        } else if self.unlock_popup.is_open() {
+            self.unlock_popup.handle_event(&event)?
         } else if self.invite_popup.is_open() {
             self.invite_popup
                 .handle_event(&event, tr, &self.shared_state)?
@@ -392,41 +508,39 @@ impl App {
         }
 
         match event {
-            Event::Input(key_event) => {
+            Event::Input(key_event) if key_event.kind == KeyEventKind::Press => {
                 // check if we should exit on 'q' press
-                if key_event.kind == KeyEventKind::Press {
-                    #[allow(clippy::single_match)]
-                    match key_event.code {
-                        KeyCode::Char(char) => {
-                            // TODO can we quit using q as well?
-                            // if char == 'q' && self.navigation.text_input().is_none() {
-                            //     self.exit = true;
-                            // }
-                            if char == 'c' && key_event.modifiers == KeyModifiers::CONTROL {
-                                self.exit = true;
-                            }
-                            if char == 'r' && key_event.modifiers == KeyModifiers::CONTROL {
-                                self.fatal_error_popup.set_text("test error".to_string());
-                            }
-                            if char == 't' && key_event.modifiers == KeyModifiers::CONTROL {
-                                self.context.push(Page::Trade(TradePage::default()));
-                            }
+                #[allow(clippy::single_match)]
+                match key_event.code {
+                    KeyCode::Char(char) => {
+                        // TODO can we quit using q as well?
+                        // if char == 'q' && self.navigation.text_input().is_none() {
+                        //     self.exit = true;
+                        // }
+                        if char == 'c' && key_event.modifiers == KeyModifiers::CONTROL {
+                            self.exit = true;
                         }
-                        KeyCode::Esc => {
-                            if self.fatal_error_popup.is_shown() {
-                                self.fatal_error_popup.clear();
-                            } else if !esc_ignores {
-                                let page = self.context.pop();
-                                if let Some(mut page) = page {
-                                    page.exit_threads().await;
-                                }
-                                if self.context.is_empty() {
-                                    self.exit = true;
-                                }
+                        if char == 'r' && key_event.modifiers == KeyModifiers::CONTROL {
+                            self.fatal_error_popup.set_text("test error".to_string());
+                        }
+                        if char == 't' && key_event.modifiers == KeyModifiers::CONTROL {
+                            self.context.push(Page::Trade(TradePage::default()));
+                        }
+                    }
+                    KeyCode::Esc => {
+                        if self.fatal_error_popup.is_shown() {
+                            self.fatal_error_popup.clear();
+                        } else if !esc_ignores {
+                            let page = self.context.pop();
+                            if let Some(mut page) = page {
+                                page.exit_threads().await;
+                            }
+                            if self.context.is_empty() {
+                                self.exit = true;
                             }
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
             }
 
@@ -458,10 +572,8 @@ impl App {
                     .assets_mut()?
                     .update_assets(wallet_address, assets)?;
             }
-            Event::AssetsUpdateError(error, silence_error) => {
-                if !silence_error {
-                    self.fatal_error_popup.set_text(format!("{error:#?}"));
-                }
+            Event::AssetsUpdateError(error, silence_error) if !silence_error => {
+                self.fatal_error_popup.set_text(format!("{error:#?}"));
             }
 
             Event::HeliosUpdate {
@@ -487,6 +599,10 @@ impl App {
                 self.fatal_error_popup.set_text(format!("{error:#?}"));
             }
 
+            Event::HistoryUpdateError(error) => {
+                self.fatal_error_popup.set_text(error);
+            }
+
             // Candles API
             Event::CandlesUpdateError(error) => {
                 self.fatal_error_popup.set_text(format!("{error}"));
@@ -494,6 +610,8 @@ impl App {
 
             // Transaction API
             Event::TxError(error) => self.fatal_error_popup.set_text(error),
+            Event::SafeInfoError(error) => self.fatal_error_popup.set_text(error),
+            Event::DelegationError(error) => self.fatal_error_popup.set_text(error),
 
             Event::WalletConnectError(_, error) => {
                 self.fatal_error_popup.set_text(error);
@@ -502,6 +620,34 @@ impl App {
             Event::InviteError(error) => {
                 self.fatal_error_popup.set_text(error);
             }
+
+            Event::MiningJobUpdate(job) => {
+                if let Some(result) = job.result {
+                    let text = match result {
+                        MiningJobResult::Account { address } => {
+                            self.reload()?;
+                            format!(
+                                "Vanity mining job #{} found the account {address}, it has been added to your accounts.",
+                                job.id
+                            )
+                        }
+                        MiningJobResult::Salt { salt, contract } => format!(
+                            "Vanity mining job #{} found the salt {salt} for the contract address {contract}.",
+                            job.id
+                        ),
+                    };
+                    self.notification_popup.set_text(text);
+                }
+            }
+            Event::MiningJobNeedsUnlock => {
+                self.unlock_popup.open()?;
+            }
+            Event::MiningJobError(error) => {
+                self.fatal_error_popup.set_text(error);
+            }
+            Event::AutoLockError(error) => {
+                self.fatal_error_popup.set_text(error);
+            }
             _ => {}
         };
 
@@ -611,6 +757,19 @@ impl Widget for &App {
                     MainMenuItem::SendMessage => {
                         Page::Text(TextPage::new("Send onchain message to someone".to_string()))
                     }
+                    MainMenuItem::History => Page::Text(TextPage::new(
+                        "Transactions sent and messages signed with gm".to_string(),
+                    )),
+                    MainMenuItem::TxQueue => Page::Text(TextPage::new(
+                        "Review queued transactions and sign them all at once".to_string(),
+                    )),
+                    MainMenuItem::OfflineSign => Page::Text(TextPage::new(
+                        "Sign a transaction without RPC access to broadcast it elsewhere"
+                            .to_string(),
+                    )),
+                    MainMenuItem::BroadcastRawTx => Page::Text(TextPage::new(
+                        "Review and submit a transaction signed offline".to_string(),
+                    )),
                     MainMenuItem::DevKeyInput => Page::DevKeyCapture(DevKeyCapturePage::default()),
                     MainMenuItem::Shell => Page::Shell(ShellPage::default()),
                 };
@@ -628,12 +787,19 @@ impl Widget for &App {
             Footer {
                 exit: &self.exit,
                 is_main_menu: &page.is_main_menu(),
+                // The macOS keychain authenticates every access by itself
+                lockable: &(!cfg!(target_os = "macos") && AccountManager::is_unlocked()),
             }
             .render(footer_area, buf, &self.shared_state.theme);
         }
 
         self.invite_popup.render(area, buf, &self.shared_state);
 
+        self.notification_popup
+            .render(area, buf, &self.shared_state.theme.popup());
+
+        self.unlock_popup.render(area, buf, &self.shared_state);
+
         self.fatal_error_popup
             .render(area, buf, &self.shared_state.theme.error_popup());
 
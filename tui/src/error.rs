@@ -1,6 +1,6 @@
 use std::io;
 
-use alloy::{primitives::Address, rpc::types::TransactionRequest};
+use alloy::primitives::Address;
 use serde_json::Value;
 use walletconnect_sdk::wc_message::WcMessage;
 
@@ -48,12 +48,6 @@ pub enum Error {
     #[error("EIP-712 Typed Data is missing field: {0}.")]
     TypedDataMissingField(String),
 
-    #[error("Transaction type is not specified in the request: {0:?}.")]
-    TxTypeNotSpecified(Box<TransactionRequest>),
-
-    #[error("Transaction type is not EIP-1559.")]
-    TxTypeIsNotEip1559,
-
     #[error("WalletConnect Session request not found at index {0}, num requests: {1}.")]
     SessionRequestNotFound(usize, usize),
 
@@ -75,6 +69,15 @@ pub enum Error {
     #[error("Transmitter 2 channel not created.")]
     Transmitter2NotCreated,
 
+    #[error("None of the owners of the Safe {0} are stored in gm, add an owner to sign for it.")]
+    NoLocalSafeOwner(Address),
+
+    #[error("Safe transaction has {confirmations} of the {threshold} confirmations needed.")]
+    SafeThresholdNotMet {
+        confirmations: usize,
+        threshold: usize,
+    },
+
     #[error("Poisoned lock, please restart gm.")]
     Poisoned(String),
 
@@ -177,6 +180,13 @@ pub enum Error {
 }
 
 impl Error {
+    pub fn is_keystore_locked(&self) -> bool {
+        match self {
+            Self::UtilsError(error) => error.is_keystore_locked(),
+            _ => false,
+        }
+    }
+
     pub fn is_connect_reqwest(&self) -> bool {
         match self {
             Self::UtilsError(error) => error.is_connect(),
@@ -1,16 +1,22 @@
-use std::time::Duration;
-
 use alloy::{
-    primitives::Address,
-    signers::{k256::ecdsa::SigningKey, Signature},
+    consensus::TxType,
+    primitives::{Address, B256},
+    rpc::types::TransactionRequest,
+    signers::Signature,
 };
 use gm_ratatui_extra::candle_chart::{Candle, Interval};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 use walletconnect_sdk::wc_message::WcMessage;
 
 use gm_utils::{
+    account::mining_jobs::MiningJob,
     assets::{Asset, LightClientVerification, TokenAddress},
     error::UtilsError,
+    gas::{FeePresets, GasSettings},
+    receipt::ReceiptSummary,
+    recipient::RecipientRisk,
+    safe::SafeInfo,
+    simulate::Simulation,
 };
 
 use crate::pages::{
@@ -21,9 +27,12 @@ use crate::pages::{
 };
 
 pub mod assets;
+pub mod auto_lock;
 pub mod eth_price;
 pub mod helios;
+pub mod history;
 pub mod input;
+pub mod mining_jobs;
 pub mod recent_addresses;
 
 #[derive(Debug)]
@@ -38,7 +47,13 @@ pub enum Event {
 
     HashRateResult(f64),
     HashRateError(String),
-    VanityResult(SigningKey, u64, Duration),
+
+    MiningJobUpdate(MiningJob),
+    MiningJobNeedsUnlock,
+    MiningJobError(String),
+
+    KeystoreAutoLocked,
+    AutoLockError(String),
 
     AssetsUpdate(Address, Vec<Asset>),
     AssetsUpdateError(gm_utils::Error, bool), // bool - whether to silence the error
@@ -46,11 +61,44 @@ pub enum Event {
     RecentAddressesUpdate(Vec<Address>),
     RecentAddressesUpdateError(crate::Error),
 
+    HistoryUpdate,
+    HistoryUpdateError(String),
+
     CandlesUpdate(Vec<Candle>, Interval),
     CandlesUpdateError(UtilsError),
 
+    TxSubmitted(Box<TransactionRequest>),
     TxUpdate(TxStatus),
     TxError(String),
+    TxReplaceError(String),
+    /// The status of a sent transaction could not be checked, the watch
+    /// keeps trying.
+    TxWatchError(String),
+    TxGasEstimate(u64, FeePresets, TxType),
+    TxGasEstimateError(String),
+    TxSimulation(Simulation),
+    TxSimulationError(String),
+    TxReceipt(Box<ReceiptSummary>),
+    TxReceiptError(String),
+    TxRecipientRisks(Vec<RecipientRisk>),
+    TxRecipientRisksError(String),
+
+    TxQueueGas(u32, GasSettings),
+    TxQueueGasError(u32, String),
+
+    RawTxBroadcast(String, B256), // network name and hash
+    RawTxBroadcastError(String),
+
+    SafeInfoUpdate(Address, SafeInfo),
+    SafeInfoError(String),
+
+    DelegationUpdate {
+        account: Address,
+        network: String,
+        delegate: Option<Address>,
+        nonce: u64,
+    },
+    DelegationError(String),
 
     SignResult(Signature),
     SignError(String),
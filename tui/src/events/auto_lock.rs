@@ -0,0 +1,57 @@
+use gm_utils::{account::AccountManager, config::Config};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use super::Event;
+
+/// Idle timeout in seconds as shared with the auto lock thread, zero when auto
+/// lock is off.
+pub fn timeout_secs(config: &Config) -> u64 {
+    config
+        .auto_lock_timeout()
+        .map_or(0, |timeout| timeout.as_secs())
+}
+
+/// Locks the key store after the idle timeout, user input is recorded as
+/// activity by the app. The app updates `timeout_secs` when the config changes.
+pub fn watch_auto_lock(
+    transmitter: Sender<Event>,
+    shutdown_signal: Arc<AtomicBool>,
+    timeout_secs: Arc<AtomicU64>,
+) {
+    let check_interval_milli = 1000;
+    let thread_sleep_duration_milli = 100;
+
+    let mut counter = 0;
+    while !shutdown_signal.load(Ordering::Relaxed) {
+        if counter >= check_interval_milli {
+            match check(timeout_secs.load(Ordering::Relaxed)) {
+                Ok(true) => {
+                    let _ = transmitter.send(Event::KeystoreAutoLocked);
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    let _ = transmitter.send(Event::AutoLockError(error.to_string()));
+                }
+            }
+            counter = 0;
+        }
+
+        counter += thread_sleep_duration_milli;
+        thread::sleep(Duration::from_millis(thread_sleep_duration_milli));
+    }
+}
+
+fn check(timeout_secs: u64) -> crate::Result<bool> {
+    match timeout_secs {
+        0 => Ok(false),
+        secs => Ok(AccountManager::lock_if_idle(Duration::from_secs(secs))?),
+    }
+}
@@ -0,0 +1,25 @@
+use std::sync::mpsc::Sender;
+
+use gm_utils::history;
+
+use super::Event;
+
+/// Updates the status of the transactions left pending by an earlier run,
+/// once since they only need to be looked up at startup.
+pub async fn refresh_pending_history(transmitter: Sender<Event>) {
+    let errors = match history::refresh_pending().await {
+        Ok((updated, errors)) => {
+            if updated > 0 {
+                let _ = transmitter.send(Event::HistoryUpdate);
+            }
+            errors
+        }
+        Err(error) => vec![error],
+    };
+    if let Some(error) = errors.first() {
+        let _ = transmitter.send(Event::HistoryUpdateError(format!(
+            "{} pending transaction(s) could not be looked up: {error}",
+            errors.len()
+        )));
+    }
+}
@@ -0,0 +1,78 @@
+use gm_utils::{
+    account::{mining_jobs::MiningJobStore, AccountManager},
+    disk_storage::DiskStorageInterface,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use super::Event;
+
+/// Progress is saved after every chunk, so at most this much work is lost
+/// when the app is killed.
+const CHUNK_DURATION: Duration = Duration::from_secs(5);
+
+pub fn watch_mining_jobs(transmitter: Sender<Event>, shutdown_signal: Arc<AtomicBool>) {
+    // Checking for new jobs is cheap, but a failing job should not be retried
+    // in a tight loop.
+    let idle_sleep_milli = 500;
+    let error_sleep_milli = 10000;
+    let thread_sleep_duration_milli = 100;
+
+    while !shutdown_signal.load(Ordering::Relaxed) {
+        let sleep_milli = match run_chunk(&transmitter, &shutdown_signal) {
+            Ok(true) => 0,
+            Ok(false) => idle_sleep_milli,
+            Err(error) => {
+                let _ = transmitter.send(Event::MiningJobError(error.to_string()));
+                error_sleep_milli
+            }
+        };
+
+        let mut counter = 0;
+        while counter < sleep_milli && !shutdown_signal.load(Ordering::Relaxed) {
+            counter += thread_sleep_duration_milli;
+            thread::sleep(Duration::from_millis(thread_sleep_duration_milli));
+        }
+    }
+}
+
+/// Mines the next job for a while, returns false if there is no job to mine.
+fn run_chunk(
+    transmitter: &Sender<Event>,
+    shutdown_signal: &Arc<AtomicBool>,
+) -> crate::Result<bool> {
+    let mut store = MiningJobStore::load()?;
+    let unlocked = AccountManager::is_unlocked();
+    // Keys found while the key store was locked wait for it to be unlocked
+    if unlocked && store.has_pending_keys() {
+        for job in store.store_pending_keys()? {
+            let _ = transmitter.send(Event::MiningJobUpdate(job));
+        }
+    }
+    let Some(job) = store.next_job(unlocked).cloned() else {
+        return Ok(false);
+    };
+
+    let (found, attempts, elapsed) = job.mine_chunk(CHUNK_DURATION, shutdown_signal.clone())?;
+    let mut store = MiningJobStore::load()?;
+    let result = match &found {
+        Some(found) => job.complete(found, &mut store)?,
+        None => None,
+    };
+    if found.is_some() && result.is_none() {
+        // The key store got locked while mining, the user is asked to unlock it
+        let _ = transmitter.send(Event::MiningJobNeedsUnlock);
+    }
+
+    if let Some(job) = store.record_progress(job.id, attempts, elapsed, result)? {
+        let _ = transmitter.send(Event::MiningJobUpdate(job));
+    }
+    Ok(true)
+}
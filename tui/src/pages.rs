@@ -1,21 +1,39 @@
 use std::sync::{atomic::AtomicBool, mpsc, Arc};
 
+use alloy::primitives::Address;
+use gm_utils::account::AccountManager;
+
 use account::AccountPage;
 use account_create::AccountCreatePage;
+use account_create_mnemonic::AccountCreateMnemonicPage;
+use account_edit::AccountEditPage;
+use account_export::AccountExportPage;
 use account_import::AccountImportPage;
+use account_safe_add::AccountSafeAddPage;
+use account_slip39_backup::AccountSlip39BackupPage;
+use account_slip39_recover::AccountSlip39RecoverPage;
+use account_watch_only::AccountWatchOnlyPage;
 use address_book::AddressBookPage;
 use address_book_create::AddressBookCreatePage;
 use address_book_display::AddressBookDisplayPage;
 use asset_transfer::AssetTransferPage;
 use assets::AssetsPage;
+use broadcast::BroadcastPage;
 use complete_setup::CompleteSetupPage;
 use config::ConfigPage;
+use create2_salt::Create2SaltPage;
+use delegation::DelegationPage;
 use dev_key_capture::DevKeyCapturePage;
+use history::HistoryPage;
 use main_menu::{MainMenuItem, MainMenuPage};
+use mining_jobs::MiningJobsPage;
+use offline_sign::OfflineSignPage;
+use safe::SafePage;
 use send_message::SendMessagePage;
 use sign_message::SignMessagePage;
 use text::TextPage;
 use trade::TradePage;
+use tx_queue::TxQueuePage;
 use walletconnect::WalletConnectPage;
 
 use crate::{
@@ -30,20 +48,34 @@ use crate::{
 
 pub mod account;
 pub mod account_create;
+pub mod account_create_mnemonic;
+pub mod account_edit;
+pub mod account_export;
 pub mod account_import;
+pub mod account_safe_add;
+pub mod account_slip39_backup;
+pub mod account_slip39_recover;
+pub mod account_watch_only;
 pub mod address_book;
 pub mod address_book_create;
 pub mod address_book_display;
 pub mod asset_transfer;
 pub mod assets;
+pub mod broadcast;
 pub mod complete_setup;
 pub mod config;
+pub mod create2_salt;
+pub mod delegation;
 pub mod dev_key_capture;
 pub mod footer;
+pub mod history;
 pub mod invite_popup;
 pub mod main_menu;
+pub mod mining_jobs;
 pub mod network;
 pub mod network_create;
+pub mod offline_sign;
+pub mod safe;
 pub mod send_message;
 pub mod shell;
 pub mod sign_message;
@@ -55,8 +87,23 @@ pub mod token;
 pub mod token_create;
 pub mod trade;
 pub mod tx_popup;
+pub mod tx_queue;
+pub mod unlock_popup;
 pub mod walletconnect;
 
+/// Rejects the request with `reject` when the account is watch-only, the
+/// returned error tells the user why nothing was signed.
+pub(crate) fn reject_if_watch_only(
+    account: &Address,
+    reject: impl FnOnce() -> crate::Result<()>,
+) -> crate::Result<()> {
+    if AccountManager::is_watch_only(account)? {
+        reject()?;
+        return Err(gm_utils::Error::WatchOnlyAccount(*account).into());
+    }
+    Ok(())
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum Page {
@@ -65,11 +112,25 @@ pub enum Page {
 
     Account(AccountPage),
     AccountCreate(AccountCreatePage),
+    AccountCreateMnemonic(AccountCreateMnemonicPage),
+    AccountEdit(AccountEditPage),
+    AccountExport(AccountExportPage),
     AccountImport(AccountImportPage),
+    AccountSlip39Backup(AccountSlip39BackupPage),
+    AccountSlip39Recover(AccountSlip39RecoverPage),
+    AccountSafeAdd(AccountSafeAddPage),
+    AccountWatchOnly(AccountWatchOnlyPage),
+    Create2Salt(Create2SaltPage),
+    Delegation(DelegationPage),
+    MiningJobs(MiningJobsPage),
+    Safe(SafePage),
 
     AddressBook(AddressBookPage),
     AddressBookCreate(AddressBookCreatePage),
     AddressBookDisplay(AddressBookDisplayPage),
+    History(HistoryPage),
+    TxQueue(TxQueuePage),
+    OfflineSign(OfflineSignPage),
 
     Network(NetworkPage),
     NetworkCreate(NetworkCreatePage),
@@ -78,6 +139,7 @@ pub enum Page {
 
     Assets(AssetsPage),
     AssetTransfer(AssetTransferPage),
+    Broadcast(BroadcastPage),
 
     Config(ConfigPage),
     SendMessage(SendMessagePage),
@@ -133,10 +195,24 @@ impl Component for Page {
             Page::AddressBook(page) => page.set_focus(focus),
             Page::AddressBookCreate(page) => page.set_focus(focus),
             Page::AddressBookDisplay(page) => page.set_focus(focus),
+            Page::History(page) => page.set_focus(focus),
+            Page::TxQueue(page) => page.set_focus(focus),
+            Page::OfflineSign(page) => page.set_focus(focus),
 
             Page::Account(page) => page.set_focus(focus),
             Page::AccountCreate(page) => page.set_focus(focus),
+            Page::AccountCreateMnemonic(page) => page.set_focus(focus),
+            Page::AccountEdit(page) => page.set_focus(focus),
+            Page::AccountExport(page) => page.set_focus(focus),
             Page::AccountImport(page) => page.set_focus(focus),
+            Page::AccountSlip39Backup(page) => page.set_focus(focus),
+            Page::AccountSlip39Recover(page) => page.set_focus(focus),
+            Page::AccountSafeAdd(page) => page.set_focus(focus),
+            Page::AccountWatchOnly(page) => page.set_focus(focus),
+            Page::Create2Salt(page) => page.set_focus(focus),
+            Page::Delegation(page) => page.set_focus(focus),
+            Page::MiningJobs(page) => page.set_focus(focus),
+            Page::Safe(page) => page.set_focus(focus),
 
             Page::Network(page) => page.set_focus(focus),
             Page::NetworkCreate(page) => page.set_focus(focus),
@@ -145,6 +221,7 @@ impl Component for Page {
 
             Page::Assets(page) => page.set_focus(focus),
             Page::AssetTransfer(page) => page.set_focus(focus),
+            Page::Broadcast(page) => page.set_focus(focus),
 
             Page::Config(page) => page.set_focus(focus),
             Page::SendMessage(page) => page.set_focus(focus),
@@ -169,6 +246,9 @@ impl Component for Page {
             Page::AddressBook(page) => page.exit_threads().await,
             Page::AddressBookCreate(page) => page.exit_threads().await,
             Page::AddressBookDisplay(page) => page.exit_threads().await,
+            Page::History(page) => page.exit_threads().await,
+            Page::TxQueue(page) => page.exit_threads().await,
+            Page::OfflineSign(page) => page.exit_threads().await,
 
             Page::Network(page) => page.exit_threads().await,
             Page::NetworkCreate(page) => page.exit_threads().await,
@@ -177,10 +257,22 @@ impl Component for Page {
 
             Page::Account(page) => page.exit_threads().await,
             Page::AccountCreate(page) => page.exit_threads().await,
+            Page::AccountCreateMnemonic(page) => page.exit_threads().await,
+            Page::AccountEdit(page) => page.exit_threads().await,
+            Page::AccountExport(page) => page.exit_threads().await,
             Page::AccountImport(page) => page.exit_threads().await,
+            Page::AccountSlip39Backup(page) => page.exit_threads().await,
+            Page::AccountSlip39Recover(page) => page.exit_threads().await,
+            Page::AccountSafeAdd(page) => page.exit_threads().await,
+            Page::AccountWatchOnly(page) => page.exit_threads().await,
+            Page::Create2Salt(page) => page.exit_threads().await,
+            Page::Delegation(page) => page.exit_threads().await,
+            Page::MiningJobs(page) => page.exit_threads().await,
+            Page::Safe(page) => page.exit_threads().await,
 
             Page::Assets(page) => page.exit_threads().await,
             Page::AssetTransfer(page) => page.exit_threads().await,
+            Page::Broadcast(page) => page.exit_threads().await,
 
             Page::Config(page) => page.exit_threads().await,
             Page::SendMessage(page) => page.exit_threads().await,
@@ -205,6 +297,9 @@ impl Component for Page {
             Page::AddressBook(page) => page.reload(ss),
             Page::AddressBookCreate(page) => page.reload(ss),
             Page::AddressBookDisplay(page) => page.reload(ss),
+            Page::History(page) => page.reload(ss),
+            Page::TxQueue(page) => page.reload(ss),
+            Page::OfflineSign(page) => page.reload(ss),
 
             Page::Network(page) => page.reload(ss),
             Page::NetworkCreate(page) => page.reload(ss),
@@ -213,10 +308,22 @@ impl Component for Page {
 
             Page::Account(page) => page.reload(ss),
             Page::AccountCreate(page) => page.reload(ss),
+            Page::AccountCreateMnemonic(page) => page.reload(ss),
+            Page::AccountEdit(page) => page.reload(ss),
+            Page::AccountExport(page) => page.reload(ss),
             Page::AccountImport(page) => page.reload(ss),
+            Page::AccountSlip39Backup(page) => page.reload(ss),
+            Page::AccountSlip39Recover(page) => page.reload(ss),
+            Page::AccountSafeAdd(page) => page.reload(ss),
+            Page::AccountWatchOnly(page) => page.reload(ss),
+            Page::Create2Salt(page) => page.reload(ss),
+            Page::Delegation(page) => page.reload(ss),
+            Page::MiningJobs(page) => page.reload(ss),
+            Page::Safe(page) => page.reload(ss),
 
             Page::Assets(page) => page.reload(ss),
             Page::AssetTransfer(page) => page.reload(ss),
+            Page::Broadcast(page) => page.reload(ss),
 
             Page::Config(page) => page.reload(ss),
             Page::SendMessage(page) => page.reload(ss),
@@ -248,6 +355,9 @@ impl Component for Page {
             Page::AddressBook(page) => page.handle_event(event, area, tr, sd, ss),
             Page::AddressBookCreate(page) => page.handle_event(event, area, tr, sd, ss),
             Page::AddressBookDisplay(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::History(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::TxQueue(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::OfflineSign(page) => page.handle_event(event, area, tr, sd, ss),
 
             Page::Network(page) => page.handle_event(event, area, tr, sd, ss),
             Page::NetworkCreate(page) => page.handle_event(event, area, tr, sd, ss),
@@ -256,10 +366,22 @@ impl Component for Page {
 
             Page::Account(page) => page.handle_event(event, area, tr, sd, ss),
             Page::AccountCreate(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::AccountCreateMnemonic(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::AccountEdit(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::AccountExport(page) => page.handle_event(event, area, tr, sd, ss),
             Page::AccountImport(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::AccountSlip39Backup(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::AccountSlip39Recover(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::AccountSafeAdd(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::AccountWatchOnly(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::Create2Salt(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::Delegation(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::MiningJobs(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::Safe(page) => page.handle_event(event, area, tr, sd, ss),
 
             Page::Assets(page) => page.handle_event(event, area, tr, sd, ss),
             Page::AssetTransfer(page) => page.handle_event(event, area, tr, sd, ss),
+            Page::Broadcast(page) => page.handle_event(event, area, tr, sd, ss),
 
             Page::Config(page) => page.handle_event(event, area, tr, sd, ss),
             Page::SendMessage(page) => page.handle_event(event, area, tr, sd, ss),
@@ -292,6 +414,9 @@ impl Component for Page {
             Page::AddressBook(page) => page.render_component(area, buf, shared_state),
             Page::AddressBookCreate(page) => page.render_component(area, buf, shared_state),
             Page::AddressBookDisplay(page) => page.render_component(area, buf, shared_state),
+            Page::History(page) => page.render_component(area, buf, shared_state),
+            Page::TxQueue(page) => page.render_component(area, buf, shared_state),
+            Page::OfflineSign(page) => page.render_component(area, buf, shared_state),
 
             Page::Network(page) => page.render_component(area, buf, shared_state),
             Page::NetworkCreate(page) => page.render_component(area, buf, shared_state),
@@ -300,10 +425,22 @@ impl Component for Page {
 
             Page::Account(page) => page.render_component(area, buf, shared_state),
             Page::AccountCreate(page) => page.render_component(area, buf, shared_state),
+            Page::AccountCreateMnemonic(page) => page.render_component(area, buf, shared_state),
+            Page::AccountEdit(page) => page.render_component(area, buf, shared_state),
+            Page::AccountExport(page) => page.render_component(area, buf, shared_state),
             Page::AccountImport(page) => page.render_component(area, buf, shared_state),
+            Page::AccountSlip39Backup(page) => page.render_component(area, buf, shared_state),
+            Page::AccountSlip39Recover(page) => page.render_component(area, buf, shared_state),
+            Page::AccountSafeAdd(page) => page.render_component(area, buf, shared_state),
+            Page::AccountWatchOnly(page) => page.render_component(area, buf, shared_state),
+            Page::Create2Salt(page) => page.render_component(area, buf, shared_state),
+            Page::Delegation(page) => page.render_component(area, buf, shared_state),
+            Page::MiningJobs(page) => page.render_component(area, buf, shared_state),
+            Page::Safe(page) => page.render_component(area, buf, shared_state),
 
             Page::Assets(page) => page.render_component(area, buf, shared_state),
             Page::AssetTransfer(page) => page.render_component(area, buf, shared_state),
+            Page::Broadcast(page) => page.render_component(area, buf, shared_state),
 
             Page::Config(page) => page.render_component(area, buf, shared_state),
             Page::SendMessage(page) => page.render_component(area, buf, shared_state),
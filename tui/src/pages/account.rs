@@ -9,34 +9,92 @@ use crate::{
     traits::{Actions, Component},
 };
 use alloy::primitives::Address;
-use gm_ratatui_extra::{cursor::Cursor, select::Select, thematize::Thematize};
+use gm_ratatui_extra::{cursor::Cursor, extensions::RectExt, select::Select, thematize::Thematize};
 use gm_utils::{
-    account::{AccountManager, AccountUtils},
+    account::{
+        metadata::{AccountMetadataStore, LabeledAddress},
+        AccountManager, AccountUtils,
+    },
     config::Config,
     disk_storage::DiskStorageInterface,
 };
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{KeyCode, KeyEventKind},
+    crossterm::event::{KeyCode, KeyEventKind, KeyModifiers},
     layout::Rect,
+    text::Line,
     widgets::Widget,
 };
 
-use super::{account_create::AccountCreatePage, account_import::AccountImportPage, Page};
+use super::{
+    account_create::AccountCreatePage, account_create_mnemonic::AccountCreateMnemonicPage,
+    account_edit::AccountEditPage, account_export::AccountExportPage,
+    account_import::AccountImportPage, account_safe_add::AccountSafeAddPage,
+    account_slip39_backup::AccountSlip39BackupPage,
+    account_slip39_recover::AccountSlip39RecoverPage, account_watch_only::AccountWatchOnlyPage,
+    create2_salt::Create2SaltPage, delegation::DelegationPage, mining_jobs::MiningJobsPage,
+    safe::SafePage, text::TextPage, Page,
+};
 
 #[derive(Debug)]
 enum AccountSelect {
     Create,
+    CreateMnemonic,
     Import,
-    Existing(Address),
+    WatchOnly,
+    AddSafe,
+    Slip39Recover,
+    Create2Salt,
+    MiningJobs,
+    DeriveNext(Address),
+    Export(Address),
+    Slip39Backup(Address),
+    Delegation(Address),
+    SafeTransactions(Address, String),
+    Existing {
+        address: Address,
+        label: Option<String>,
+        watch_only: bool,
+        safe: bool,
+    },
 }
 
 impl Display for AccountSelect {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AccountSelect::Create => write!(f, "Create new wallet"),
+            AccountSelect::CreateMnemonic => write!(f, "Create new mnemonic wallet"),
             AccountSelect::Import => write!(f, "Import existing wallet"),
-            AccountSelect::Existing(address) => write!(f, "{address}"),
+            AccountSelect::WatchOnly => write!(f, "Add watch-only address"),
+            AccountSelect::AddSafe => write!(f, "Add Safe multisig account"),
+            AccountSelect::Slip39Recover => write!(f, "Recover wallet from SLIP-39 shares"),
+            AccountSelect::Create2Salt => write!(f, "Mine CREATE2 salt for vanity contract"),
+            AccountSelect::MiningJobs => write!(f, "Vanity mining jobs"),
+            AccountSelect::DeriveNext(_) => write!(f, "Derive next account from current wallet"),
+            AccountSelect::Export(_) => write!(f, "Export current wallet as keystore"),
+            AccountSelect::Slip39Backup(_) => {
+                write!(f, "Back up current wallet as SLIP-39 shares")
+            }
+            AccountSelect::Delegation(_) => write!(f, "EIP-7702 delegation of current wallet"),
+            AccountSelect::SafeTransactions(_, _) => write!(f, "Transactions of current Safe"),
+            AccountSelect::Existing {
+                address,
+                label,
+                watch_only,
+                safe,
+            } => {
+                let address = LabeledAddress {
+                    address,
+                    label: label.as_ref(),
+                };
+                if *watch_only {
+                    write!(f, "{address} [watch-only]")
+                } else if *safe {
+                    write!(f, "{address} [Safe]")
+                } else {
+                    write!(f, "{address}")
+                }
+            }
         }
     }
 }
@@ -50,11 +108,44 @@ pub struct AccountPage {
 
 impl AccountPage {
     pub fn new() -> crate::Result<Self> {
-        let mut list = vec![AccountSelect::Create, AccountSelect::Import];
+        let mut list = vec![
+            AccountSelect::Create,
+            AccountSelect::CreateMnemonic,
+            AccountSelect::Import,
+            AccountSelect::WatchOnly,
+            AccountSelect::AddSafe,
+            AccountSelect::Slip39Recover,
+            AccountSelect::Create2Salt,
+            AccountSelect::MiningJobs,
+        ];
+        let metadata = AccountMetadataStore::load()?;
+        if let Some(current_account) = Config::load()?.current_account {
+            if let Some(network) = metadata.safe_network(&current_account) {
+                list.push(AccountSelect::SafeTransactions(
+                    current_account,
+                    network.clone(),
+                ));
+            } else if !metadata.is_watch_only(&current_account) {
+                let mnemonic = AccountManager::is_mnemonic(&current_account)?;
+                if mnemonic {
+                    list.push(AccountSelect::DeriveNext(current_account));
+                }
+                list.push(AccountSelect::Export(current_account));
+                if mnemonic {
+                    list.push(AccountSelect::Slip39Backup(current_account));
+                }
+                list.push(AccountSelect::Delegation(current_account));
+            }
+        }
         list.extend(
             AccountManager::get_account_list()?
                 .into_iter()
-                .map(AccountSelect::Existing)
+                .map(|address| AccountSelect::Existing {
+                    address,
+                    label: metadata.label(&address).cloned(),
+                    watch_only: metadata.is_watch_only(&address),
+                    safe: metadata.safe_network(&address).is_some(),
+                })
                 .collect::<Vec<_>>(),
         );
         Ok(Self {
@@ -82,28 +173,129 @@ impl Component for AccountPage {
         _area: Rect,
         transmitter: &mpsc::Sender<Event>,
         _shutdown_signal: &Arc<AtomicBool>,
-        _shared_state: &SharedState,
+        shared_state: &SharedState,
     ) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+
+        // Shift + Up/Down moves the selected account instead of the cursor
+        if let Event::Input(key_event) = event {
+            if key_event.kind == KeyEventKind::Press
+                && key_event.modifiers.contains(KeyModifiers::SHIFT)
+                && matches!(key_event.code, KeyCode::Up | KeyCode::Down)
+            {
+                if let AccountSelect::Existing { address, .. } = &self.list[self.cursor.current] {
+                    let up = key_event.code == KeyCode::Up;
+                    let target = if up {
+                        self.cursor.current.checked_sub(1)
+                    } else {
+                        Some(self.cursor.current + 1)
+                    };
+                    if let Some(target) = target.filter(|target| {
+                        matches!(self.list.get(*target), Some(AccountSelect::Existing { .. }))
+                    }) {
+                        AccountManager::move_account(address, up)?;
+                        self.list.swap(self.cursor.current, target);
+                        self.cursor.current = target;
+                    }
+                }
+                return Ok(result);
+            }
+        }
+
         let cursor_max = self.list.len();
         self.cursor.handle(event.key_event(), cursor_max);
 
-        let mut result = Actions::default();
         if let Event::Input(key_event) = event {
             if key_event.kind == KeyEventKind::Press {
-                #[allow(clippy::single_match)]
                 match key_event.code {
+                    KeyCode::Char('e') => {
+                        if let AccountSelect::Existing {
+                            address,
+                            label,
+                            watch_only,
+                            safe,
+                        } = &self.list[self.cursor.current]
+                        {
+                            // Neither watch-only accounts nor Safes have a secret
+                            result
+                                .page_inserts
+                                .push(Page::AccountEdit(AccountEditPage::new(
+                                    *address,
+                                    label.clone(),
+                                    *watch_only || *safe,
+                                )?));
+                        }
+                    }
                     KeyCode::Enter => match &self.list[self.cursor.current] {
                         AccountSelect::Create => {
                             result
                                 .page_inserts
                                 .push(Page::AccountCreate(AccountCreatePage::default()));
                         }
+                        AccountSelect::CreateMnemonic => {
+                            result.page_inserts.push(Page::AccountCreateMnemonic(
+                                AccountCreateMnemonicPage::new()?,
+                            ));
+                        }
                         AccountSelect::Import => {
                             result
                                 .page_inserts
-                                .push(Page::AccountImport(AccountImportPage::default()));
+                                .push(Page::AccountImport(AccountImportPage::new()?));
+                        }
+                        AccountSelect::WatchOnly => {
+                            result
+                                .page_inserts
+                                .push(Page::AccountWatchOnly(AccountWatchOnlyPage::new()?));
+                        }
+                        AccountSelect::AddSafe => {
+                            result
+                                .page_inserts
+                                .push(Page::AccountSafeAdd(AccountSafeAddPage::new()?));
+                        }
+                        AccountSelect::Slip39Recover => {
+                            result
+                                .page_inserts
+                                .push(Page::AccountSlip39Recover(AccountSlip39RecoverPage::new()?));
+                        }
+                        AccountSelect::Create2Salt => {
+                            result
+                                .page_inserts
+                                .push(Page::Create2Salt(Create2SaltPage::new()?));
+                        }
+                        AccountSelect::MiningJobs => {
+                            result
+                                .page_inserts
+                                .push(Page::MiningJobs(MiningJobsPage::new()?));
+                        }
+                        AccountSelect::DeriveNext(address) => {
+                            let (derived, mnemonic) = AccountManager::derive_next_account(address)?;
+                            self.reload(shared_state)?;
+                            result.page_inserts.push(Page::Text(TextPage::new(format!(
+                                "Derived account {derived} at path {}",
+                                mnemonic.derivation_path
+                            ))));
+                        }
+                        AccountSelect::Export(address) => {
+                            result
+                                .page_inserts
+                                .push(Page::AccountExport(AccountExportPage::new(*address)?));
+                        }
+                        AccountSelect::Slip39Backup(address) => {
+                            result.page_inserts.push(Page::AccountSlip39Backup(
+                                AccountSlip39BackupPage::new(*address)?,
+                            ));
+                        }
+                        AccountSelect::Delegation(address) => {
+                            result
+                                .page_inserts
+                                .push(Page::Delegation(DelegationPage::new(*address)?));
+                        }
+                        AccountSelect::SafeTransactions(address, network) => {
+                            result
+                                .page_inserts
+                                .push(Page::Safe(SafePage::new(*address, network)?));
                         }
-                        AccountSelect::Existing(address) => {
+                        AccountSelect::Existing { address, .. } => {
                             let mut config = Config::load()?;
                             config.current_account = Some(*address);
                             config.save()?;
@@ -130,7 +322,12 @@ impl Component for AccountPage {
             focus: self.focus,
             focus_style: shared_state.theme.select_focused(),
         }
-        .render(area, buf);
+        .render(area.margin_down(2), buf);
+
+        if area.height > 2 {
+            Line::from("e: edit, export or delete account, shift + up/down: reorder accounts")
+                .render(area.margin_top(area.height - 1), buf);
+        }
         area
     }
 }
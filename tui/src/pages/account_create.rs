@@ -4,11 +4,11 @@ use std::{
         mpsc, Arc,
     },
     thread::{self, JoinHandle},
-    time::{Duration, Instant},
+    time::Duration,
 };
 
-use alloy::primitives::{address, Address};
-use gm_ratatui_extra::{act::Act, confirm_popup::ConfirmPopup, thematize::Thematize};
+use alloy::primitives::Address;
+use gm_ratatui_extra::thematize::Thematize;
 use ratatui::{
     buffer::Buffer,
     crossterm::event::KeyCode,
@@ -23,7 +23,14 @@ use crate::{
     events::Event,
     traits::{Actions, Component},
 };
-use gm_utils::account::{mine_wallet, AccountManager, AccountUtils};
+use gm_utils::{
+    account::{
+        mining_jobs::{MiningJob, MiningJobResult, MiningJobStore},
+        vanity::{VanityMode, VanityPattern},
+        AccountManager,
+    },
+    disk_storage::DiskStorageInterface,
+};
 
 #[derive(Debug, PartialEq)]
 pub enum HashRateResult {
@@ -35,68 +42,72 @@ pub enum HashRateResult {
 
 #[derive(Debug)]
 pub struct AccountCreatePage {
+    mode: VanityMode,
     cursor: usize,
     mask: [Option<u8>; 40],
+    /// Case required in the checksummed address, `Some(true)` for upper case.
+    case: [Option<bool>; 40],
     hash_rate: HashRateResult,
-    vanity_result: Option<(Address, u64, Duration)>,
     mnemonic_result: Option<Address>,
-    mining: bool,
-    started_mining_at: Instant,
+    /// Background job started from this page, it keeps running after the
+    /// page is left.
+    job: Option<MiningJob>,
 
     exit_signal: Arc<AtomicBool>,
     hash_rate_thread: Option<JoinHandle<()>>,
-    vanity_thread: Option<JoinHandle<()>>,
-
-    exit_popup: ConfirmPopup,
 }
 
 impl Default for AccountCreatePage {
     fn default() -> Self {
+        Self::new(VanityMode::Wallet)
+    }
+}
+
+impl AccountCreatePage {
+    pub fn new(mode: VanityMode) -> Self {
         Self {
+            mode,
             cursor: 0,
             mask: [None; 40],
+            case: [None; 40],
             hash_rate: HashRateResult::None,
-            vanity_result: None,
             mnemonic_result: None,
-            mining: false,
-            started_mining_at: Instant::now(),
+            job: None,
 
             exit_signal: Arc::new(AtomicBool::new(false)),
             hash_rate_thread: None,
-            vanity_thread: None,
-
-            exit_popup: ConfirmPopup::new(
-                "Warning",
-                "Mining will be ended if you go back. You can press ESC to go back or select End. If you want to continue mining you can choose to wait."
-                    .to_string(),
-                "Wait",
-                "End",
-            ),
         }
     }
-}
 
-impl AccountCreatePage {
     pub fn is_mask_empty(&self) -> bool {
         self.mask.iter().all(|&x| x.is_none())
     }
 
-    pub fn mask_count(&self) -> usize {
-        self.mask.iter().filter(|&&x| x.is_some()).count()
+    pub fn pattern(&self) -> VanityPattern {
+        VanityPattern::new(&self.mask, &self.case)
     }
 
-    pub fn mask_a_b(&self) -> (Address, Address) {
-        let mut mask_a = [0; 20];
-        let mut mask_b = [0; 20];
+    fn set_nibble(&mut self, nibble: u8, case: Option<bool>) {
+        self.mask[self.cursor] = Some(nibble);
+        self.case[self.cursor] = case;
+        if self.cursor < self.mask.len() - 1 {
+            self.cursor += 1;
+        }
+    }
 
-        for (i, &b) in self.mask.iter().enumerate() {
-            if let Some(n) = b {
-                mask_a[i / 2] |= n << ((1 - i % 2) * 4);
-                mask_b[i / 2] |= (0xf ^ n) << ((1 - i % 2) * 4);
-            }
+    /// Cycles the letter at the cursor through any case, upper and lower case.
+    fn toggle_case(&mut self) {
+        if self.mask[self.cursor].is_some_and(|n| n >= 10) {
+            self.case[self.cursor] = match self.case[self.cursor] {
+                None => Some(true),
+                Some(true) => Some(false),
+                Some(false) => None,
+            };
         }
+    }
 
-        (Address::from(mask_a), Address::from(mask_b))
+    fn has_job(&self) -> bool {
+        self.job.is_some()
     }
 }
 
@@ -107,109 +118,81 @@ impl Component for AccountCreatePage {
         if let Some(thread) = self.hash_rate_thread.take() {
             thread.join().unwrap();
         }
-        if let Some(thread) = self.vanity_thread.take() {
-            thread.join().unwrap();
-        }
     }
 
     fn handle_event(
         &mut self,
         event: &Event,
-        area: Rect,
+        _area: Rect,
         transmitter: &mpsc::Sender<Event>,
         _shutdown_signal: &Arc<AtomicBool>,
         _shared_state: &SharedState,
     ) -> crate::Result<Actions> {
         let mut result = Actions::default();
 
-        if self.exit_popup.is_open() {
-            let r = self.exit_popup.handle_event(
-                event.key_event(),
-                area,
-                || Ok(()),
-                || -> crate::Result<()> {
-                    result.page_pops += 1;
-                    result.reload = true;
-                    Ok(())
-                },
-            )?;
-            result.merge(r);
-        }
-
         let cursor_max = self.mask.len();
         match event {
-            Event::Input(key_event) => {
-                if self.mining && key_event.code == KeyCode::Esc {
-                    self.exit_popup.open();
-                } else {
-                    // Handle input only if not mining
-                    match key_event.code {
-                        KeyCode::Right => {
-                            self.cursor = (self.cursor + 1) % cursor_max;
-                        }
-                        KeyCode::Left => {
-                            self.cursor = (self.cursor + cursor_max - 1) % cursor_max;
+            // The mask cannot be edited once the job is started
+            Event::Input(key_event) if !self.has_job() || key_event.code == KeyCode::Esc => {
+                match key_event.code {
+                    KeyCode::Right => {
+                        self.cursor = (self.cursor + 1) % cursor_max;
+                    }
+                    KeyCode::Left => {
+                        self.cursor = (self.cursor + cursor_max - 1) % cursor_max;
+                    }
+                    KeyCode::Up | KeyCode::Down => self.toggle_case(),
+                    KeyCode::Char(c) => match c {
+                        '0'..='9' => self.set_nibble(c as u8 - b'0', None),
+                        // Lower case letters match any case, upper case
+                        // letters have to be upper case in the checksum
+                        'a'..='f' => self.set_nibble(c as u8 - b'a' + 10, None),
+                        'A'..='F' => self.set_nibble(c as u8 - b'A' + 10, Some(true)),
+                        _ => {}
+                    },
+                    KeyCode::Backspace => {
+                        if self.cursor == 0
+                            || (self.cursor == cursor_max - 1 && self.mask[self.cursor].is_some())
+                        {
+                            self.mask[self.cursor] = None;
+                            self.case[self.cursor] = None;
+                        } else if self.cursor > 0 {
+                            self.mask[self.cursor - 1] = None;
+                            self.case[self.cursor - 1] = None;
+                            self.cursor -= 1;
                         }
-                        KeyCode::Char(c) => match c {
-                            '0'..='9' => {
-                                self.mask[self.cursor] = Some(c as u8 - b'0');
-                                if self.cursor < cursor_max - 1 {
-                                    self.cursor += 1;
-                                }
-                            }
-                            'a'..='f' => {
-                                self.mask[self.cursor] = Some(c as u8 - b'a' + 10);
-                                if self.cursor < cursor_max - 1 {
-                                    self.cursor += 1;
-                                }
-                            }
-                            'A'..='F' => {
-                                self.mask[self.cursor] = Some(c as u8 - b'A' + 10);
-                                if self.cursor < cursor_max - 1 {
-                                    self.cursor += 1;
-                                }
-                            }
-                            _ => {}
-                        },
-                        KeyCode::Backspace => {
-                            if self.cursor == 0
-                                || (self.cursor == cursor_max - 1
-                                    && self.mask[self.cursor].is_some())
-                            {
-                                self.mask[self.cursor] = None;
-                            } else if self.cursor > 0 {
-                                self.mask[self.cursor - 1] = None;
-                                self.cursor -= 1;
-                            }
+                    }
+                    // Switching modes needs a fresh hash rate, so wait for
+                    // the current measurement to finish
+                    KeyCode::Tab if self.hash_rate != HashRateResult::Pending => {
+                        let mode = match self.mode {
+                            VanityMode::Wallet => Some(VanityMode::Deployer),
+                            VanityMode::Deployer => Some(VanityMode::Wallet),
+                            VanityMode::Create2 { .. } => None,
+                        };
+                        if let Some(mode) = mode {
+                            self.mode = mode;
+                            self.hash_rate = HashRateResult::None;
                         }
-                        KeyCode::Enter => {
-                            if self.is_mask_empty() {
-                                let addr = AccountManager::create_mnemonic_wallet()?;
-                                self.mnemonic_result = Some(addr);
-                            }
-
-                            if !self.mining && self.vanity_result.is_none() {
-                                self.mining = true;
-                                self.started_mining_at = Instant::now();
-                                let tr = transmitter.clone();
-                                let (mask_a, mask_b) = self.mask_a_b();
-                                let exit_signal = self.exit_signal.clone();
-                                let vanity_thread = thread::spawn(move || {
-                                    let result = mine_wallet(mask_a, mask_b, None, exit_signal);
-                                    if let Ok((Some(key), counter, duration)) = result {
-                                        tr.send(Event::VanityResult(key, counter, duration))
-                                            .unwrap();
-                                    }
-                                });
-                                self.vanity_thread = Some(vanity_thread);
-                            }
+                    }
+                    KeyCode::Enter => {
+                        // Only a salt is mined in CREATE2 mode, no key is stored
+                        if !matches!(self.mode, VanityMode::Create2 { .. }) {
+                            AccountManager::ensure_unlocked()?;
                         }
-                        KeyCode::Esc => {
-                            // When context goes back to previous page, it should reload state
-                            result.reload = true;
+                        if self.mode == VanityMode::Wallet && self.is_mask_empty() {
+                            let addr = AccountManager::create_mnemonic_wallet()?;
+                            self.mnemonic_result = Some(addr);
+                        } else {
+                            let job = MiningJob::new(self.mode, &self.mask, &self.case);
+                            self.job = Some(MiningJobStore::load()?.add(job)?);
                         }
-                        _ => {}
                     }
+                    KeyCode::Esc => {
+                        // When context goes back to previous page, it should reload state
+                        result.reload = true;
+                    }
+                    _ => {}
                 }
             }
             Event::HashRateResult(hash_rate) => {
@@ -218,26 +201,26 @@ impl Component for AccountCreatePage {
             Event::HashRateError(error) => {
                 self.hash_rate = HashRateResult::Error(error.clone());
             }
-            Event::VanityResult(key, counter, duration) => {
-                let addr = Address::from_private_key(key);
-                AccountManager::store_private_key(&key.to_bytes(), addr)?;
-                self.vanity_result = Some((addr, *counter, *duration));
-                self.hash_rate = HashRateResult::None;
-                self.mining = false;
+            Event::MiningJobUpdate(job) if self.job.as_ref().is_some_and(|j| j.id == job.id) => {
+                if let Some(hash_rate) = job.hash_rate() {
+                    self.hash_rate = HashRateResult::Some(hash_rate as usize);
+                }
+                self.job = Some(job.clone());
             }
             _ => {}
         }
 
-        if self.hash_rate == HashRateResult::None {
+        // Measuring while the job runs would only slow it down, the job
+        // reports its own hash rate
+        if self.hash_rate == HashRateResult::None && !self.has_job() {
             self.hash_rate = HashRateResult::Pending;
 
             let tr = transmitter.clone();
+            let mode = self.mode;
             let exit_signal = self.exit_signal.clone();
             let hash_rate_thread = thread::spawn(move || {
-                let address_one = address!("0xffffffffffffffffffffffffffffffffffffffff");
-                let result = mine_wallet(
-                    Address::ZERO,
-                    address_one,
+                let result = mode.mine(
+                    VanityPattern::unmatchable(),
                     Some(Duration::from_secs(1)),
                     exit_signal,
                 );
@@ -261,9 +244,31 @@ impl Component for AccountCreatePage {
     where
         Self: Sized,
     {
-        Line::from("Create Wallet").bold().render(area, buf);
+        let (title, description) = match self.mode {
+            VanityMode::Wallet => (
+                "Create Wallet",
+                "Press TAB to mine a deployer whose first contract gets the vanity address instead"
+                    .to_string(),
+            ),
+            VanityMode::Deployer => (
+                "Create Deployer Wallet",
+                "The first contract deployed by this wallet (nonce 0) gets the vanity address, \
+                press TAB to mine the wallet address instead"
+                    .to_string(),
+            ),
+            VanityMode::Create2 {
+                deployer,
+                init_code_hash,
+            } => (
+                "Mine CREATE2 Salt",
+                format!("Deployer: {deployer}, init code hash: {init_code_hash}"),
+            ),
+        };
+        Line::from(title).bold().render(area, buf);
+        description.render(area.offset(Offset { x: 0, y: 1 }), buf);
 
-        "You can edit mask if you wish to vanity generate special address"
+        "You can edit mask if you wish to vanity generate special address, upper case letters \
+        have to match the checksum, UP/DOWN changes the case of the letter at the cursor"
             .render(area.offset(Offset { x: 0, y: 3 }), buf);
 
         "0x".render(area.offset(Offset { x: 0, y: 5 }), buf);
@@ -272,6 +277,7 @@ impl Component for AccountCreatePage {
             let content = if let Some(n) = b {
                 match n {
                     0..=9 => (b'0' + n) as char,
+                    10..=15 if self.case[i] == Some(true) => (b'A' + (n - 10)) as char,
                     10..=15 => (b'a' + (n - 10)) as char,
                     _ => unreachable!("Only 0..=15 allowed"),
                 }
@@ -280,11 +286,15 @@ impl Component for AccountCreatePage {
             };
             let span = Span::from(content.to_string());
 
-            let style = if self.cursor == i {
+            let mut style = if self.cursor == i {
                 Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
             } else {
                 Style::default()
             };
+            // Letters with a fixed case are underlined
+            if self.case[i].is_some() {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
 
             span.style(style).render(
                 area.offset(Offset {
@@ -295,18 +305,24 @@ impl Component for AccountCreatePage {
             );
         }
 
-        let text = if self.is_mask_empty() {
+        let text = if self.has_job() {
+            "Press ESC to go back, the job can be paused or deleted from the mining jobs page"
+                .to_string()
+        } else if self.is_mask_empty() && self.mode == VanityMode::Wallet {
             "Press enter to generate address instantly".to_string()
         } else if let HashRateResult::Some(hash_rate) = self.hash_rate {
-            let count = self.mask_count();
-            let est_attempts = 16_usize.pow(count as u32);
-            let est_time = est_attempts as f64 / hash_rate as f64;
-            let est_time = Duration::from_secs(est_time as u64);
+            let pattern = self.pattern();
+            let est_time = pattern.expected_attempts() / hash_rate as f64;
 
-            if est_time.as_secs() > 0 {
+            if est_time >= SECONDS_PER_YEAR {
+                format!(
+                    "Warning: estimated to take {}, consider a shorter pattern",
+                    fmt_seconds(est_time)
+                )
+            } else if est_time >= 1.0 {
                 format!(
                     "Estimated to take {}, press enter to generate your vanity address",
-                    humantime::format_duration(est_time)
+                    fmt_seconds(est_time)
                 )
             } else {
                 "Press enter to generate your vanity address instantly".to_string()
@@ -317,6 +333,23 @@ impl Component for AccountCreatePage {
 
         text.render(area.offset(Offset { x: 0, y: 8 }), buf);
 
+        if !self.is_mask_empty() {
+            let pattern = self.pattern();
+            let mut odds = format!(
+                "1 in {} addresses match",
+                fmt_attempts(pattern.expected_attempts())
+            );
+            if let HashRateResult::Some(hash_rate) = self.hash_rate {
+                let hash_rate = hash_rate as f64;
+                odds = format!(
+                    "{odds}, 50% chance within {}, 90% chance within {}",
+                    fmt_seconds(pattern.attempts_for_probability(0.5) / hash_rate),
+                    fmt_seconds(pattern.attempts_for_probability(0.9) / hash_rate),
+                );
+            }
+            odds.render(area.offset(Offset { x: 0, y: 9 }), buf);
+        }
+
         format!(
             "Hash rate: {}",
             match self.hash_rate {
@@ -328,54 +361,98 @@ impl Component for AccountCreatePage {
         )
         .render(area.offset(Offset { x: 0, y: 10 }), buf);
 
-        if self.mining {
-            "Mining...".render(area.offset(Offset { x: 0, y: 12 }), buf);
-            if let HashRateResult::Some(hash_rate) = self.hash_rate {
-                let count = self.mask_count();
-                let est_attempts = 16_usize.pow(count as u32);
-                let est_time = est_attempts as f64 / hash_rate as f64;
-                let elapsed_time = self.started_mining_at.elapsed();
-                let remaining_time = est_time - elapsed_time.as_secs_f64();
+        if let Some(job) = &self.job {
+            match job.result {
+                None => {
+                    format!(
+                        "Mining in the background as job #{}{}, progress is kept if you leave \
+                        this page or quit",
+                        job.id,
+                        if job.paused { " (paused)" } else { "" }
+                    )
+                    .render(area.offset(Offset { x: 0, y: 12 }), buf);
+                    format!(
+                        "Searched {} addresses in {}",
+                        job.attempts,
+                        fmt_seconds(job.elapsed_secs)
+                    )
+                    .render(area.offset(Offset { x: 0, y: 13 }), buf);
 
-                if remaining_time.is_sign_negative() {
+                    let chance = job.chance();
+                    format!(
+                        "Chance of a match by now: {:.1}%",
+                        (chance * 100.0).clamp(0.0, 100.0)
+                    )
+                    .render(area.offset(Offset { x: 0, y: 14 }), buf);
+                    Gauge::default()
+                        // TODO rename theme.block to theme.style, or add method
+                        .gauge_style(shared_state.theme.block())
+                        .ratio(chance.clamp(0.0, 1.0))
+                        .render(
+                            Rect::new(area.x, area.y + 15, area.width, 1).intersection(area),
+                            buf,
+                        );
+
+                    if let Some(hash_rate) = job.hash_rate() {
+                        let est_time = job.pattern().expected_attempts() / hash_rate;
+                        let remaining_time = est_time - job.elapsed_secs;
+                        if remaining_time.is_sign_negative() {
+                            "The expected time has passed, but a match could happen any moment now."
+                                .render(area.offset(Offset { x: 0, y: 16 }), buf);
+                        } else {
+                            format!("Remaining time: {}", fmt_seconds(remaining_time))
+                                .render(area.offset(Offset { x: 0, y: 16 }), buf);
+                        }
+                    }
+                }
+                Some(MiningJobResult::Account { address }) => {
                     format!(
-                        "Searched for: {}.\
-                        The expected time has passed, but a match could happen any moment now.",
-                        humantime::format_duration(
-                            Duration::from_secs(remaining_time.abs() as u64)
-                        )
+                        "Vanity mined the address: {}, took {} to perform {} iters",
+                        address,
+                        fmt_seconds(job.elapsed_secs),
+                        job.attempts
                     )
-                    .render(area.offset(Offset { x: 0, y: 16 }), buf);
-                } else {
+                    .render(area.offset(Offset { x: 0, y: 12 }), buf);
+                    if self.mode == VanityMode::Deployer {
+                        format!("First contract deployed at nonce 0: {}", address.create(0))
+                            .render(area.offset(Offset { x: 0, y: 13 }), buf);
+                    }
+                }
+                Some(MiningJobResult::Salt { salt, contract }) => {
                     format!(
-                        "Remaining time: {}",
-                        humantime::format_duration(Duration::from_secs(remaining_time as u64))
+                        "Mined the salt: {}, took {} to perform {} iters",
+                        salt,
+                        fmt_seconds(job.elapsed_secs),
+                        job.attempts
                     )
-                    .render(area.offset(Offset { x: 0, y: 16 }), buf);
+                    .render(area.offset(Offset { x: 0, y: 12 }), buf);
+                    format!("Contract address: {contract}")
+                        .render(area.offset(Offset { x: 0, y: 13 }), buf);
                 }
-                Gauge::default()
-                    // TODO rename theme.block to theme.style, or add method
-                    .gauge_style(shared_state.theme.block())
-                    .percent(std::cmp::min(
-                        100,
-                        (elapsed_time.as_secs() * 100)
-                            .checked_div(est_time as u64)
-                            .unwrap_or(100) as u16,
-                    ))
-                    .render(Rect::new(area.x, 16, area.width, 1), buf);
             }
-        } else if let Some((addr, counter, duration)) = self.vanity_result {
-            format!(
-                "Vanity mined the address: {}, took {} to perform {} iters",
-                addr,
-                humantime::format_duration(Duration::from_secs(duration.as_secs())),
-                counter
-            )
-            .render(area.offset(Offset { x: 0, y: 12 }), buf);
+        } else if let Some(address) = self.mnemonic_result {
+            format!("Created the account {address}")
+                .render(area.offset(Offset { x: 0, y: 12 }), buf);
         }
 
-        self.exit_popup.render(area, buf, &shared_state.theme);
-
         area
     }
 }
+
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+
+fn fmt_seconds(seconds: f64) -> String {
+    if seconds >= SECONDS_PER_YEAR * 1e6 {
+        format!("{} years", fmt_attempts(seconds / SECONDS_PER_YEAR))
+    } else {
+        humantime::format_duration(Duration::from_secs(seconds as u64)).to_string()
+    }
+}
+
+fn fmt_attempts(attempts: f64) -> String {
+    if attempts < 1e12 {
+        format!("{attempts:.0}")
+    } else {
+        format!("{attempts:.2e}")
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    str::FromStr,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+};
+
+use gm_common::secret::MnemonicLanguage;
+use gm_ratatui_extra::{
+    act::Act,
+    widgets::{
+        filter_select_popup::FilterSelectPopup,
+        form::{Form, FormItemIndex, FormWidget},
+    },
+};
+use gm_utils::account::{AccountManager, DEFAULT_WORD_COUNT, MNEMONIC_WORD_COUNTS};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+
+#[derive(Debug, Display, EnumIter, PartialEq)]
+pub enum FormItem {
+    Heading,
+    WordCount,
+    Language,
+    Passphrase,
+    ConfirmPassphrase,
+    CreateButton,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Create new mnemonic wallet"),
+            FormItem::WordCount => FormWidget::SelectInput {
+                label: "Number of words",
+                text: String::new(),
+                empty_text: Some("Select number of words"),
+                popup: FilterSelectPopup::new("Number of words", None),
+            },
+            FormItem::Language => FormWidget::SelectInput {
+                label: "Wordlist",
+                text: String::new(),
+                empty_text: Some("Select a wordlist"),
+                popup: FilterSelectPopup::new("Wordlist", None),
+            },
+            FormItem::Passphrase => FormWidget::PasswordInput {
+                label: "BIP-39 passphrase (optional)",
+                text: String::new(),
+            },
+            FormItem::ConfirmPassphrase => FormWidget::PasswordInput {
+                label: "Confirm passphrase",
+                text: String::new(),
+            },
+            FormItem::CreateButton => FormWidget::Button { label: "Create" },
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct AccountCreateMnemonicPage {
+    pub form: Form<FormItem, crate::Error>,
+}
+
+impl AccountCreateMnemonicPage {
+    pub fn new() -> crate::Result<Self> {
+        let form = Form::init(|form| {
+            let word_count = DEFAULT_WORD_COUNT.to_string();
+            *form.get_text_mut(FormItem::WordCount) = word_count.clone();
+            let popup = form.get_popup_mut(FormItem::WordCount);
+            popup.set_items(Some(
+                MNEMONIC_WORD_COUNTS
+                    .iter()
+                    .map(|count| count.to_string())
+                    .collect(),
+            ));
+            popup.set_cursor(&word_count);
+
+            let language = MnemonicLanguage::default().to_string();
+            *form.get_text_mut(FormItem::Language) = language.clone();
+            let popup = form.get_popup_mut(FormItem::Language);
+            popup.set_items(Some(MnemonicLanguage::list()));
+            popup.set_cursor(&language);
+            Ok(())
+        })?;
+
+        Ok(Self { form })
+    }
+}
+
+impl Component for AccountCreateMnemonicPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                if label == FormItem::CreateButton {
+                    let passphrase = form.get_text(FormItem::Passphrase).clone();
+                    if &passphrase != form.get_text(FormItem::ConfirmPassphrase) {
+                        *form.get_text_mut(FormItem::ErrorText) =
+                            "Passphrases do not match".to_string();
+                        return Ok(());
+                    }
+
+                    AccountManager::ensure_unlocked()?;
+
+                    let word_count = form
+                        .get_text(FormItem::WordCount)
+                        .parse::<usize>()
+                        .unwrap_or(DEFAULT_WORD_COUNT);
+                    let language = MnemonicLanguage::from_str(form.get_text(FormItem::Language))
+                        .unwrap_or_default();
+
+                    AccountManager::create_mnemonic_wallet_with(
+                        word_count,
+                        language,
+                        Some(passphrase).filter(|p| !p.is_empty()),
+                    )?;
+
+                    handle_result.page_pops = 1;
+                    handle_result.reload = true;
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+        area
+    }
+}
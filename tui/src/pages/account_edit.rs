@@ -0,0 +1,209 @@
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+
+use super::{account_export::AccountExportPage, Page};
+use alloy::primitives::Address;
+use gm_ratatui_extra::{
+    act::Act,
+    confirm_popup::ConfirmPopup,
+    form::{Form, FormItemIndex, FormWidget},
+    thematize::Thematize,
+};
+use gm_utils::account::AccountManager;
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Account,
+    Label,
+    SaveButton,
+    ExportButton,
+    BackupDirectory,
+    BackupPassword,
+    DeleteButton,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Edit account"),
+            FormItem::Account => FormWidget::DisplayBox {
+                label: "Account",
+                text: String::new(),
+                empty_text: None,
+            },
+            FormItem::Label => FormWidget::InputBox {
+                label: "Label",
+                text: String::new(),
+                empty_text: Some("No label"),
+                currency: None,
+            },
+            FormItem::SaveButton => FormWidget::Button { label: "Save" },
+            FormItem::ExportButton => FormWidget::Button {
+                label: "Export as keystore",
+            },
+            FormItem::BackupDirectory => FormWidget::InputBox {
+                label: "Backup directory before deleting (optional)",
+                text: String::new(),
+                empty_text: Some("Leave empty to delete without a backup"),
+                currency: None,
+            },
+            FormItem::BackupPassword => FormWidget::PasswordInput {
+                label: "Backup keystore password",
+                text: String::new(),
+            },
+            FormItem::DeleteButton => FormWidget::Button { label: "Delete" },
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct AccountEditPage {
+    pub address: Address,
+    pub form: Form<FormItem, crate::Error>,
+    pub delete_popup: ConfirmPopup,
+}
+
+impl AccountEditPage {
+    pub fn new(address: Address, label: Option<String>, no_secret: bool) -> crate::Result<Self> {
+        Ok(Self {
+            address,
+            form: Form::init(|form| {
+                *form.get_text_mut(FormItem::Account) = address.to_string();
+                *form.get_text_mut(FormItem::Label) = label.unwrap_or_default();
+                // There is no secret to back up for watch-only accounts and Safes
+                if no_secret {
+                    form.hide_item(FormItem::ExportButton);
+                    form.hide_item(FormItem::BackupDirectory);
+                    form.hide_item(FormItem::BackupPassword);
+                }
+                Ok(())
+            })?,
+            delete_popup: ConfirmPopup::new(
+                "Delete Account",
+                format!(
+                    "Are you sure you want to delete the account {address}? Without a backup \
+                     the funds in this account cannot be recovered."
+                ),
+                "Delete",
+                "Cancel",
+            ),
+        })
+    }
+}
+
+impl Component for AccountEditPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+        let address = self.address;
+
+        if self.delete_popup.is_open() {
+            let form = &mut self.form;
+            let r = self.delete_popup.handle_event(
+                event.key_event(),
+                area,
+                || -> crate::Result<()> {
+                    if !AccountManager::is_watch_only(&address)?
+                        && AccountManager::safe_network(&address)?.is_none()
+                    {
+                        AccountManager::ensure_unlocked()?;
+                    }
+
+                    let dir = form.get_text(FormItem::BackupDirectory).trim().to_string();
+                    if !dir.is_empty() {
+                        let password = form.get_text(FormItem::BackupPassword).clone();
+                        if password.is_empty() {
+                            *form.get_text_mut(FormItem::ErrorText) =
+                                "Please enter a password for the backup keystore".to_string();
+                            return Ok(());
+                        }
+                        if let Err(err) = AccountManager::export_keystore(
+                            &address,
+                            &PathBuf::from(dir),
+                            &password,
+                        ) {
+                            *form.get_text_mut(FormItem::ErrorText) =
+                                format!("Backup failed, account was not deleted: {err}");
+                            return Ok(());
+                        }
+                    }
+
+                    AccountManager::remove_account(&address)?;
+                    handle_result.page_pops = 1;
+                    handle_result.reload = true;
+                    Ok(())
+                },
+                || Ok(()),
+            )?;
+            handle_result.merge(r);
+            return Ok(handle_result);
+        }
+
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                match label {
+                    FormItem::SaveButton => {
+                        AccountManager::set_label(&address, form.get_text(FormItem::Label))?;
+                        handle_result.page_pops = 1;
+                        handle_result.reload = true;
+                    }
+                    FormItem::ExportButton => {
+                        handle_result
+                            .page_inserts
+                            .push(Page::AccountExport(AccountExportPage::new(address)?));
+                    }
+                    FormItem::DeleteButton => {
+                        form.get_text_mut(FormItem::ErrorText).clear();
+                        self.delete_popup.open();
+                    }
+                    _ => {}
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+        self.delete_popup.render(area, buf, &ss.theme.popup());
+
+        area
+    }
+}
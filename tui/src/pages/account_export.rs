@@ -0,0 +1,143 @@
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use alloy::primitives::Address;
+use gm_ratatui_extra::{
+    act::Act,
+    form::{Form, FormItemIndex, FormWidget},
+};
+use gm_utils::account::AccountManager;
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Account,
+    Directory,
+    Password,
+    ConfirmPassword,
+    ExportButton,
+    DisplayText,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Export as Keystore JSON"),
+            FormItem::Account => FormWidget::DisplayBox {
+                label: "Account",
+                text: String::new(),
+                empty_text: None,
+            },
+            FormItem::Directory => FormWidget::InputBox {
+                label: "Directory",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::Password => FormWidget::PasswordInput {
+                label: "Password",
+                text: String::new(),
+            },
+            FormItem::ConfirmPassword => FormWidget::PasswordInput {
+                label: "Confirm Password",
+                text: String::new(),
+            },
+            FormItem::ExportButton => FormWidget::Button { label: "Export" },
+            FormItem::DisplayText => FormWidget::DisplayText(String::new()),
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct AccountExportPage {
+    pub address: Address,
+    pub form: Form<FormItem, crate::Error>,
+}
+
+impl AccountExportPage {
+    pub fn new(address: Address) -> crate::Result<Self> {
+        Ok(Self {
+            address,
+            form: Form::init(|form| {
+                *form.get_text_mut(FormItem::Account) = address.to_string();
+                *form.get_text_mut(FormItem::Directory) = AccountManager::foundry_keystores_dir()?
+                    .to_string_lossy()
+                    .to_string();
+                Ok(())
+            })?,
+        })
+    }
+}
+
+impl Component for AccountExportPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+
+        let address = self.address;
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                if label == FormItem::ExportButton {
+                    form.get_text_mut(FormItem::DisplayText).clear();
+                    form.get_text_mut(FormItem::ErrorText).clear();
+
+                    let password = form.get_text(FormItem::Password).clone();
+                    if password.is_empty() {
+                        *form.get_text_mut(FormItem::ErrorText) =
+                            "Please enter a password for the keystore file".to_string();
+                    } else if &password != form.get_text(FormItem::ConfirmPassword) {
+                        *form.get_text_mut(FormItem::ErrorText) =
+                            "Passwords do not match".to_string();
+                    } else {
+                        let dir = PathBuf::from(form.get_text(FormItem::Directory));
+                        let path = AccountManager::export_keystore(&address, &dir, &password)?;
+                        *form.get_text_mut(FormItem::DisplayText) =
+                            format!("Exported to {}", path.display());
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+
+        area
+    }
+}
@@ -1,12 +1,20 @@
-use std::sync::{atomic::AtomicBool, mpsc, Arc};
+use std::{
+    path::Path,
+    str::FromStr,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+};
 
-use gm_ratatui_extra::input_box::InputBox;
-use ratatui::{
-    buffer::Buffer,
-    crossterm::event::KeyCode,
-    layout::{Offset, Rect},
-    widgets::Widget,
+use alloy::hex;
+use gm_common::secret::{MnemonicLanguage, MnemonicSecret, DEFAULT_DERIVATION_PATH};
+use gm_ratatui_extra::{
+    act::Act,
+    widgets::{
+        filter_select_popup::FilterSelectPopup,
+        form::{Form, FormItemIndex, FormWidget},
+    },
 };
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
 
 use crate::{
     app::SharedState,
@@ -15,16 +23,91 @@ use crate::{
 };
 use gm_utils::account::AccountManager;
 
-#[derive(Default, Debug)]
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Input,
+    DerivationPath,
+    Language,
+    Passphrase,
+    KeystorePassword,
+    ImportButton,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Import existing wallet"),
+            FormItem::Input => FormWidget::InputBox {
+                label: "Private key, Mnemonic phrase or Keystore JSON file path",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::DerivationPath => FormWidget::InputBox {
+                label: "Derivation path (for Mnemonic phrase)",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::Language => FormWidget::SelectInput {
+                label: "Wordlist (for Mnemonic phrase)",
+                text: String::new(),
+                empty_text: Some("Select a wordlist"),
+                popup: FilterSelectPopup::new("Wordlist", None),
+            },
+            FormItem::Passphrase => FormWidget::PasswordInput {
+                label: "BIP-39 passphrase (optional)",
+                text: String::new(),
+            },
+            FormItem::KeystorePassword => FormWidget::PasswordInput {
+                label: "Keystore password",
+                text: String::new(),
+            },
+            FormItem::ImportButton => FormWidget::Button { label: "Import" },
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
 pub struct AccountImportPage {
-    pub screen: usize,
-    pub input: String,
-    pub text_cursor: usize,
-    pub display: Option<String>,
-    pub success: bool,
+    pub form: Form<FormItem, crate::Error>,
+}
+
+impl AccountImportPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            form: Form::init(|form| {
+                *form.get_text_mut(FormItem::DerivationPath) = DEFAULT_DERIVATION_PATH.to_string();
+                let language = MnemonicLanguage::default().to_string();
+                *form.get_text_mut(FormItem::Language) = language.clone();
+                let popup = form.get_popup_mut(FormItem::Language);
+                popup.set_items(Some(MnemonicLanguage::list()));
+                popup.set_cursor(&language);
+                form.hide_item(FormItem::KeystorePassword);
+                Ok(())
+            })?,
+        })
+    }
+}
+
+fn is_keystore_path(input: &str) -> bool {
+    Path::new(input.trim()).is_file()
 }
 
 impl Component for AccountImportPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
@@ -33,63 +116,76 @@ impl Component for AccountImportPage {
         _shutdown_signal: &Arc<AtomicBool>,
         _shared_state: &SharedState,
     ) -> crate::Result<Actions> {
-        let mut result = Actions::default();
+        let mut handle_result = Actions::default();
 
-        if let Event::Input(key_event) = event {
-            if self.display.is_some() {
-                if self.success {
-                    result.page_pops = 1;
-                    result.reload = true;
-                } else {
-                    self.display = None;
+        let r = self.form.handle_event(
+            event.key_event(),
+            |label, form| {
+                if label == FormItem::Input {
+                    // Only ask for the fields relevant to the kind of input
+                    if is_keystore_path(form.get_text(FormItem::Input)) {
+                        form.show_item(FormItem::KeystorePassword);
+                        form.hide_item(FormItem::DerivationPath);
+                        form.hide_item(FormItem::Language);
+                        form.hide_item(FormItem::Passphrase);
+                    } else {
+                        form.hide_item(FormItem::KeystorePassword);
+                        form.show_item(FormItem::DerivationPath);
+                        form.show_item(FormItem::Language);
+                        form.show_item(FormItem::Passphrase);
+                    }
                 }
-                return Ok(result);
-            }
+                Ok(())
+            },
+            |label, form| {
+                if label == FormItem::ImportButton {
+                    AccountManager::ensure_unlocked()?;
 
-            match key_event.code {
-                KeyCode::Char(char) => {
-                    self.input.push(char);
-                }
-                KeyCode::Backspace => {
-                    self.input.pop();
-                }
-                KeyCode::Enter => {
-                    let import_result = AccountManager::import_mnemonic_wallet(&self.input)
-                        .or_else(|_| AccountManager::import_private_key(&self.input));
+                    let input = form.get_text(FormItem::Input).trim().to_string();
+                    let import_result = if is_keystore_path(&input) {
+                        AccountManager::import_keystore(
+                            Path::new(&input),
+                            form.get_text(FormItem::KeystorePassword),
+                        )
+                    } else if hex::decode(&input).is_ok() {
+                        AccountManager::import_private_key(&input)
+                    } else {
+                        let mut mnemonic = MnemonicSecret::new(
+                            &input,
+                            form.get_text(FormItem::DerivationPath).trim(),
+                        );
+                        mnemonic.language =
+                            MnemonicLanguage::from_str(form.get_text(FormItem::Language))
+                                .unwrap_or_default();
+                        mnemonic.passphrase = Some(form.get_text(FormItem::Passphrase).clone())
+                            .filter(|p| !p.is_empty());
+                        AccountManager::import_mnemonic_wallet(&mnemonic)
+                    };
 
                     match import_result {
-                        Ok(address) => {
-                            self.display = Some(format!("Successfully imported wallet: {address}"));
-                            self.success = true;
+                        Ok(_) => {
+                            handle_result.page_pops = 1;
+                            handle_result.reload = true;
                         }
                         Err(err) => {
-                            self.display = Some(format!("Error importing wallet: {err}"));
+                            *form.get_text_mut(FormItem::ErrorText) =
+                                format!("Error importing wallet: {err}");
                         }
                     }
                 }
-                _ => {}
-            }
-        }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
 
-        Ok(result)
+        Ok(handle_result)
     }
 
     fn render_component(&self, area: Rect, buf: &mut Buffer, shared_state: &SharedState) -> Rect
     where
         Self: Sized,
     {
-        InputBox {
-            focus: true,
-            label: "Private key or Mnemonic phrase",
-            text: &self.input,
-            empty_text: None,
-            currency: None,
-        }
-        .render(area, buf, &self.text_cursor, &shared_state.theme);
-
-        if let Some(display) = &self.display {
-            display.render(area.offset(Offset { x: 0, y: 4 }), buf);
-        }
+        self.form.render(area, buf, &shared_state.theme);
 
         area
     }
@@ -0,0 +1,169 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+    widgets::{networks_popup, NetworksPopup},
+};
+use gm_ratatui_extra::{
+    act::Act,
+    form::{Form, FormItemIndex, FormWidget},
+    thematize::Thematize,
+};
+use gm_utils::{
+    account::AccountManager, alloy::StringExt, disk_storage::DiskStorageInterface,
+    network::NetworkStore,
+};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Address,
+    Network,
+    Label,
+    AddButton,
+    DisplayText,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Add Safe multisig account"),
+            FormItem::Address => FormWidget::InputBox {
+                label: "Safe address",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::Network => FormWidget::DisplayBox {
+                label: "Network",
+                text: String::new(),
+                empty_text: Some("<press SPACE to select network>"),
+            },
+            FormItem::Label => FormWidget::InputBox {
+                label: "Label (optional)",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::AddButton => FormWidget::Button { label: "Add" },
+            FormItem::DisplayText => FormWidget::DisplayText(
+                "Transactions of the Safe are signed by its owners stored in gm and executed \
+                 once enough owners confirmed them"
+                    .to_string(),
+            ),
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct AccountSafeAddPage {
+    pub form: Form<FormItem, crate::Error>,
+    pub networks_popup: NetworksPopup,
+}
+
+impl AccountSafeAddPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            form: Form::init(|_| Ok(()))?,
+            networks_popup: networks_popup(),
+        })
+    }
+}
+
+impl Component for AccountSafeAddPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+
+        if self.networks_popup.is_open() {
+            handle_result.merge(self.networks_popup.handle_event(
+                event.key_event(),
+                |network| -> crate::Result<()> {
+                    *self.form.get_text_mut(FormItem::Network) = network.name.clone();
+                    self.form.advance_cursor();
+                    Ok(())
+                },
+            )?);
+            return Ok(handle_result);
+        }
+
+        if self.form.is_focused(FormItem::Network) && event.is_space_or_enter_pressed() {
+            self.networks_popup.open();
+            self.networks_popup
+                .set_items(Some(NetworkStore::load()?.filter(ss.testnet_mode)));
+            return Ok(handle_result);
+        }
+
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                if label == FormItem::AddButton {
+                    let network = form.get_text(FormItem::Network);
+                    if network.is_empty() {
+                        *form.get_text_mut(FormItem::ErrorText) =
+                            "Please select the network of the Safe".to_string();
+                        return Ok(());
+                    }
+                    let result = form
+                        .get_text(FormItem::Address)
+                        .trim()
+                        .parse_as_address()
+                        .and_then(|address| {
+                            AccountManager::add_safe(
+                                &address,
+                                network,
+                                form.get_text(FormItem::Label),
+                            )
+                        });
+                    match result {
+                        Ok(()) => {
+                            handle_result.page_pops = 1;
+                            handle_result.reload = true;
+                        }
+                        Err(err) => {
+                            *form.get_text_mut(FormItem::ErrorText) = err.to_string();
+                        }
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+
+        self.networks_popup.render(area, buf, &ss.theme.popup());
+
+        area
+    }
+}
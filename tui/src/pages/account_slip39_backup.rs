@@ -0,0 +1,182 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use alloy::primitives::Address;
+use gm_ratatui_extra::{
+    act::Act,
+    form::{Form, FormItemIndex, FormWidget},
+};
+use gm_utils::account::AccountManager;
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+use super::{text::TextPage, Page};
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Account,
+    Threshold,
+    ShareCount,
+    Passphrase,
+    ConfirmPassphrase,
+    CreateButton,
+    DisplayText,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Back up as SLIP-39 shares"),
+            FormItem::Account => FormWidget::DisplayBox {
+                label: "Account",
+                text: String::new(),
+                empty_text: None,
+            },
+            FormItem::Threshold => FormWidget::InputBox {
+                label: "Shares needed to recover",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::ShareCount => FormWidget::InputBox {
+                label: "Total shares (at most 16)",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::Passphrase => FormWidget::PasswordInput {
+                label: "SLIP-39 passphrase (optional)",
+                text: String::new(),
+            },
+            FormItem::ConfirmPassphrase => FormWidget::PasswordInput {
+                label: "Confirm SLIP-39 passphrase",
+                text: String::new(),
+            },
+            FormItem::CreateButton => FormWidget::Button {
+                label: "Create shares",
+            },
+            FormItem::DisplayText => FormWidget::DisplayText(
+                "Shares only hold the mnemonic, keep note of the wordlist, derivation path and \
+                 BIP-39 passphrase of the account separately"
+                    .to_string(),
+            ),
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct AccountSlip39BackupPage {
+    pub address: Address,
+    pub form: Form<FormItem, crate::Error>,
+}
+
+impl AccountSlip39BackupPage {
+    pub fn new(address: Address) -> crate::Result<Self> {
+        Ok(Self {
+            address,
+            form: Form::init(|form| {
+                *form.get_text_mut(FormItem::Account) = address.to_string();
+                *form.get_text_mut(FormItem::Threshold) = "2".to_string();
+                *form.get_text_mut(FormItem::ShareCount) = "3".to_string();
+                Ok(())
+            })?,
+        })
+    }
+}
+
+impl Component for AccountSlip39BackupPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+
+        let address = self.address;
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                if label == FormItem::CreateButton {
+                    form.get_text_mut(FormItem::ErrorText).clear();
+
+                    let threshold = form.get_text(FormItem::Threshold).trim().parse::<u8>();
+                    let share_count = form.get_text(FormItem::ShareCount).trim().parse::<u8>();
+                    let passphrase = form.get_text(FormItem::Passphrase).clone();
+                    let (Ok(threshold), Ok(share_count)) = (threshold, share_count) else {
+                        *form.get_text_mut(FormItem::ErrorText) =
+                            "Please enter the number of shares as numbers".to_string();
+                        return Ok(());
+                    };
+                    if &passphrase != form.get_text(FormItem::ConfirmPassphrase) {
+                        *form.get_text_mut(FormItem::ErrorText) =
+                            "Passphrases do not match".to_string();
+                        return Ok(());
+                    }
+
+                    AccountManager::ensure_unlocked()?;
+                    match AccountManager::slip39_backup(
+                        &address,
+                        threshold,
+                        share_count,
+                        &passphrase,
+                    ) {
+                        Ok(shares) => {
+                            let shares = shares
+                                .iter()
+                                .enumerate()
+                                .map(|(i, share)| {
+                                    format!("Share {} of {share_count}:\n{share}", i + 1)
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n\n");
+                            handle_result
+                                .page_inserts
+                                .push(Page::Text(TextPage::new(format!(
+                                    "Any {threshold} of these shares recover the account \
+                                     {address}. Write each share down and store them in \
+                                     different places.\n\n{shares}"
+                                ))));
+                        }
+                        Err(err) => {
+                            *form.get_text_mut(FormItem::ErrorText) = err.to_string();
+                        }
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+
+        area
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    str::FromStr,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+};
+
+use gm_common::secret::{MnemonicLanguage, DEFAULT_DERIVATION_PATH};
+use gm_ratatui_extra::{
+    act::Act,
+    widgets::{
+        filter_select_popup::FilterSelectPopup,
+        form::{Form, FormItemIndex, FormWidget},
+    },
+};
+use gm_utils::account::{slip39::Share, AccountManager};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Share,
+    AddShareButton,
+    Status,
+    Passphrase,
+    DerivationPath,
+    Language,
+    Bip39Passphrase,
+    RecoverButton,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Recover wallet from SLIP-39 shares"),
+            FormItem::Share => FormWidget::InputBox {
+                label: "Share (one at a time)",
+                text: String::new(),
+                empty_text: Some("Words of the share separated by spaces"),
+                currency: None,
+            },
+            FormItem::AddShareButton => FormWidget::Button { label: "Add share" },
+            FormItem::Status => FormWidget::DisplayText("No shares entered yet".to_string()),
+            FormItem::Passphrase => FormWidget::PasswordInput {
+                label: "SLIP-39 passphrase (if used for the backup)",
+                text: String::new(),
+            },
+            FormItem::DerivationPath => FormWidget::InputBox {
+                label: "Derivation path",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::Language => FormWidget::SelectInput {
+                label: "Wordlist of the mnemonic",
+                text: String::new(),
+                empty_text: Some("Select a wordlist"),
+                popup: FilterSelectPopup::new("Wordlist", None),
+            },
+            FormItem::Bip39Passphrase => FormWidget::PasswordInput {
+                label: "BIP-39 passphrase (optional)",
+                text: String::new(),
+            },
+            FormItem::RecoverButton => FormWidget::Button { label: "Recover" },
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct AccountSlip39RecoverPage {
+    pub form: Form<FormItem, crate::Error>,
+    pub shares: Vec<String>,
+}
+
+impl AccountSlip39RecoverPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            form: Form::init(|form| {
+                *form.get_text_mut(FormItem::DerivationPath) = DEFAULT_DERIVATION_PATH.to_string();
+                let language = MnemonicLanguage::default().to_string();
+                *form.get_text_mut(FormItem::Language) = language.clone();
+                let popup = form.get_popup_mut(FormItem::Language);
+                popup.set_items(Some(MnemonicLanguage::list()));
+                popup.set_cursor(&language);
+                Ok(())
+            })?,
+            shares: vec![],
+        })
+    }
+}
+
+/// Shares entered so far along with how many are needed, the thresholds are
+/// part of every share.
+fn status_text(shares: &[Share]) -> String {
+    let Some(first) = shares.first() else {
+        return "No shares entered yet".to_string();
+    };
+    if first.group_count == 1 {
+        format!(
+            "{} of {} shares entered",
+            shares.len(),
+            first.member_threshold
+        )
+    } else {
+        format!(
+            "{} shares entered, shares of {} of the {} groups are needed",
+            shares.len(),
+            first.group_threshold,
+            first.group_count
+        )
+    }
+}
+
+impl Component for AccountSlip39RecoverPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+
+        let shares = &mut self.shares;
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                match label {
+                    FormItem::AddShareButton => {
+                        form.get_text_mut(FormItem::ErrorText).clear();
+
+                        let input = form
+                            .get_text(FormItem::Share)
+                            .split_whitespace()
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                            .to_lowercase();
+                        // Checks the words and checksum right away, so that a
+                        // typo is caught before all the shares are entered
+                        match Share::from_mnemonic(&input) {
+                            Ok(share) => {
+                                let mut parsed = shares
+                                    .iter()
+                                    .filter_map(|s| Share::from_mnemonic(s).ok())
+                                    .collect::<Vec<_>>();
+                                if parsed.iter().any(|p| p.identifier != share.identifier) {
+                                    *form.get_text_mut(FormItem::ErrorText) =
+                                        "This share belongs to a different backup".to_string();
+                                } else if shares.contains(&input) {
+                                    *form.get_text_mut(FormItem::ErrorText) =
+                                        "This share was already entered".to_string();
+                                } else {
+                                    shares.push(input);
+                                    parsed.push(share);
+                                    form.get_text_mut(FormItem::Share).clear();
+                                    *form.get_text_mut(FormItem::Status) = status_text(&parsed);
+                                }
+                            }
+                            Err(err) => {
+                                *form.get_text_mut(FormItem::ErrorText) = err.to_string();
+                            }
+                        }
+                    }
+                    FormItem::RecoverButton => {
+                        AccountManager::ensure_unlocked()?;
+
+                        let language =
+                            MnemonicLanguage::from_str(form.get_text(FormItem::Language))
+                                .unwrap_or_default();
+                        let bip39_passphrase =
+                            Some(form.get_text(FormItem::Bip39Passphrase).clone())
+                                .filter(|p| !p.is_empty());
+                        match AccountManager::slip39_recover(
+                            shares,
+                            form.get_text(FormItem::Passphrase),
+                            language,
+                            form.get_text(FormItem::DerivationPath).trim(),
+                            bip39_passphrase,
+                        ) {
+                            Ok(_) => {
+                                handle_result.page_pops = 1;
+                                handle_result.reload = true;
+                            }
+                            Err(err) => {
+                                *form.get_text_mut(FormItem::ErrorText) =
+                                    format!("Error recovering wallet: {err}");
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, shared_state: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &shared_state.theme);
+
+        area
+    }
+}
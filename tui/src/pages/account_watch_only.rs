@@ -0,0 +1,123 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use gm_ratatui_extra::{
+    act::Act,
+    form::{Form, FormItemIndex, FormWidget},
+};
+use gm_utils::{account::AccountManager, alloy::StringExt};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Address,
+    Label,
+    AddButton,
+    DisplayText,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Add watch-only address"),
+            FormItem::Address => FormWidget::InputBox {
+                label: "Address",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::Label => FormWidget::InputBox {
+                label: "Label (optional)",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::AddButton => FormWidget::Button { label: "Add" },
+            FormItem::DisplayText => FormWidget::DisplayText(
+                "Watch-only accounts show the portfolio but cannot sign anything".to_string(),
+            ),
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct AccountWatchOnlyPage {
+    pub form: Form<FormItem, crate::Error>,
+}
+
+impl AccountWatchOnlyPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            form: Form::init(|_| Ok(()))?,
+        })
+    }
+}
+
+impl Component for AccountWatchOnlyPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                if label == FormItem::AddButton {
+                    let result = form
+                        .get_text(FormItem::Address)
+                        .trim()
+                        .parse_as_address()
+                        .and_then(|address| {
+                            AccountManager::add_watch_only(&address, form.get_text(FormItem::Label))
+                        });
+                    match result {
+                        Ok(()) => {
+                            handle_result.page_pops = 1;
+                            handle_result.reload = true;
+                        }
+                        Err(err) => {
+                            *form.get_text_mut(FormItem::ErrorText) = err.to_string();
+                        }
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+
+        area
+    }
+}
@@ -16,7 +16,10 @@ use crate::{
     traits::{Actions, Component},
 };
 use gm_utils::{
-    account::{AccountManager, AccountUtils},
+    account::{
+        metadata::{AccountMetadataStore, LabeledAddress},
+        AccountManager, AccountUtils,
+    },
     address_book::{AddressBookEntry, AddressBookStore},
     disk_storage::DiskStorageInterface,
 };
@@ -29,7 +32,7 @@ use super::{
 pub enum AddressBookMenuItem {
     Create,
     View(AddressBookEntry),
-    UnnamedOwned(Address),
+    UnnamedOwned(Address, Option<String>),
     RecentlyInteracted(Address),
 }
 
@@ -38,7 +41,11 @@ impl Display for AddressBookMenuItem {
         match self {
             AddressBookMenuItem::Create => write!(f, "Create new address book entry"),
             AddressBookMenuItem::View(entry) => write!(f, "{} - {}", entry.name, entry.address),
-            AddressBookMenuItem::UnnamedOwned(address) => {
+            AddressBookMenuItem::UnnamedOwned(address, label) => {
+                let address = LabeledAddress {
+                    address,
+                    label: label.as_ref(),
+                };
                 write!(f, "Self: {address}")
             }
             AddressBookMenuItem::RecentlyInteracted(address) => {
@@ -69,6 +76,7 @@ impl AddressBookMenuItem {
         );
 
         // Self accounts that do not exist in the address book
+        let metadata = AccountMetadataStore::load()?;
         entries.extend(
             AccountManager::get_account_list()?
                 .into_iter()
@@ -78,7 +86,9 @@ impl AddressBookMenuItem {
                         _ => false,
                     })
                 })
-                .map(AddressBookMenuItem::UnnamedOwned)
+                .map(|address| {
+                    AddressBookMenuItem::UnnamedOwned(address, metadata.label(&address).cloned())
+                })
                 .collect::<Vec<AddressBookMenuItem>>(),
         );
 
@@ -103,7 +113,7 @@ impl AddressBookMenuItem {
         match self {
             AddressBookMenuItem::Create => Err(crate::Error::AddressBookEntryIsInvalid),
             AddressBookMenuItem::View(entry) => Ok(entry.address),
-            AddressBookMenuItem::UnnamedOwned(address) => Ok(*address),
+            AddressBookMenuItem::UnnamedOwned(address, _) => Ok(*address),
             AddressBookMenuItem::RecentlyInteracted(address) => Ok(*address),
         }
     }
@@ -191,9 +201,12 @@ impl Component for AddressBookPage {
                                 entry.address.to_string(),
                             )?)
                         }
-                        AddressBookMenuItem::UnnamedOwned(address) => Page::AddressBookCreate(
-                            AddressBookCreatePage::new(String::new(), address.to_string())?,
-                        ),
+                        AddressBookMenuItem::UnnamedOwned(address, label) => {
+                            Page::AddressBookCreate(AddressBookCreatePage::new(
+                                label.clone().unwrap_or_default(),
+                                address.to_string(),
+                            )?)
+                        }
                         AddressBookMenuItem::RecentlyInteracted(address) => {
                             Page::AddressBookCreate(AddressBookCreatePage::new(
                                 String::new(),
@@ -7,7 +7,7 @@ use crate::{
     traits::{Actions, Component},
 };
 use alloy::primitives::utils::parse_units;
-use alloy::primitives::{Bytes, U256};
+use alloy::primitives::{Address, Bytes, U256};
 use alloy::rpc::types::TransactionRequest;
 use gm_ratatui_extra::act::Act;
 use gm_ratatui_extra::form::{Form, FormItemIndex, FormWidget};
@@ -15,6 +15,7 @@ use gm_utils::alloy::StringExt;
 use gm_utils::assets::{Asset, TokenAddress};
 use gm_utils::erc20;
 use gm_utils::network::Network;
+use gm_utils::recipient;
 use std::sync::mpsc;
 use std::sync::{atomic::AtomicBool, Arc};
 use strum::{Display, EnumIter};
@@ -25,6 +26,7 @@ use super::address_book::AddressBookMenuItem;
 pub enum FormItem {
     Heading,
     To,
+    RecipientWarning,
     AssetType,
     Amount,
     ErrorText,
@@ -46,6 +48,7 @@ impl TryFrom<FormItem> for FormWidget {
                 empty_text: Some("<press SPACE to select from address book>"),
                 currency: None,
             },
+            FormItem::RecipientWarning => FormWidget::ErrorText(String::new()),
             FormItem::AssetType => FormWidget::DisplayBox {
                 label: "Asset Type",
                 text: String::new(),
@@ -71,6 +74,8 @@ pub struct AssetTransferPage {
     pub address_book_popup: AddressBookPopup,
     pub asset_popup: AssetsPopup,
     pub tx_popup: TxPopup,
+    /// Addresses read from disk for the recipient warning, loaded once.
+    stored_addresses: Option<Vec<Address>>,
 }
 
 impl AssetTransferPage {
@@ -81,10 +86,42 @@ impl AssetTransferPage {
             address_book_popup: address_book_popup(),
             asset_popup: assets_popup(),
             tx_popup: TxPopup::default(),
+            stored_addresses: None,
         })
     }
 }
 
+impl AssetTransferPage {
+    /// Warns as soon as the recipient is entered if it was never sent to or
+    /// looks like a known address, the rest is checked by the tx popup.
+    fn update_recipient_warning(&mut self, ss: &SharedState) -> crate::Result<()> {
+        let warning = match self.form.get_text(FormItem::To).parse_as_address() {
+            Ok(to) => {
+                let token =
+                    self.asset
+                        .as_ref()
+                        .and_then(|asset| match asset.r#type.token_address {
+                            TokenAddress::Native => None,
+                            TokenAddress::Contract(address) => Some(address),
+                        });
+                let stored = match &self.stored_addresses {
+                    Some(stored) => stored,
+                    None => self.stored_addresses.insert(recipient::stored_addresses()?),
+                };
+                let known = recipient::with_recent(stored.clone(), ss.recent_addresses.as_deref());
+                recipient::check_known(to, token, &known)
+                    .iter()
+                    .map(|risk| risk.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            Err(_) => String::new(),
+        };
+        *self.form.get_text_mut(FormItem::RecipientWarning) = warning;
+        Ok(())
+    }
+}
+
 impl AssetTransferPage {
     #[allow(clippy::field_reassign_with_default)]
     pub fn new(asset: &Asset) -> crate::Result<Self> {
@@ -109,6 +146,11 @@ impl Component for AssetTransferPage {
         self.form.set_form_focus(focus);
     }
 
+    fn reload(&mut self, _ss: &SharedState) -> crate::Result<()> {
+        self.stored_addresses = None;
+        Ok(())
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
@@ -128,6 +170,7 @@ impl Component for AssetTransferPage {
                     Ok(())
                 },
             )?);
+            self.update_recipient_warning(ss)?;
         } else if self.asset_popup.is_open() {
             result.merge(self.asset_popup.handle_event(
                 event.key_event(),
@@ -143,6 +186,7 @@ impl Component for AssetTransferPage {
                     Ok(())
                 },
             )?);
+            self.update_recipient_warning(ss)?;
         } else if self.tx_popup.is_open() {
             let is_confirmed = self.tx_popup.is_confirmed();
             let r = self.tx_popup.handle_event(
@@ -178,9 +222,13 @@ impl Component for AssetTransferPage {
                 self.asset_popup.set_items(ss.assets_read()?);
                 result.ignore_esc();
             } else {
+                let mut to_changed = false;
                 let r = self.form.handle_event(
                     event.key_event(),
-                    |_, _| Ok(()),
+                    |item, _| {
+                        to_changed |= item == FormItem::To;
+                        Ok(())
+                    },
                     |label, form| {
                         if label == FormItem::TransferButton {
                             let to = form.get_text(FormItem::To);
@@ -207,6 +255,7 @@ impl Component for AssetTransferPage {
                             };
 
                             if self.tx_popup.is_not_sent() || self.tx_popup.is_confirmed() {
+                                self.tx_popup.set_origin("Transfer");
                                 self.tx_popup.set_tx_req(
                                     Network::from_name(&asset.r#type.network)?,
                                     TransactionRequest::default()
@@ -222,6 +271,9 @@ impl Component for AssetTransferPage {
                     },
                 )?;
                 result.merge(r);
+                if to_changed {
+                    self.update_recipient_warning(ss)?;
+                }
             }
         }
 
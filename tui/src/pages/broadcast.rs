@@ -0,0 +1,300 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use alloy::{
+    consensus::{Transaction, TxEnvelope},
+    primitives::{utils::format_ether, Address},
+};
+use gm_ratatui_extra::{
+    act::Act,
+    form::{Form, FormItemIndex, FormWidget},
+    thematize::Thematize,
+};
+use gm_utils::{
+    calldata::CalldataDecoder,
+    disk_storage::DiskStorageInterface,
+    gas,
+    history::{HistoryEntry, HistoryStore},
+    network::{Network, NetworkStore},
+    raw_tx, recipient,
+};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+use tokio::task::JoinHandle;
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+    widgets::{networks_popup, NetworksPopup},
+};
+
+#[derive(Debug, Display, EnumIter, PartialEq)]
+pub enum FormItem {
+    Heading,
+    RawTx,
+    Network,
+    Review,
+    ErrorText,
+    BroadcastButton,
+    Result,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Broadcast Raw Transaction"),
+            FormItem::RawTx => FormWidget::InputBox {
+                label: "Raw Transaction",
+                text: String::new(),
+                empty_text: Some("Hex of a signed transaction"),
+                currency: None,
+            },
+            FormItem::Network => FormWidget::DisplayBox {
+                label: "Network",
+                text: String::new(),
+                empty_text: Some("<press SPACE to select network>"),
+            },
+            FormItem::Review => FormWidget::DisplayText(String::new()),
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+            FormItem::BroadcastButton => FormWidget::Button { label: "Broadcast" },
+            FormItem::Result => FormWidget::DisplayText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+fn fmt_raw_tx(tx: &TxEnvelope, from: Address) -> String {
+    let mut lines = vec![
+        format!("Type: {}", tx.tx_type()),
+        format!("From: {from}"),
+        format!(
+            "To: {}",
+            tx.to()
+                .map(|to| to.to_string())
+                .unwrap_or_else(|| "new contract".to_string())
+        ),
+        format!("Value: {} ether", format_ether(tx.value())),
+        format!("Nonce: {}", tx.nonce()),
+        format!(
+            "Chain id: {}",
+            tx.chain_id()
+                .map(|chain_id| chain_id.to_string())
+                .unwrap_or_else(|| "none".to_string())
+        ),
+        format!("Gas limit: {}", tx.gas_limit()),
+        match (tx.gas_price(), tx.max_priority_fee_per_gas()) {
+            (Some(gas_price), _) => format!("Gas price: {} gwei", gas::format_gwei(gas_price)),
+            (None, priority_fee) => format!(
+                "Max fee: {} gwei | Priority fee: {} gwei",
+                gas::format_gwei(tx.max_fee_per_gas()),
+                gas::format_gwei(priority_fee.unwrap_or_default())
+            ),
+        },
+    ];
+    if !tx.input().is_empty() {
+        lines.push(
+            match CalldataDecoder::load()
+                .ok()
+                .and_then(|decoder| decoder.describe(tx.input()))
+            {
+                Some(call) => format!("Call: {}", call.trim_end()),
+                None => format!("Data: {}", tx.input()),
+            },
+        );
+    }
+    lines.push(format!("Hash: {}", tx.tx_hash()));
+    lines.join("\n")
+}
+
+/// Reviews a transaction signed elsewhere, such as on an offline machine,
+/// and submits it with `eth_sendRawTransaction`.
+#[derive(Debug)]
+pub struct BroadcastPage {
+    form: Form<FormItem, crate::Error>,
+    networks_popup: NetworksPopup,
+    /// Decoded transaction of the raw hex and its sender.
+    decoded: Option<(TxEnvelope, Address)>,
+    broadcast_thread: Option<JoinHandle<()>>,
+}
+
+impl BroadcastPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            form: Form::init(|form| {
+                form.hide_item(FormItem::BroadcastButton);
+                Ok(())
+            })?,
+            networks_popup: networks_popup(),
+            decoded: None,
+            broadcast_thread: None,
+        })
+    }
+
+    pub fn set_raw_tx(&mut self, raw: &str) {
+        *self.form.get_text_mut(FormItem::RawTx) = raw.to_string();
+        self.decode();
+    }
+
+    fn decode(&mut self) {
+        self.decoded = None;
+        self.form.get_text_mut(FormItem::ErrorText).clear();
+        self.form.get_text_mut(FormItem::Result).clear();
+        self.form.hide_item(FormItem::BroadcastButton);
+
+        let raw = self.form.get_text(FormItem::RawTx).trim();
+        if raw.is_empty() {
+            self.form.get_text_mut(FormItem::Review).clear();
+            return;
+        }
+        let decoded = raw_tx::decode_raw_tx(raw)
+            .and_then(|tx| raw_tx::recover_sender(&tx).map(|from| (tx, from)));
+        match decoded {
+            Ok((tx, from)) => {
+                *self.form.get_text_mut(FormItem::Review) = fmt_raw_tx(&tx, from);
+                match raw_tx::network_of(&tx) {
+                    Ok(network) => *self.form.get_text_mut(FormItem::Network) = network.name,
+                    Err(err) => *self.form.get_text_mut(FormItem::ErrorText) = err.to_string(),
+                }
+                self.form.show_item(FormItem::BroadcastButton);
+                self.decoded = Some((tx, from));
+            }
+            Err(err) => *self.form.get_text_mut(FormItem::Review) = err.to_string(),
+        }
+    }
+
+    fn broadcast(&mut self, tr: &mpsc::Sender<Event>) -> crate::Result<()> {
+        let Some((tx, _)) = &self.decoded else {
+            return Ok(());
+        };
+        let network_name = self.form.get_text(FormItem::Network);
+        if network_name.is_empty() {
+            *self.form.get_text_mut(FormItem::ErrorText) = "Please select a network".to_string();
+            return Ok(());
+        }
+        let network = Network::from_name(network_name)?;
+        let raw = raw_tx::encode_raw_tx(tx);
+        *self.form.get_text_mut(FormItem::Result) = "Broadcasting...".to_string();
+        self.form.hide_item(FormItem::BroadcastButton);
+
+        let tr = tr.clone();
+        self.broadcast_thread = Some(tokio::spawn(async move {
+            let _ = match raw_tx::broadcast_raw_tx(&network, &raw).await {
+                Ok(hash) => tr.send(Event::RawTxBroadcast(network.name, hash)),
+                Err(err) => tr.send(Event::RawTxBroadcastError(err.to_string())),
+            };
+        }));
+        Ok(())
+    }
+}
+
+impl Component for BroadcastPage {
+    async fn exit_threads(&mut self) {
+        if let Some(thread) = self.broadcast_thread.take() {
+            thread.abort();
+            let _ = thread.await;
+        }
+    }
+
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        tr: &mpsc::Sender<Event>,
+        _sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+
+        match event {
+            Event::RawTxBroadcast(network_name, hash) => {
+                let network = Network::from_name(network_name)?;
+                let mut text = format!("Submitted transaction {hash}");
+                if let Some(url) = network.get_tx_url(&hash.to_string()) {
+                    text.push_str(&format!("\nExplorer: {url}"));
+                }
+                *self.form.get_text_mut(FormItem::Result) = text;
+                if let Some((tx, from)) = &self.decoded {
+                    HistoryStore::load()?.add(
+                        HistoryEntry::transaction(
+                            "Broadcast",
+                            &network,
+                            *from,
+                            tx.to(),
+                            tx.value(),
+                            Some(tx.nonce()),
+                            *hash,
+                        )
+                        .with_recipient(
+                            recipient::recipient_of_call(tx.to(), tx.input())
+                                .map(|(recipient, _)| recipient),
+                        ),
+                    )?;
+                }
+                return Ok(result);
+            }
+            Event::RawTxBroadcastError(error) => {
+                self.form.get_text_mut(FormItem::Result).clear();
+                *self.form.get_text_mut(FormItem::ErrorText) = error.clone();
+                self.form.show_item(FormItem::BroadcastButton);
+                return Ok(result);
+            }
+            _ => {}
+        }
+
+        if self.networks_popup.is_open() {
+            result.merge(self.networks_popup.handle_event(
+                event.key_event(),
+                |network| -> crate::Result<()> {
+                    *self.form.get_text_mut(FormItem::Network) = network.name.clone();
+                    self.form.get_text_mut(FormItem::ErrorText).clear();
+                    Ok(())
+                },
+            )?);
+        } else if self.form.is_focused(FormItem::Network) && event.is_space_or_enter_pressed() {
+            self.networks_popup.open();
+            self.networks_popup
+                .set_items(Some(NetworkStore::load()?.filter(ss.testnet_mode)));
+        } else {
+            let mut changed = false;
+            let mut pressed = false;
+            let r: Actions = self.form.handle_event(
+                event.key_event(),
+                |item, _| {
+                    changed |= item == FormItem::RawTx;
+                    Ok(())
+                },
+                |item, _| {
+                    pressed |= item == FormItem::BroadcastButton;
+                    Ok(())
+                },
+            )?;
+            result.merge(r);
+            if changed {
+                self.decode();
+            }
+            if pressed {
+                self.broadcast(tr)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+        self.networks_popup.render(area, buf, &ss.theme.popup());
+        area
+    }
+}
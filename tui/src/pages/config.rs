@@ -1,9 +1,13 @@
 use std::{
+    path::PathBuf,
     str::FromStr,
     sync::{atomic::AtomicBool, mpsc, Arc},
 };
 
-use gm_utils::{config::Config, disk_storage::DiskStorageInterface};
+use gm_utils::{
+    config::{Config, DEFAULT_AUTO_LOCK_MINUTES},
+    disk_storage::DiskStorageInterface,
+};
 use ratatui::{buffer::Buffer, layout::Rect};
 use strum::{Display, EnumIter};
 
@@ -28,6 +32,8 @@ pub enum FormItem {
     TestnetMode,
     DeveloperMode,
     Theme,
+    AutoLockMinutes,
+    AbiDirs,
     SaveButton,
     DisplayText,
 }
@@ -61,6 +67,18 @@ impl TryFrom<FormItem> for FormWidget {
                 empty_text: Some("Select a theme"),
                 popup: FilterSelectPopup::new("Select a theme", Some("No themes available")),
             },
+            FormItem::AutoLockMinutes => FormWidget::InputBox {
+                label: "Lock accounts after idle minutes",
+                text: String::new(),
+                empty_text: Some("0 to never lock"),
+                currency: None,
+            },
+            FormItem::AbiDirs => FormWidget::InputBox {
+                label: "ABI directories",
+                text: String::new(),
+                empty_text: Some("Comma separated, e.g. a Foundry out directory"),
+                currency: None,
+            },
             FormItem::SaveButton => FormWidget::Button { label: "Save" },
             FormItem::DisplayText => FormWidget::DisplayText(String::new()),
         };
@@ -82,6 +100,16 @@ impl ConfigPage {
             *form.get_boolean_mut(FormItem::TestnetMode) = config.testnet_mode;
             *form.get_boolean_mut(FormItem::DeveloperMode) = config.developer_mode;
             *form.get_text_mut(FormItem::Theme) = config.theme_name.clone();
+            *form.get_text_mut(FormItem::AutoLockMinutes) = config
+                .auto_lock_minutes
+                .unwrap_or(DEFAULT_AUTO_LOCK_MINUTES)
+                .to_string();
+            *form.get_text_mut(FormItem::AbiDirs) = config
+                .abi_dirs
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
             let popup = form.get_popup_mut(FormItem::Theme);
             popup.set_items(Some(ThemeName::list()));
             popup.set_cursor(&config.theme_name);
@@ -122,6 +150,20 @@ impl Component for ConfigPage {
                 config.developer_mode = form.get_boolean(FormItem::DeveloperMode);
                 let theme_name = form.get_text(FormItem::Theme).clone();
                 config.theme_name = theme::ThemeName::from_str(&theme_name)?.to_string();
+                let auto_lock_minutes = form.get_text(FormItem::AutoLockMinutes).trim();
+                let Ok(auto_lock_minutes) = auto_lock_minutes.parse::<u64>() else {
+                    *form.get_text_mut(FormItem::DisplayText) =
+                        "Please enter the idle minutes as a number".to_string();
+                    return Ok(());
+                };
+                config.auto_lock_minutes = Some(auto_lock_minutes);
+                config.abi_dirs = form
+                    .get_text(FormItem::AbiDirs)
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from)
+                    .collect();
 
                 config.save()?;
 
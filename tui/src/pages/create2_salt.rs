@@ -0,0 +1,152 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use alloy::{
+    hex,
+    primitives::{address, keccak256, Address, B256},
+};
+use gm_ratatui_extra::{
+    act::Act,
+    form::{Form, FormItemIndex, FormWidget},
+};
+use gm_utils::{account::vanity::VanityMode, alloy::StringExt};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+use super::{account_create::AccountCreatePage, Page};
+
+/// Deterministic deployment proxy, available at the same address on most chains.
+const DEFAULT_CREATE2_DEPLOYER: Address = address!("0x4e59b44847b379578588920cA78FbF26c0B4956C");
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Deployer,
+    InitCode,
+    ContinueButton,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Mine CREATE2 salt"),
+            FormItem::Deployer => FormWidget::InputBox {
+                label: "Deployer (factory contract)",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::InitCode => FormWidget::InputBox {
+                label: "Init code hash or init code",
+                text: String::new(),
+                empty_text: Some("0x..."),
+                currency: None,
+            },
+            FormItem::ContinueButton => FormWidget::Button {
+                label: "Continue to mask",
+            },
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct Create2SaltPage {
+    pub form: Form<FormItem, crate::Error>,
+}
+
+impl Create2SaltPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            form: Form::init(|form| {
+                *form.get_text_mut(FormItem::Deployer) = DEFAULT_CREATE2_DEPLOYER.to_string();
+                Ok(())
+            })?,
+        })
+    }
+}
+
+/// Accepts the hash directly, anything else is hashed as the init code.
+fn parse_init_code_hash(input: &str) -> Option<B256> {
+    let bytes = hex::decode(input.trim()).ok()?;
+    if bytes.len() == 32 {
+        Some(B256::from_slice(&bytes))
+    } else if bytes.is_empty() {
+        None
+    } else {
+        Some(keccak256(bytes))
+    }
+}
+
+impl Component for Create2SaltPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut handle_result = Actions::default();
+
+        let r = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |label, form| {
+                if label == FormItem::ContinueButton {
+                    match form.get_text(FormItem::Deployer).trim().parse_as_address() {
+                        Err(err) => {
+                            *form.get_text_mut(FormItem::ErrorText) = err.to_string();
+                        }
+                        Ok(deployer) => {
+                            match parse_init_code_hash(form.get_text(FormItem::InitCode)) {
+                                None => {
+                                    *form.get_text_mut(FormItem::ErrorText) =
+                                        "Please enter the init code hash or the init code as hex"
+                                            .to_string();
+                                }
+                                Some(init_code_hash) => {
+                                    form.get_text_mut(FormItem::ErrorText).clear();
+                                    handle_result.page_inserts.push(Page::AccountCreate(
+                                        AccountCreatePage::new(VanityMode::Create2 {
+                                            deployer,
+                                            init_code_hash,
+                                        }),
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        handle_result.merge(r);
+
+        Ok(handle_result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+
+        area
+    }
+}
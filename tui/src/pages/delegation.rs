@@ -0,0 +1,295 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+    widgets::{networks_popup, NetworksPopup},
+};
+use alloy::{primitives::Address, rpc::types::TransactionRequest};
+use gm_ratatui_extra::{
+    act::Act,
+    form::{Form, FormItemIndex, FormWidget},
+    thematize::Thematize,
+};
+use gm_utils::{
+    account::AccountManager,
+    alloy::StringExt,
+    delegation,
+    disk_storage::DiskStorageInterface,
+    network::{Network, NetworkStore},
+};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+use tokio::task::JoinHandle;
+
+use super::{text::TextPage, tx_popup::TxPopup, Page};
+
+#[derive(Debug, EnumIter, PartialEq, Display)]
+pub enum FormItem {
+    Heading,
+    Account,
+    Network,
+    Status,
+    Delegate,
+    DelegateButton,
+    RevokeButton,
+    Nonce,
+    SignButton,
+    ErrorText,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("EIP-7702 delegation"),
+            FormItem::Account => FormWidget::DisplayBox {
+                label: "Account",
+                text: String::new(),
+                empty_text: None,
+            },
+            FormItem::Network => FormWidget::DisplayBox {
+                label: "Network",
+                text: String::new(),
+                empty_text: Some("<press SPACE to select network>"),
+            },
+            FormItem::Status => {
+                FormWidget::DisplayText("Select a network to see the delegation".to_string())
+            }
+            FormItem::Delegate => FormWidget::InputBox {
+                label: "Delegate contract",
+                text: String::new(),
+                empty_text: Some("Address of the contract your account runs the code of"),
+                currency: None,
+            },
+            FormItem::DelegateButton => FormWidget::Button { label: "Delegate" },
+            FormItem::RevokeButton => FormWidget::Button {
+                label: "Revoke delegation",
+            },
+            FormItem::Nonce => FormWidget::InputBox {
+                label: "Authorization nonce",
+                text: String::new(),
+                empty_text: Some("Nonce of the account when the authorization is sent"),
+                currency: None,
+            },
+            FormItem::SignButton => FormWidget::Button {
+                label: "Sign authorization only",
+            },
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+        };
+        Ok(widget)
+    }
+}
+
+#[derive(Debug)]
+pub struct DelegationPage {
+    pub address: Address,
+    pub form: Form<FormItem, crate::Error>,
+    pub networks_popup: NetworksPopup,
+    pub tx_popup: TxPopup,
+    status_thread: Option<JoinHandle<()>>,
+}
+
+impl DelegationPage {
+    pub fn new(address: Address) -> crate::Result<Self> {
+        Ok(Self {
+            address,
+            form: Form::init(|form| {
+                *form.get_text_mut(FormItem::Account) = address.to_string();
+                form.hide_item(FormItem::RevokeButton);
+                Ok(())
+            })?,
+            networks_popup: networks_popup(),
+            tx_popup: TxPopup::default(),
+            status_thread: None,
+        })
+    }
+
+    fn fetch_status(&mut self, tr: &mpsc::Sender<Event>) -> crate::Result<()> {
+        if let Some(thread) = self.status_thread.take() {
+            thread.abort();
+        }
+        let network = Network::from_name(self.form.get_text(FormItem::Network))?;
+        *self.form.get_text_mut(FormItem::Status) = "Loading delegation...".to_string();
+
+        let tr = tr.clone();
+        let account = self.address;
+        self.status_thread = Some(tokio::spawn(async move {
+            let _ = match delegation::fetch_delegation(&network, account).await {
+                Ok((delegate, nonce)) => tr.send(Event::DelegationUpdate {
+                    account,
+                    network: network.name,
+                    delegate,
+                    nonce,
+                }),
+                Err(err) => tr.send(Event::DelegationError(err.to_string())),
+            };
+        }));
+        Ok(())
+    }
+}
+
+impl Component for DelegationPage {
+    async fn exit_threads(&mut self) {
+        if let Some(thread) = self.status_thread.take() {
+            thread.abort();
+            let _ = thread.await;
+        }
+    }
+
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        area: Rect,
+        tr: &mpsc::Sender<Event>,
+        sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+
+        if let Event::DelegationUpdate {
+            account,
+            network,
+            delegate,
+            nonce,
+        } = event
+        {
+            if account == &self.address && network == self.form.get_text(FormItem::Network) {
+                *self.form.get_text_mut(FormItem::Status) = match delegate {
+                    Some(delegate) => format!("Account is delegated to {delegate}"),
+                    None => "Account is not delegated".to_string(),
+                };
+                *self.form.get_text_mut(FormItem::Nonce) = nonce.to_string();
+                if delegate.is_some() {
+                    self.form.show_item(FormItem::RevokeButton);
+                } else {
+                    self.form.hide_item(FormItem::RevokeButton);
+                }
+            }
+        }
+
+        if self.networks_popup.is_open() {
+            let mut selected = false;
+            result.merge(self.networks_popup.handle_event(
+                event.key_event(),
+                |network| -> crate::Result<()> {
+                    *self.form.get_text_mut(FormItem::Network) = network.name.clone();
+                    self.form.advance_cursor();
+                    selected = true;
+                    Ok(())
+                },
+            )?);
+            if selected {
+                self.fetch_status(tr)?;
+            }
+        } else if self.tx_popup.is_open() {
+            let r = self.tx_popup.handle_event(
+                (event, area, tr, sd, ss),
+                |_| Ok(()),
+                |_| Ok(()),
+                |_, _, _| Ok(()),
+                || Ok(()),
+                || Ok(()),
+            )?;
+            result.merge(r);
+            if !self.tx_popup.is_open() {
+                self.fetch_status(tr)?;
+            }
+        } else if self.form.is_focused(FormItem::Network) && event.is_space_or_enter_pressed() {
+            self.networks_popup.open();
+            self.networks_popup
+                .set_items(Some(NetworkStore::load()?.filter(ss.testnet_mode)));
+        } else {
+            let address = self.address;
+            let tx_popup = &mut self.tx_popup;
+            let r = self.form.handle_event(
+                event.key_event(),
+                |_, _| Ok(()),
+                |label, form| {
+                    form.get_text_mut(FormItem::ErrorText).clear();
+                    if !matches!(
+                        label,
+                        FormItem::DelegateButton | FormItem::RevokeButton | FormItem::SignButton
+                    ) {
+                        return Ok(());
+                    }
+
+                    let network_name = form.get_text(FormItem::Network);
+                    if network_name.is_empty() {
+                        *form.get_text_mut(FormItem::ErrorText) =
+                            "Please select a network".to_string();
+                        return Ok(());
+                    }
+                    let network = Network::from_name(network_name)?;
+                    let delegate = if label == FormItem::RevokeButton {
+                        Address::ZERO
+                    } else {
+                        match form.get_text(FormItem::Delegate).trim().parse_as_address() {
+                            Ok(delegate) => delegate,
+                            Err(err) => {
+                                *form.get_text_mut(FormItem::ErrorText) = err.to_string();
+                                return Ok(());
+                            }
+                        }
+                    };
+
+                    if label == FormItem::SignButton {
+                        let Ok(nonce) = form.get_text(FormItem::Nonce).trim().parse::<u64>() else {
+                            *form.get_text_mut(FormItem::ErrorText) =
+                                "Please enter the nonce as a number".to_string();
+                            return Ok(());
+                        };
+                        AccountManager::ensure_unlocked()?;
+                        let authorization = delegation::sign_authorization(
+                            &address,
+                            network.chain_id as u64,
+                            delegate,
+                            nonce,
+                        )?;
+                        result.page_inserts.push(Page::Text(TextPage::new(format!(
+                            "Authorization of {address} to delegate to {delegate} on {}, it can \
+                             be included in a transaction by anyone until the nonce of the \
+                             account changes.\n\n{}",
+                            network.name,
+                            serde_json::to_string_pretty(&authorization)?
+                        ))));
+                    } else {
+                        AccountManager::ensure_can_sign(&address)?;
+                        // The authorization is signed along with the transaction,
+                        // which is sent to the account itself
+                        tx_popup.set_sender(Some(address));
+                        tx_popup.set_delegation(Some(delegate));
+                        tx_popup.set_origin("Delegation");
+                        tx_popup.set_tx_req(network, TransactionRequest::default().to(address));
+                        tx_popup.open();
+                    }
+                    Ok(())
+                },
+            )?;
+            result.merge(r);
+        }
+
+        Ok(result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+
+        self.networks_popup.render(area, buf, &ss.theme.popup());
+        self.tx_popup.render(area, buf, &ss.theme.popup());
+
+        area
+    }
+}
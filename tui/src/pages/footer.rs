@@ -5,6 +5,7 @@ use ratatui::{layout::Rect, text::Line, widgets::Widget};
 pub struct Footer<'a> {
     pub exit: &'a bool,
     pub is_main_menu: &'a bool,
+    pub lockable: &'a bool,
 }
 
 impl Footer<'_> {
@@ -26,6 +27,11 @@ impl Footer<'_> {
         } else {
             "press control c to quit | press [ESC] to go back"
         };
+        let footer_text = if *self.lockable && !*self.exit {
+            format!("{footer_text} | control l to lock")
+        } else {
+            footer_text.to_string()
+        };
         Line::from(footer_text).style(theme.block()).render(
             Rect {
                 x: area.x + 1,
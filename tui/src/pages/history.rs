@@ -0,0 +1,211 @@
+use std::{
+    sync::{atomic::AtomicBool, mpsc, Arc},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use alloy::primitives::utils::format_ether;
+use gm_ratatui_extra::{cursor::Cursor, filter_select::FilterSelect, thematize::Thematize};
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEventKind},
+    widgets::Widget,
+};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use gm_utils::{
+    disk_storage::DiskStorageInterface,
+    history::{HistoryEntry, HistoryKind, HistoryStore},
+    network::Network,
+};
+
+use super::{text::TextPage, Page};
+
+#[derive(Debug)]
+pub struct HistoryItem(HistoryEntry);
+
+impl HistoryItem {
+    fn line(&self, ss: &SharedState) -> String {
+        format!(
+            "{} {}",
+            fmt_timestamp(self.0.timestamp),
+            self.0
+                .summary_line(|address| ss.labeled_address(address).to_string())
+        )
+    }
+}
+
+fn fmt_timestamp(timestamp: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(timestamp)).to_string()
+}
+
+fn fmt_details(entry: &HistoryEntry, ss: &SharedState) -> String {
+    let mut lines = vec![
+        format!("Time: {}", fmt_timestamp(entry.timestamp)),
+        format!("Origin: {}", entry.origin),
+        format!("Status: {}", entry.status),
+        format!("From: {}", ss.labeled_address(&entry.from)),
+    ];
+    if let Some(summary) = &entry.summary {
+        lines.push(match entry.kind {
+            HistoryKind::Transaction => format!("Call: {summary}"),
+            HistoryKind::Message => format!("Message: {summary}"),
+            HistoryKind::TypedData => format!("Typed data: {summary}"),
+        });
+    }
+    if let Some(signature) = &entry.signature {
+        lines.push(format!("Signature: {signature}"));
+    }
+    if entry.kind == HistoryKind::Transaction {
+        let network = entry.network.as_deref().unwrap_or_default();
+        lines.push(format!("Network: {network}"));
+        lines.push(format!(
+            "To: {}",
+            entry
+                .to
+                .map(|to| ss.labeled_address(&to).to_string())
+                .unwrap_or_else(|| "new contract".to_string())
+        ));
+        if let Some(recipient) = entry
+            .recipient
+            .filter(|recipient| Some(*recipient) != entry.to)
+        {
+            lines.push(format!("Recipient: {}", ss.labeled_address(&recipient)));
+        }
+        lines.push(format!("Value: {} ether", format_ether(entry.value)));
+        if let Some(nonce) = entry.nonce {
+            lines.push(format!("Nonce: {nonce}"));
+        }
+        if let Some(hash) = entry.hash {
+            lines.push(format!("Hash: {hash}"));
+            if let Some(url) = Network::from_name(network)
+                .ok()
+                .and_then(|network| network.get_tx_url(&hash.to_string()))
+            {
+                lines.push(format!("Explorer: {url}"));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+#[derive(Debug)]
+pub struct HistoryPage {
+    full_list: Vec<HistoryItem>,
+    search_string: String,
+    cursor: Cursor,
+    focus: bool,
+}
+
+impl HistoryPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            full_list: HistoryStore::load()?
+                .entries()
+                .into_iter()
+                .map(HistoryItem)
+                .collect(),
+            search_string: String::new(),
+            cursor: Cursor::default(),
+            focus: true,
+        })
+    }
+}
+
+impl Component for HistoryPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.focus = focus;
+    }
+
+    fn reload(&mut self, _ss: &SharedState) -> crate::Result<()> {
+        let fresh = Self::new()?;
+        self.full_list = fresh.full_list;
+        Ok(())
+    }
+
+    fn text_input_mut(&mut self) -> Option<&mut String> {
+        Some(&mut self.search_string)
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: ratatui::prelude::Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let list: Vec<&HistoryItem> = self
+            .full_list
+            .iter()
+            .filter(|item| {
+                item.line(shared_state)
+                    .contains(self.search_string.as_str())
+            })
+            .collect();
+
+        let cursor_max = list.len();
+        self.cursor.handle(event.key_event(), cursor_max);
+
+        let mut result = Actions::default();
+        match event {
+            Event::Input(key_event) if key_event.kind == KeyEventKind::Press => {
+                match key_event.code {
+                    KeyCode::Char(char) => {
+                        if let Some(text_input) = self.text_input_mut() {
+                            text_input.push(char);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        if let Some(text_input) = self.text_input_mut() {
+                            text_input.pop();
+                        }
+                    }
+                    KeyCode::Enter => {
+                        if let Some(item) = list.get(self.cursor.current) {
+                            result
+                                .page_inserts
+                                .push(Page::Text(TextPage::new(fmt_details(
+                                    &item.0,
+                                    shared_state,
+                                ))));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            // Pending transactions of an earlier run got their status
+            Event::HistoryUpdate => self.reload(shared_state)?,
+            _ => {}
+        }
+
+        Ok(result)
+    }
+
+    fn render_component(
+        &self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        shared_state: &SharedState,
+    ) -> ratatui::prelude::Rect
+    where
+        Self: Sized,
+    {
+        let lines = self
+            .full_list
+            .iter()
+            .map(|item| item.line(shared_state))
+            .collect();
+        FilterSelect {
+            full_list: &lines,
+            cursor: &self.cursor,
+            search_string: &self.search_string,
+            focus: self.focus,
+            focus_style: shared_state.theme.select_focused(),
+        }
+        .render(area, buf);
+        area
+    }
+}
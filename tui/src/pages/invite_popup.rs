@@ -152,29 +152,23 @@ impl InvitePopup {
         }
 
         match event {
-            Event::Input(key_event) => {
-                if key_event.kind == KeyEventKind::Press {
-                    match key_event.code {
-                        KeyCode::Enter => {
-                            if self.validity == InviteCodeValidity::Valid
-                                && self.claim_status == InviteCodeClaimStatus::Idle
-                            {
-                                if let Some(invite_code) = self.invite_code.as_ref() {
-                                    let claim_thread = start_claim_thread(
-                                        invite_code,
-                                        ss.try_current_account()?,
-                                        tr,
-                                    )?;
-                                    self.claim_thread = Some(claim_thread);
-                                }
-                            }
+            Event::Input(key_event) if key_event.kind == KeyEventKind::Press => {
+                match key_event.code {
+                    KeyCode::Enter
+                        if self.validity == InviteCodeValidity::Valid
+                            && self.claim_status == InviteCodeClaimStatus::Idle =>
+                    {
+                        if let Some(invite_code) = self.invite_code.as_ref() {
+                            let claim_thread =
+                                start_claim_thread(invite_code, ss.try_current_account()?, tr)?;
+                            self.claim_thread = Some(claim_thread);
                         }
-                        KeyCode::Esc => {
-                            self.close();
-                            result.ignore_esc();
-                        }
-                        _ => {}
                     }
+                    KeyCode::Esc => {
+                        self.close();
+                        result.ignore_esc();
+                    }
+                    _ => {}
                 }
             }
             Event::InviteCodeValidity(validity) => {
@@ -10,9 +10,10 @@ use strum::{Display, EnumIter, IntoEnumIterator};
 
 use super::{
     account::AccountPage, address_book::AddressBookPage, assets::AssetsPage,
-    complete_setup::CompleteSetupPage, config::ConfigPage, dev_key_capture::DevKeyCapturePage,
-    send_message::SendMessagePage, sign_message::SignMessagePage, walletconnect::WalletConnectPage,
-    Page,
+    broadcast::BroadcastPage, complete_setup::CompleteSetupPage, config::ConfigPage,
+    dev_key_capture::DevKeyCapturePage, history::HistoryPage, offline_sign::OfflineSignPage,
+    send_message::SendMessagePage, sign_message::SignMessagePage, tx_queue::TxQueuePage,
+    walletconnect::WalletConnectPage, Page,
 };
 use crate::pages::{network::NetworkPage, shell::ShellPage};
 use crate::{
@@ -35,6 +36,10 @@ pub enum MainMenuItem {
     WalletConnect,
     SignMessage,
     SendMessage,
+    History,
+    TxQueue,
+    OfflineSign,
+    BroadcastRawTx,
     DevKeyInput,
     Shell,
     Config,
@@ -51,6 +56,10 @@ impl MainMenuItem {
             MainMenuItem::WalletConnect => Page::WalletConnect(WalletConnectPage::new()?),
             MainMenuItem::SignMessage => Page::SignMessage(SignMessagePage::new()?),
             MainMenuItem::SendMessage => Page::SendMessage(SendMessagePage::new()?),
+            MainMenuItem::History => Page::History(HistoryPage::new()?),
+            MainMenuItem::TxQueue => Page::TxQueue(TxQueuePage::default()),
+            MainMenuItem::OfflineSign => Page::OfflineSign(OfflineSignPage::new()?),
+            MainMenuItem::BroadcastRawTx => Page::Broadcast(BroadcastPage::new()?),
             MainMenuItem::DevKeyInput => Page::DevKeyCapture(DevKeyCapturePage::default()),
             MainMenuItem::Shell => Page::Shell(ShellPage::default()),
             MainMenuItem::Config => Page::Config(ConfigPage::new()?),
@@ -64,11 +73,17 @@ impl MainMenuItem {
             | MainMenuItem::Networks
             | MainMenuItem::Accounts
             | MainMenuItem::WalletConnect
+            | MainMenuItem::History
+            | MainMenuItem::TxQueue
+            | MainMenuItem::BroadcastRawTx
             | MainMenuItem::DevKeyInput
             | MainMenuItem::Shell
             | MainMenuItem::Config => false,
 
-            MainMenuItem::Portfolio | MainMenuItem::SignMessage | MainMenuItem::SendMessage => true,
+            MainMenuItem::Portfolio
+            | MainMenuItem::SignMessage
+            | MainMenuItem::SendMessage
+            | MainMenuItem::OfflineSign => true,
         }
     }
 
@@ -82,6 +97,10 @@ impl MainMenuItem {
             | MainMenuItem::WalletConnect
             | MainMenuItem::SignMessage
             | MainMenuItem::SendMessage
+            | MainMenuItem::History
+            | MainMenuItem::TxQueue
+            | MainMenuItem::OfflineSign
+            | MainMenuItem::BroadcastRawTx
             | MainMenuItem::Shell
             | MainMenuItem::Config => false,
             MainMenuItem::DevKeyInput => true,
@@ -0,0 +1,210 @@
+use std::{
+    fmt::Display,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+    time::Duration,
+};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use gm_ratatui_extra::{
+    act::Act, confirm_popup::ConfirmPopup, cursor::Cursor, extensions::RectExt, select::Select,
+    thematize::Thematize,
+};
+use gm_utils::{
+    account::{
+        mining_jobs::{MiningJob, MiningJobResult, MiningJobStore},
+        vanity::VanityMode,
+    },
+    disk_storage::DiskStorageInterface,
+};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEventKind},
+    layout::Rect,
+    text::Line,
+    widgets::Widget,
+};
+
+#[derive(Debug)]
+struct JobItem(MiningJob);
+
+impl Display for JobItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let job = &self.0;
+        let mode = match job.mode {
+            VanityMode::Wallet => "Wallet",
+            VanityMode::Deployer => "Deployer",
+            VanityMode::Create2 { .. } => "CREATE2 salt",
+        };
+        write!(f, "#{} {mode} 0x{}", job.id, job.display_pattern())?;
+
+        if let Some(address) = job.pending_account {
+            return write!(f, " found {address}, unlock to add it to your accounts");
+        }
+
+        match job.result {
+            Some(MiningJobResult::Account { address }) => write!(f, " found {address}"),
+            Some(MiningJobResult::Salt { salt, contract }) => {
+                write!(f, " found salt {salt} for {contract}")
+            }
+            None => {
+                write!(
+                    f,
+                    " {:.1}% chance so far, {} attempts in {}",
+                    (job.chance() * 100.0).clamp(0.0, 100.0),
+                    job.attempts,
+                    humantime::format_duration(Duration::from_secs(job.elapsed_secs as u64))
+                )?;
+                if let Some(hash_rate) = job.hash_rate() {
+                    write!(f, " ({hash_rate:.0} H/s)")?;
+                }
+                if job.paused {
+                    write!(f, " [paused]")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MiningJobsPage {
+    cursor: Cursor,
+    focus: bool,
+    list: Vec<JobItem>,
+    delete_popup: ConfirmPopup,
+}
+
+impl MiningJobsPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            cursor: Cursor::default(),
+            focus: true,
+            list: Self::load_list()?,
+            delete_popup: ConfirmPopup::new(
+                "Delete Job",
+                "Are you sure you want to delete this mining job? The progress will be lost."
+                    .to_string(),
+                "Delete",
+                "Cancel",
+            ),
+        })
+    }
+
+    fn load_list() -> crate::Result<Vec<JobItem>> {
+        Ok(MiningJobStore::load()?
+            .jobs()
+            .iter()
+            .cloned()
+            .map(JobItem)
+            .collect())
+    }
+
+    fn selected_id(&self) -> Option<u32> {
+        self.list.get(self.cursor.current).map(|item| item.0.id)
+    }
+}
+
+impl Component for MiningJobsPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.focus = focus;
+    }
+
+    fn reload(&mut self, _ss: &SharedState) -> crate::Result<()> {
+        self.list = Self::load_list()?;
+        Ok(())
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        _shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+
+        if self.delete_popup.is_open() {
+            let id = self.selected_id();
+            let list = &mut self.list;
+            let r = self.delete_popup.handle_event(
+                event.key_event(),
+                area,
+                || -> crate::Result<()> {
+                    if let Some(id) = id {
+                        MiningJobStore::load()?.remove(id)?;
+                        *list = Self::load_list()?;
+                    }
+                    Ok(())
+                },
+                || Ok(()),
+            )?;
+            result.merge(r);
+            self.cursor.current = self.cursor.current.min(self.list.len().saturating_sub(1));
+            return Ok(result);
+        }
+
+        self.cursor.handle(event.key_event(), self.list.len());
+
+        match event {
+            Event::Input(key_event) if key_event.kind == KeyEventKind::Press => {
+                match key_event.code {
+                    KeyCode::Char('p') => {
+                        if let Some(item) = self.list.get_mut(self.cursor.current) {
+                            let job = &mut item.0;
+                            if !job.is_done() {
+                                job.paused = !job.paused;
+                                MiningJobStore::load()?.set_paused(job.id, job.paused)?;
+                            }
+                        }
+                    }
+                    KeyCode::Char('d') if self.selected_id().is_some() => {
+                        self.delete_popup.open();
+                    }
+                    _ => {}
+                }
+            }
+            Event::MiningJobUpdate(job) => {
+                if let Some(item) = self.list.iter_mut().find(|item| item.0.id == job.id) {
+                    item.0 = job.clone();
+                }
+            }
+            _ => {}
+        }
+
+        Ok(result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, shared_state: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        if self.list.is_empty() {
+            "No vanity mining jobs, jobs are started from the create wallet page".render(area, buf);
+        } else {
+            Select {
+                list: &self.list,
+                cursor: &self.cursor,
+                focus: self.focus,
+                focus_style: shared_state.theme.select_focused(),
+            }
+            .render(area.margin_down(2), buf);
+        }
+
+        if area.height > 2 {
+            Line::from(
+                "Jobs are mined one at a time in the background, p: pause or resume, d: delete",
+            )
+            .render(area.margin_top(area.height - 1), buf);
+        }
+
+        self.delete_popup
+            .render(area, buf, &shared_state.theme.popup());
+
+        area
+    }
+}
@@ -1,5 +1,6 @@
 use gm_ratatui_extra::act::Act;
 use gm_ratatui_extra::confirm_popup::ConfirmPopup;
+use gm_ratatui_extra::filter_select_popup::FilterSelectPopup;
 use gm_ratatui_extra::form::{Form, FormItemIndex, FormWidget};
 use gm_ratatui_extra::thematize::Thematize;
 use gm_utils::disk_storage::DiskStorageInterface;
@@ -15,7 +16,9 @@ use crate::pages::token::TokenPage;
 use crate::pages::Page;
 use crate::traits::{Actions, Component};
 use crate::Event;
+use gm_utils::gas::FeePreset;
 use gm_utils::network::{Network, NetworkStore, Token};
+use gm_utils::tx_type::TxTypeSelection;
 use strum::{Display, EnumIter};
 
 #[derive(Debug, Display, EnumIter, PartialEq)]
@@ -34,6 +37,10 @@ pub enum FormItem {
     ExplorerUrl,
     IsTestnet,
     RpcPort,
+    FeePreset,
+    GasLimitPercent,
+    MaxFeeCapGwei,
+    TxType,
     TokensButton,
     SaveButton,
     RemoveButton,
@@ -125,6 +132,30 @@ impl TryFrom<FormItem> for FormWidget {
                 empty_text: None,
                 currency: None,
             },
+            FormItem::FeePreset => FormWidget::SelectInput {
+                label: "Fee Preset",
+                text: String::new(),
+                empty_text: Some("Select the default fees"),
+                popup: FilterSelectPopup::new("Fee Preset", None),
+            },
+            FormItem::GasLimitPercent => FormWidget::InputBox {
+                label: "Gas Limit (% of estimate)",
+                text: String::new(),
+                empty_text: Some("110"),
+                currency: None,
+            },
+            FormItem::MaxFeeCapGwei => FormWidget::InputBox {
+                label: "Max Fee Cap (gwei)",
+                text: String::new(),
+                empty_text: Some("No cap"),
+                currency: None,
+            },
+            FormItem::TxType => FormWidget::SelectInput {
+                label: "Transaction Type",
+                text: String::new(),
+                empty_text: Some("Select the transaction type"),
+                popup: FilterSelectPopup::new("Transaction Type", None),
+            },
             FormItem::TokensButton => FormWidget::Button { label: "Tokens" },
             FormItem::SaveButton => FormWidget::Button { label: "Save" },
             FormItem::RemoveButton => FormWidget::Button { label: "Remove" },
@@ -183,6 +214,29 @@ impl NetworkCreatePage {
                     form.hide_item(FormItem::RemoveButton);
                 }
                 *form.get_boolean_mut(FormItem::IsTestnet) = network.is_testnet;
+                if let Some(rpc_port) = network.rpc_port {
+                    *form.get_text_mut(FormItem::RpcPort) = rpc_port.to_string();
+                }
+
+                let fee_preset = network.fee_preset.unwrap_or_default().to_string();
+                *form.get_text_mut(FormItem::FeePreset) = fee_preset.clone();
+                let popup = form.get_popup_mut(FormItem::FeePreset);
+                popup.set_items(Some(FeePreset::ALL.iter().map(|p| p.to_string()).collect()));
+                popup.set_cursor(&fee_preset);
+                if let Some(percent) = network.gas_limit_percent {
+                    *form.get_text_mut(FormItem::GasLimitPercent) = percent.to_string();
+                }
+                if let Some(cap) = network.max_fee_cap_gwei {
+                    *form.get_text_mut(FormItem::MaxFeeCapGwei) = cap.to_string();
+                }
+
+                let tx_type = network.tx_type.unwrap_or_default().to_string();
+                *form.get_text_mut(FormItem::TxType) = tx_type.clone();
+                let popup = form.get_popup_mut(FormItem::TxType);
+                popup.set_items(Some(
+                    TxTypeSelection::ALL.iter().map(|t| t.to_string()).collect(),
+                ));
+                popup.set_cursor(&tx_type);
                 Ok(())
             })?,
             network_index,
@@ -246,6 +300,25 @@ impl NetworkCreatePage {
                 .not()
                 .then(|| form.get_text(FormItem::RpcPort).clone().parse())
                 .transpose()?,
+            fee_preset: FeePreset::ALL
+                .into_iter()
+                .find(|p| &p.to_string() == form.get_text(FormItem::FeePreset)),
+            gas_limit_percent: form
+                .get_text(FormItem::GasLimitPercent)
+                .is_empty()
+                .not()
+                .then(|| form.get_text(FormItem::GasLimitPercent).trim().parse())
+                .transpose()?,
+            max_fee_cap_gwei: form
+                .get_text(FormItem::MaxFeeCapGwei)
+                .is_empty()
+                .not()
+                .then(|| form.get_text(FormItem::MaxFeeCapGwei).trim().parse())
+                .transpose()
+                .map_err(|e| crate::Error::ParseFloatError(Box::new(e)))?,
+            tx_type: TxTypeSelection::ALL
+                .into_iter()
+                .find(|t| &t.to_string() == form.get_text(FormItem::TxType)),
             tokens: tokens.to_owned(),
         })
     }
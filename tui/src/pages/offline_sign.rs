@@ -0,0 +1,296 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use alloy::{
+    consensus::{Transaction, TxType},
+    hex,
+    primitives::{utils::parse_units, Bytes, TxKind},
+    rpc::types::{AccessList, TransactionRequest},
+};
+use gm_ratatui_extra::{
+    act::Act,
+    filter_select_popup::FilterSelectPopup,
+    form::{Form, FormItemIndex, FormWidget},
+};
+use gm_utils::{
+    account::AccountManager,
+    alloy::StringExt,
+    disk_storage::DiskStorageInterface,
+    gas,
+    history::{HistoryEntry, HistoryStore},
+    raw_tx, recipient,
+    tx_type::TxTypeSelection,
+};
+use qrcode::{render::unicode::Dense1x2, QrCode};
+use ratatui::{buffer::Buffer, layout::Rect};
+use strum::{Display, EnumIter};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+
+use super::{text::TextPage, Page};
+
+/// Types that can be signed without asking the node, auto needs the latest
+/// block.
+const TX_TYPES: [TxTypeSelection; 3] = [
+    TxTypeSelection::Eip1559,
+    TxTypeSelection::Legacy,
+    TxTypeSelection::Eip2930,
+];
+
+#[derive(Debug, Display, EnumIter, PartialEq)]
+pub enum FormItem {
+    Heading,
+    To,
+    Value,
+    Data,
+    ChainId,
+    Nonce,
+    GasLimit,
+    TxType,
+    MaxFee,
+    PriorityFee,
+    ErrorText,
+    SignButton,
+}
+impl FormItemIndex for FormItem {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+impl TryFrom<FormItem> for FormWidget {
+    type Error = crate::Error;
+    fn try_from(value: FormItem) -> crate::Result<Self> {
+        let widget = match value {
+            FormItem::Heading => FormWidget::Heading("Offline Signing"),
+            FormItem::To => FormWidget::InputBox {
+                label: "To",
+                text: String::new(),
+                empty_text: Some("Leave empty to deploy a contract"),
+                currency: None,
+            },
+            FormItem::Value => FormWidget::InputBox {
+                label: "Value (ether)",
+                text: String::new(),
+                empty_text: Some("0"),
+                currency: None,
+            },
+            FormItem::Data => FormWidget::InputBox {
+                label: "Data",
+                text: String::new(),
+                empty_text: Some("0x"),
+                currency: None,
+            },
+            FormItem::ChainId => FormWidget::InputBox {
+                label: "Chain Id",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::Nonce => FormWidget::InputBox {
+                label: "Nonce",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::GasLimit => FormWidget::InputBox {
+                label: "Gas Limit",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::TxType => FormWidget::SelectInput {
+                label: "Transaction Type",
+                text: String::new(),
+                empty_text: Some("Select the transaction type"),
+                popup: FilterSelectPopup::new("Transaction Type", None),
+            },
+            FormItem::MaxFee => FormWidget::InputBox {
+                label: "Max Fee or Gas Price (gwei)",
+                text: String::new(),
+                empty_text: None,
+                currency: None,
+            },
+            FormItem::PriorityFee => FormWidget::InputBox {
+                label: "Priority Fee (gwei)",
+                text: String::new(),
+                empty_text: Some("Not used by legacy and EIP-2930 transactions"),
+                currency: None,
+            },
+            FormItem::ErrorText => FormWidget::ErrorText(String::new()),
+            FormItem::SignButton => FormWidget::Button { label: "Sign" },
+        };
+        Ok(widget)
+    }
+}
+
+/// Signs a transaction from the values entered, without any RPC access, and
+/// shows the raw transaction to broadcast it from another machine.
+#[derive(Debug)]
+pub struct OfflineSignPage {
+    form: Form<FormItem, crate::Error>,
+}
+
+impl OfflineSignPage {
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            form: Form::init(|form| {
+                let tx_type = TX_TYPES[0].to_string();
+                *form.get_text_mut(FormItem::TxType) = tx_type.clone();
+                let popup = form.get_popup_mut(FormItem::TxType);
+                popup.set_items(Some(TX_TYPES.iter().map(|t| t.to_string()).collect()));
+                popup.set_cursor(&tx_type);
+                Ok(())
+            })?,
+        })
+    }
+
+    fn tx_request(form: &Form<FormItem, crate::Error>) -> Result<TransactionRequest, String> {
+        let text = |item| form.get_text(item).trim();
+
+        let to = match text(FormItem::To) {
+            "" => TxKind::Create,
+            to => TxKind::Call(to.parse_as_address().map_err(|err| err.to_string())?),
+        };
+        let value = match text(FormItem::Value) {
+            "" => Default::default(),
+            value => parse_units(value, "ether")
+                .map_err(|_| format!("'{value}' is not a valid amount of ether"))?
+                .get_absolute(),
+        };
+        let input = match text(FormItem::Data) {
+            "" => Bytes::new(),
+            data => hex::decode(data)
+                .map_err(|_| format!("'{data}' is not valid hex data"))?
+                .into(),
+        };
+        let number = |item, name: &str| {
+            text(item)
+                .parse::<u64>()
+                .map_err(|_| format!("Please enter the {name} as a number"))
+        };
+        let max_fee = gas::parse_gwei(text(FormItem::MaxFee)).map_err(|err| err.to_string())?;
+
+        let tx_type = match TX_TYPES
+            .into_iter()
+            .find(|t| t.to_string() == text(FormItem::TxType))
+        {
+            Some(TxTypeSelection::Legacy) => TxType::Legacy,
+            Some(TxTypeSelection::Eip2930) => TxType::Eip2930,
+            _ => TxType::Eip1559,
+        };
+        let mut tx = TransactionRequest {
+            to: Some(to),
+            value: Some(value),
+            input: input.into(),
+            chain_id: Some(number(FormItem::ChainId, "chain id")?),
+            nonce: Some(number(FormItem::Nonce, "nonce")?),
+            gas: Some(number(FormItem::GasLimit, "gas limit")?),
+            transaction_type: Some(tx_type.into()),
+            ..Default::default()
+        };
+        match tx_type {
+            TxType::Legacy => tx.gas_price = Some(max_fee),
+            TxType::Eip2930 => {
+                tx.gas_price = Some(max_fee);
+                tx.access_list = Some(AccessList::default());
+            }
+            _ => {
+                let priority_fee =
+                    gas::parse_gwei(text(FormItem::PriorityFee)).map_err(|err| err.to_string())?;
+                tx.max_fee_per_gas = Some(max_fee);
+                tx.max_priority_fee_per_gas = Some(priority_fee.min(max_fee));
+            }
+        }
+        Ok(tx)
+    }
+}
+
+/// QR code of the text in half height blocks, light on dark so that it scans
+/// on a dark terminal. `None` if the text is too long for a QR code.
+fn qr_code(text: &str) -> Option<String> {
+    let code = QrCode::new(text.as_bytes()).ok()?;
+    Some(
+        code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .build(),
+    )
+}
+
+impl Component for OfflineSignPage {
+    fn set_focus(&mut self, focus: bool) {
+        self.form.set_form_focus(focus);
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        _transmitter: &mpsc::Sender<Event>,
+        _shutdown_signal: &Arc<AtomicBool>,
+        shared_state: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+        let r: Actions = self.form.handle_event(
+            event.key_event(),
+            |_, _| Ok(()),
+            |item, form| {
+                form.get_text_mut(FormItem::ErrorText).clear();
+                if item != FormItem::SignButton {
+                    return Ok(());
+                }
+                let tx = match Self::tx_request(form) {
+                    Ok(tx) => tx,
+                    Err(err) => {
+                        *form.get_text_mut(FormItem::ErrorText) = err;
+                        return Ok(());
+                    }
+                };
+
+                let account = shared_state.try_current_account()?;
+                AccountManager::ensure_can_sign(&account)?;
+                let signed = raw_tx::sign_tx(&AccountManager::load_wallet(&account)?, tx)?;
+                HistoryStore::load()?.add(
+                    HistoryEntry::signed_transaction(
+                        "Offline Sign",
+                        raw_tx::network_of(&signed).ok().as_ref(),
+                        account,
+                        signed.to(),
+                        signed.value(),
+                        Some(signed.nonce()),
+                        *signed.tx_hash(),
+                    )
+                    .with_recipient(
+                        recipient::recipient_of_call(signed.to(), signed.input())
+                            .map(|(recipient, _)| recipient),
+                    ),
+                )?;
+                let raw = raw_tx::encode_raw_tx(&signed).to_string();
+                let qr = qr_code(&raw)
+                    .unwrap_or_else(|| "The transaction is too large for a QR code.".to_string());
+                result.page_inserts.push(Page::Text(TextPage::new(format!(
+                    "Signed by {account} with nonce {} on chain id {}, broadcast it from \
+                     \"Broadcast Raw Tx\" or with `gm broadcast <raw tx>` on a machine with RPC \
+                     access.\n\nHash: {}\n\nRaw transaction:\n{raw}\n\n{qr}",
+                    signed.nonce(),
+                    signed.chain_id().unwrap_or_default(),
+                    signed.tx_hash(),
+                ))));
+                Ok(())
+            },
+        )?;
+        result.merge(r);
+        Ok(result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        self.form.render(area, buf, &ss.theme);
+        area
+    }
+}
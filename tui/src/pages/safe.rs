@@ -0,0 +1,367 @@
+use std::sync::{atomic::AtomicBool, mpsc, Arc};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use alloy::{
+    primitives::{utils::format_ether, Address, U256},
+    rpc::types::{TransactionInput, TransactionRequest},
+};
+use gm_ratatui_extra::{
+    act::Act, confirm_popup::ConfirmPopup, cursor::Cursor, extensions::RectExt, select::Select,
+    thematize::Thematize,
+};
+use gm_utils::{
+    account::AccountManager,
+    disk_storage::DiskStorageInterface,
+    network::Network,
+    safe::{SafeInfo, SafeTransaction, SafeTxStore},
+};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEventKind},
+    layout::Rect,
+    text::Line,
+    widgets::Widget,
+};
+use tokio::task::JoinHandle;
+
+use super::tx_popup::TxPopup;
+
+#[derive(Debug)]
+struct SafeTxItem {
+    tx: SafeTransaction,
+    threshold: Option<usize>,
+    onchain_nonce: Option<U256>,
+}
+
+impl SafeTxItem {
+    /// Another transaction with the same nonce was executed on-chain.
+    fn is_stale(&self) -> bool {
+        self.tx.executed.is_none() && self.onchain_nonce.is_some_and(|n| self.tx.nonce < n)
+    }
+}
+
+impl SafeTxItem {
+    fn line(&self, ss: &SharedState) -> String {
+        let tx = &self.tx;
+        let mut line = format!(
+            "#{} nonce {}: {} ETH to {}",
+            tx.id,
+            tx.nonce,
+            format_ether(tx.value),
+            ss.labeled_address(&tx.to)
+        );
+        if !tx.data.is_empty() {
+            line.push_str(&format!(" with {} bytes of data", tx.data.len()));
+        }
+
+        line.push_str(&if let Some(hash) = tx.executed {
+            format!(" [executed {hash}]")
+        } else if self.is_stale() {
+            " [replaced]".to_string()
+        } else {
+            match self.threshold {
+                Some(threshold) => {
+                    format!(" [{} of {threshold} confirmations]", tx.signatures.len())
+                }
+                None => format!(" [{} confirmations]", tx.signatures.len()),
+            }
+        });
+        line
+    }
+}
+
+#[derive(Debug)]
+pub struct SafePage {
+    safe: Address,
+    network: Network,
+    info: Option<SafeInfo>,
+    cursor: Cursor,
+    focus: bool,
+    list: Vec<SafeTxItem>,
+    delete_popup: ConfirmPopup,
+    tx_popup: TxPopup,
+    info_thread: Option<JoinHandle<()>>,
+}
+
+impl SafePage {
+    pub fn new(safe: Address, network: &str) -> crate::Result<Self> {
+        Ok(Self {
+            safe,
+            network: Network::from_name(network)?,
+            info: None,
+            cursor: Cursor::default(),
+            focus: true,
+            list: Self::load_list(&safe, network, None)?,
+            delete_popup: ConfirmPopup::new(
+                "Delete Transaction",
+                "Are you sure you want to delete this Safe transaction? The confirmations \
+                 collected for it will be lost."
+                    .to_string(),
+                "Delete",
+                "Cancel",
+            ),
+            tx_popup: TxPopup::default(),
+            info_thread: None,
+        })
+    }
+
+    fn load_list(
+        safe: &Address,
+        network: &str,
+        info: Option<&SafeInfo>,
+    ) -> crate::Result<Vec<SafeTxItem>> {
+        let mut transactions = SafeTxStore::load()?.transactions_of(safe, network);
+        transactions.sort_by(|a, b| b.nonce.cmp(&a.nonce).then(b.id.cmp(&a.id)));
+        Ok(transactions
+            .into_iter()
+            .map(|tx| SafeTxItem {
+                tx,
+                threshold: info.map(|info| info.threshold),
+                onchain_nonce: info.map(|info| info.nonce),
+            })
+            .collect())
+    }
+
+    fn refresh_list(&mut self) -> crate::Result<()> {
+        self.list = Self::load_list(&self.safe, &self.network.name, self.info.as_ref())?;
+        self.cursor.current = self.cursor.current.min(self.list.len().saturating_sub(1));
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&SafeTransaction> {
+        self.list.get(self.cursor.current).map(|item| &item.tx)
+    }
+
+    fn fetch_info(&mut self, tr: &mpsc::Sender<Event>) {
+        if let Some(thread) = self.info_thread.take() {
+            thread.abort();
+        }
+        let tr = tr.clone();
+        let network = self.network.clone();
+        let safe = self.safe;
+        self.info_thread = Some(tokio::spawn(async move {
+            let _ = match SafeInfo::fetch(&network, safe).await {
+                Ok(info) => tr.send(Event::SafeInfoUpdate(safe, info)),
+                Err(err) => tr.send(Event::SafeInfoError(err.to_string())),
+            };
+        }));
+    }
+
+    /// Adds the signatures of the owners stored in gm to the selected
+    /// transaction.
+    fn confirm_selected(&mut self) -> crate::Result<()> {
+        let (Some(info), Some(tx)) = (&self.info, self.selected()) else {
+            return Ok(());
+        };
+        if tx.executed.is_some() {
+            return Ok(());
+        }
+        let owners = info.local_owners()?;
+        if owners.is_empty() {
+            return Err(crate::Error::NoLocalSafeOwner(self.safe));
+        }
+
+        AccountManager::ensure_unlocked()?;
+        let mut tx = tx.clone();
+        for owner in owners {
+            tx.confirm(owner, info.chain_id)?;
+        }
+        SafeTxStore::load()?.update(tx)?;
+        self.refresh_list()
+    }
+
+    /// Opens the popup to send `execTransaction` from an owner stored in gm.
+    fn execute_selected(&mut self) -> crate::Result<()> {
+        let (Some(info), Some(tx)) = (&self.info, self.selected()) else {
+            return Ok(());
+        };
+        if tx.executed.is_some() {
+            return Ok(());
+        }
+        if tx.signatures.len() < info.threshold {
+            return Err(crate::Error::SafeThresholdNotMet {
+                confirmations: tx.signatures.len(),
+                threshold: info.threshold,
+            });
+        }
+        let executor = *info
+            .local_owners()?
+            .first()
+            .ok_or(crate::Error::NoLocalSafeOwner(self.safe))?;
+        let calldata = tx.exec_calldata();
+
+        self.tx_popup.set_sender(Some(executor));
+        self.tx_popup.set_origin("Safe execution");
+        self.tx_popup.set_tx_req(
+            self.network.clone(),
+            TransactionRequest::default()
+                .to(self.safe)
+                .input(TransactionInput::from(calldata)),
+        );
+        self.tx_popup.open();
+        Ok(())
+    }
+}
+
+impl Component for SafePage {
+    async fn exit_threads(&mut self) {
+        if let Some(thread) = self.info_thread.take() {
+            thread.abort();
+            let _ = thread.await;
+        }
+    }
+
+    fn set_focus(&mut self, focus: bool) {
+        self.focus = focus;
+    }
+
+    fn reload(&mut self, _ss: &SharedState) -> crate::Result<()> {
+        self.refresh_list()
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        area: Rect,
+        tr: &mpsc::Sender<Event>,
+        sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+
+        if self.info_thread.is_none() {
+            self.fetch_info(tr);
+        }
+
+        if let Event::SafeInfoUpdate(safe, info) = event {
+            if safe == &self.safe {
+                self.info = Some(info.clone());
+                self.refresh_list()?;
+            }
+        }
+
+        if self.tx_popup.is_open() {
+            let id = self.selected().map(|tx| tx.id);
+            let mut executed = None;
+            let r = self.tx_popup.handle_event(
+                (event, area, tr, sd, ss),
+                |tx_hash| {
+                    executed = Some(tx_hash);
+                    Ok(())
+                },
+                |_| Ok(()),
+                |_, _, _| Ok(()),
+                || Ok(()),
+                || Ok(()),
+            )?;
+            result.merge(r);
+
+            if let (Some(hash), Some(id)) = (executed, id) {
+                let mut store = SafeTxStore::load()?;
+                if let Some(tx) = store.get(id).cloned() {
+                    store.update(SafeTransaction {
+                        executed: Some(hash),
+                        ..tx
+                    })?;
+                }
+                self.refresh_list()?;
+            }
+            if !self.tx_popup.is_open() {
+                // The nonce of the Safe has changed if the transaction went through
+                self.fetch_info(tr);
+            }
+            return Ok(result);
+        }
+
+        if self.delete_popup.is_open() {
+            let id = self.selected().map(|tx| tx.id);
+            let r = self.delete_popup.handle_event(
+                event.key_event(),
+                area,
+                || -> crate::Result<()> {
+                    if let Some(id) = id {
+                        SafeTxStore::load()?.remove(id)?;
+                    }
+                    Ok(())
+                },
+                || Ok(()),
+            )?;
+            result.merge(r);
+            self.refresh_list()?;
+            return Ok(result);
+        }
+
+        self.cursor.handle(event.key_event(), self.list.len());
+
+        if let Event::Input(key_event) = event {
+            if key_event.kind == KeyEventKind::Press {
+                match key_event.code {
+                    KeyCode::Char('c') => self.confirm_selected()?,
+                    KeyCode::Char('e') => self.execute_selected()?,
+                    KeyCode::Char('r') => self.fetch_info(tr),
+                    KeyCode::Char('d') if self.selected().is_some() => {
+                        self.delete_popup.open();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, shared_state: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        let info = match &self.info {
+            Some(info) => format!(
+                "Safe {} on {}, {} of {} owners needed, nonce {}",
+                shared_state.labeled_address(&self.safe),
+                self.network.name,
+                info.threshold,
+                info.owners.len(),
+                info.nonce
+            ),
+            None => format!(
+                "Safe {} on {}, loading owners...",
+                shared_state.labeled_address(&self.safe),
+                self.network.name
+            ),
+        };
+        Line::from(info).render(area, buf);
+
+        let list_area = area.margin_top(2).margin_down(2);
+        if self.list.is_empty() {
+            "No transactions, propose one by sending a transfer or contract call from this account"
+                .render(list_area, buf);
+        } else {
+            Select {
+                list: &self
+                    .list
+                    .iter()
+                    .map(|item| item.line(shared_state))
+                    .collect::<Vec<_>>(),
+                cursor: &self.cursor,
+                focus: self.focus,
+                focus_style: shared_state.theme.select_focused(),
+            }
+            .render(list_area, buf);
+        }
+
+        if area.height > 4 {
+            Line::from("c: confirm with stored owners, e: execute, d: delete, r: refresh")
+                .render(area.margin_top(area.height - 1), buf);
+        }
+
+        self.delete_popup
+            .render(area, buf, &shared_state.theme.popup());
+        self.tx_popup.render(area, buf, &shared_state.theme.popup());
+
+        area
+    }
+}
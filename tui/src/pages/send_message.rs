@@ -175,6 +175,7 @@ impl Component for SendMessagePage {
                                 return Err(crate::Error::CannotBeEmpty("Message".to_string()));
                             }
 
+                            self.tx_popup.set_origin("Message");
                             self.tx_popup.set_tx_req(
                                 Network::from_name(network_name)?,
                                 TransactionRequest::default()
@@ -20,13 +20,21 @@ use std::{
     thread,
 };
 
-use alloy::{hex, primitives::Address, rpc::types::TransactionRequest};
+use alloy::{
+    hex,
+    primitives::{Address, FixedBytes},
+    rpc::types::TransactionRequest,
+};
 use gm_ratatui_extra::{act::Act, input_box::InputBox, text_scroll::TextScroll};
 use gm_rpc_proxy::{
     error::RpcProxyError,
-    rpc_types::{ErrorObj, ResponsePayload},
+    rpc_types::{ErrorObj, JsonRpcErrorCode, ResponsePayload},
+};
+use gm_utils::{
+    disk_storage::DiskStorageInterface,
+    network::{Network, NetworkStore},
+    nonce,
 };
-use gm_utils::{disk_storage::DiskStorageInterface, network::NetworkStore};
 use ratatui::{buffer::Buffer, crossterm::event::KeyCode, layout::Rect, widgets::Widget};
 use serde_json::{json, Value};
 use tokio::sync::oneshot;
@@ -37,6 +45,7 @@ use crate::{
     pages::{
         sign_popup::{SignPopup, SignPopupEvent},
         tx_popup::TxPopup,
+        tx_queue::QueuedTxResult,
     },
     traits::{Actions, Component},
     Event,
@@ -52,6 +61,8 @@ enum ShellLine {
 #[derive(Debug)]
 pub struct UserRequest {
     params: UserRequestParams,
+    /// Network of the RPC proxy the request was made to.
+    network_name: String,
     reply_to: Option<oneshot::Sender<ResponsePayload<Value>>>,
 }
 
@@ -87,6 +98,9 @@ pub struct ShellPage {
     requests: Vec<UserRequest>,
     tx_popup: TxPopup,
     sign_popup: SignPopup,
+    /// Set once a transaction was queued, the following ones of the script
+    /// go to the queue without asking.
+    queue_requests: bool,
 
     stdin: Option<process::ChildStdin>,
     stdout_thread: Option<thread::JoinHandle<()>>,
@@ -108,6 +122,7 @@ impl Default for ShellPage {
             requests: vec![],
             tx_popup: TxPopup::default(),
             sign_popup: SignPopup::default(),
+            queue_requests: false,
 
             stdin: None,
             stdout_thread: None,
@@ -148,14 +163,14 @@ impl ShellPage {
         let mut server_threads = vec![];
         let mut env_vars = HashMap::new();
 
-        let mut port = 9393;
-        for network in networks {
+        for (port, network) in (9393..).zip(networks) {
             let rpc_url = network.get_rpc()?.parse()?;
             let secret_clone = secret.clone();
             let tr = tr.clone();
             let port_actual = network.rpc_port.unwrap_or(port);
             let network_name = network.name.clone();
             let current_account = ss.try_current_account()?;
+            let proxy_network = network.clone();
             server_threads.push(tokio::spawn(async move {
                 let tr_clone = tr.clone();
                 let result =
@@ -165,7 +180,21 @@ impl ShellPage {
                             Ok(gm_rpc_proxy::OverrideResult::Sync(
                                 ResponsePayload::Success(json!([current_account])),
                             ))
-                        } else if request.method == "eth_sendTransction" {
+                        } else if request.method == "eth_getTransactionCount"
+                            && is_pending_count_of(&request.params, current_account)
+                        {
+                            // Counts the transactions gm is sending, so that
+                            // scripts picking their own nonces do not collide
+                            let (oneshot_tr, oneshot_rv) =
+                                oneshot::channel::<ResponsePayload<Value>>();
+                            let network = proxy_network.clone();
+                            tokio::spawn(async move {
+                                let _ =
+                                    oneshot_tr.send(pending_nonce(network, current_account).await);
+                            });
+
+                            Ok(gm_rpc_proxy::OverrideResult::Async(oneshot_rv))
+                        } else if request.method == "eth_sendTransaction" {
                             let (oneshot_tr, oneshot_rv) =
                                 oneshot::channel::<ResponsePayload<Value>>();
 
@@ -179,6 +208,7 @@ impl ShellPage {
                                         )
                                         .map_err(RpcProxyError::RequestParseFailed)?,
                                     ),
+                                    network_name: proxy_network.name.clone(),
                                     reply_to: Some(oneshot_tr),
                                 })),
                             )));
@@ -198,6 +228,7 @@ impl ShellPage {
                                         )
                                         .map_err(RpcProxyError::RequestParseFailed)?,
                                     ),
+                                    network_name: proxy_network.name.clone(),
                                     reply_to: Some(oneshot_tr),
                                 })),
                             )));
@@ -219,8 +250,6 @@ impl ShellPage {
                 format!("{}_RPC_URL", network.name.to_uppercase().replace(' ', "_")),
                 format!("http://localhost:{port_actual}/{secret}"),
             );
-
-            port += 1;
         }
 
         self.server_threads = Some(server_threads);
@@ -229,6 +258,108 @@ impl ShellPage {
         Ok(())
     }
 
+    /// Shows the first request, an `eth_sendTransaction` of the script, in the
+    /// tx popup or adds it to the queue. True if the request was queued
+    /// without a popup.
+    fn handle_send_transaction(
+        &mut self,
+        event: &Event,
+        area: Rect,
+        tr: &Sender<Event>,
+        sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+        actions: &mut Actions,
+    ) -> crate::Result<bool> {
+        let Some(request) = self.requests.first_mut() else {
+            return Ok(false);
+        };
+        let UserRequestParams::SendTransaction([tx_req]) = &request.params else {
+            return Ok(false);
+        };
+        let current = ss.try_current_account()?;
+        if let Some(from) = tx_req.from.filter(|from| *from != current) {
+            return Err(crate::Error::RequestAsksForDifferentAddress {
+                asked: from,
+                current,
+            });
+        }
+
+        if self.queue_requests && !self.tx_popup.is_open() {
+            let (id, queued) = ss.tx_queue()?.push(
+                "Shell",
+                Network::from_name(&request.network_name)?,
+                current,
+                *tx_req.clone(),
+            );
+            reply_when_sent(queued, request.reply_to.take());
+            self.requests.remove(0);
+            self.cmd_lines.push(ShellLine::StdOut(format!(
+                "gm: transaction queued as #{id}, sign it from TxQueue"
+            )));
+            self.display.text = self.full_text();
+            return Ok(true);
+        }
+
+        if !self.tx_popup.is_open() {
+            self.tx_popup.open();
+            self.tx_popup.set_origin("Shell");
+            self.tx_popup
+                .set_tx_req(Network::from_name(&request.network_name)?, *tx_req.clone());
+        }
+
+        let mut submitted = None;
+        let mut rpc_error = None;
+        let mut rejected = false;
+        let r = self.tx_popup.handle_event(
+            (event, area, tr, sd, ss),
+            |tx_hash| {
+                submitted = Some(tx_hash);
+                Ok(())
+            },
+            |_| Ok(()),
+            |message, code, data| {
+                rpc_error = Some(ErrorObj {
+                    code: code as i32,
+                    message,
+                    data: data.map(|data| json!(data)),
+                });
+                Ok(())
+            },
+            || {
+                rejected = true;
+                Ok(())
+            },
+            || Ok(()),
+        )?;
+        actions.merge(r);
+
+        let queued = self.tx_popup.take_queued();
+        let payload = send_transaction_reply(
+            submitted,
+            rpc_error,
+            queued.is_some(),
+            rejected || !self.tx_popup.is_open(),
+        );
+        // oneshot's sender is consumed here, cannot be used again, so it is
+        // only taken once there is something to reply
+        if let Some(payload) = payload {
+            if let Some(reply_to) = request.reply_to.take() {
+                reply_to
+                    .send(payload)
+                    .map_err(|_| crate::Error::OneshotSendFailed)?;
+            }
+        }
+        if let Some(queued) = queued {
+            reply_when_sent(queued, request.reply_to.take());
+            self.queue_requests = true;
+        }
+
+        if !self.tx_popup.is_open() {
+            self.requests.remove(0);
+        }
+        Ok(false)
+    }
+
     fn exit_threads_sync(&mut self) {
         if let Some(stdin) = self.stdin.take() {
             drop(stdin);
@@ -245,6 +376,53 @@ impl ShellPage {
     }
 }
 
+/// Reply to an `eth_sendTransaction` once the popup is done with it, `None`
+/// while it is still shown or when the queue replies later.
+fn send_transaction_reply(
+    submitted: Option<FixedBytes<32>>,
+    rpc_error: Option<ErrorObj>,
+    queued: bool,
+    closed: bool,
+) -> Option<ResponsePayload<Value>> {
+    match (submitted, rpc_error) {
+        (Some(tx_hash), _) => Some(ResponsePayload::Success(json!(tx_hash))),
+        (None, Some(error)) => Some(ResponsePayload::Error(error)),
+        (None, None) if queued => None,
+        // Closing the popup before sending rejects the request
+        (None, None) if closed => Some(ResponsePayload::Error(ErrorObj::user_denied())),
+        (None, None) => None,
+    }
+}
+
+/// Replies to the request once its queued transaction is sent, or denies it
+/// if the transaction is removed from the queue.
+fn reply_when_sent(
+    queued: oneshot::Receiver<QueuedTxResult>,
+    reply_to: Option<oneshot::Sender<ResponsePayload<Value>>>,
+) {
+    tokio::spawn(async move {
+        let payload = match queued.await {
+            Ok(QueuedTxResult::Submitted(tx_hash)) => ResponsePayload::Success(json!(tx_hash)),
+            Ok(QueuedTxResult::JsonRpcError {
+                message,
+                code,
+                data,
+            }) => ResponsePayload::Error(ErrorObj {
+                code: code as i32,
+                message,
+                data: data.map(|data| json!(data)),
+            }),
+            Ok(QueuedTxResult::Rejected) | Err(_) => {
+                ResponsePayload::Error(ErrorObj::user_denied())
+            }
+        };
+        // The script may have exited in the meantime
+        if let Some(reply_to) = reply_to {
+            let _ = reply_to.send(payload);
+        }
+    });
+}
+
 impl Component for ShellPage {
     async fn exit_threads(&mut self) {
         self.exit_threads_sync();
@@ -255,7 +433,7 @@ impl Component for ShellPage {
         event: &Event,
         area: Rect,
         tr: &Sender<crate::Event>,
-        _sd: &Arc<AtomicBool>,
+        sd: &Arc<AtomicBool>,
         ss: &SharedState,
     ) -> crate::Result<Actions> {
         let mut actions = Actions::default();
@@ -266,9 +444,12 @@ impl Component for ShellPage {
 
         self.display.handle_event(event.key_event(), area);
 
+        let popup_open = self.tx_popup.is_open() || self.sign_popup.is_open();
+
         #[allow(clippy::single_match)]
         match event {
-            Event::Input(key_event) => {
+            // Keys go to the popup while a request is shown
+            Event::Input(key_event) if !popup_open => {
                 let mut scroll_to_bottom = false;
 
                 if let Some((text_input, text_cursor)) = self.get_user_input_mut() {
@@ -437,8 +618,10 @@ impl Component for ShellPage {
 
         if let Some(request) = self.requests.first_mut() {
             match &request.params {
-                UserRequestParams::SendTransaction(transaction_request) => {
-                    todo!("{transaction_request:?}")
+                UserRequestParams::SendTransaction(_) => {
+                    if self.handle_send_transaction(event, area, tr, sd, ss, &mut actions)? {
+                        return Ok(actions);
+                    }
                 }
                 UserRequestParams::SignMessage((msg, address)) => {
                     let current = ss.try_current_account()?;
@@ -451,6 +634,7 @@ impl Component for ShellPage {
 
                     if !self.sign_popup.is_open() {
                         self.sign_popup.open();
+                        self.sign_popup.set_origin("Shell");
                         self.sign_popup.set_text(msg);
                     }
 
@@ -506,3 +690,66 @@ impl Component for ShellPage {
         area
     }
 }
+
+/// Whether the parameters of `eth_getTransactionCount` ask for the pending
+/// count of the account.
+fn is_pending_count_of(params: &Option<Value>, account: Address) -> bool {
+    let Some(params) = params.as_ref().and_then(Value::as_array) else {
+        return false;
+    };
+    params
+        .first()
+        .and_then(|address| serde_json::from_value::<Address>(address.clone()).ok())
+        == Some(account)
+        && params.get(1).and_then(Value::as_str) == Some("pending")
+}
+
+async fn pending_nonce(network: Network, account: Address) -> ResponsePayload<Value> {
+    let result = match network.get_provider() {
+        Ok(provider) => nonce::next_nonce(&provider, account).await,
+        Err(error) => Err(error),
+    };
+    match result {
+        Ok(nonce) => ResponsePayload::Success(json!(format!("{nonce:#x}"))),
+        Err(error) => ResponsePayload::Error(ErrorObj {
+            code: JsonRpcErrorCode::InternalError.as_i32(),
+            message: error.to_string(),
+            data: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_transaction_request_and_reply() {
+        let params: [Box<TransactionRequest>; 1] = serde_json::from_value(json!([{
+            "from": "0x0101010101010101010101010101010101010101",
+            "to": "0x0202020202020202020202020202020202020202",
+            "value": "0x5"
+        }]))
+        .unwrap();
+        assert_eq!(params[0].from, Some(Address::repeat_byte(1)));
+
+        let reply = |payload: Option<ResponsePayload<Value>>| {
+            payload.map(|payload| serde_json::to_value(payload).unwrap())
+        };
+        let hash = FixedBytes::repeat_byte(3);
+        assert_eq!(
+            reply(send_transaction_reply(Some(hash), None, false, true)),
+            Some(json!({ "result": hash }))
+        );
+        // Still shown in the popup, the script keeps waiting
+        assert_eq!(
+            reply(send_transaction_reply(None, None, false, false)),
+            None
+        );
+        assert_eq!(reply(send_transaction_reply(None, None, true, true)), None);
+        assert_eq!(
+            reply(send_transaction_reply(None, None, false, true)),
+            Some(json!({ "error": { "code": -4001, "message": "User rejected the request." } }))
+        );
+    }
+}
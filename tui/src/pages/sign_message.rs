@@ -10,7 +10,11 @@ use crate::{
     events::Event,
     traits::{Actions, Component},
 };
-use gm_utils::account::AccountManager;
+use gm_utils::{
+    account::AccountManager,
+    disk_storage::DiskStorageInterface,
+    history::{HistoryEntry, HistoryKind, HistoryStore},
+};
 
 #[derive(Debug, Display, EnumIter, PartialEq)]
 pub enum FormItem {
@@ -80,8 +84,16 @@ impl Component for SignMessagePage {
                     let message = form.get_text(FormItem::Message);
 
                     let wallet_address = shared_state.try_current_account()?;
+                    AccountManager::ensure_can_sign(&wallet_address)?;
                     let wallet = AccountManager::load_wallet(&wallet_address)?;
                     let signature = wallet.sign_message_sync(message.as_bytes())?;
+                    HistoryStore::load()?.add(HistoryEntry::signature(
+                        HistoryKind::Message,
+                        "Sign message",
+                        wallet_address,
+                        signature.to_string(),
+                        message.clone(),
+                    ))?;
                     *form.get_text_mut(FormItem::Signature) = format!("Signature:\n{signature}");
                 }
                 Ok(())
@@ -14,7 +14,11 @@ use gm_ratatui_extra::{
     text_scroll::TextScroll,
     thematize::Thematize,
 };
-use gm_utils::account::AccountManager;
+use gm_utils::{
+    account::AccountManager,
+    disk_storage::DiskStorageInterface,
+    history::{HistoryEntry, HistoryKind, HistoryStore},
+};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEventKind},
@@ -23,7 +27,7 @@ use ratatui::{
 };
 use tokio::task::JoinHandle;
 
-use crate::{app::SharedState, theme::Theme, traits::Actions, Event};
+use crate::{app::SharedState, pages::reject_if_watch_only, theme::Theme, traits::Actions, Event};
 
 pub fn sign_thread(
     message: &str,
@@ -72,6 +76,10 @@ pub enum SignPopupEvent {
 #[derive(Default, Debug)]
 pub struct SignPopup {
     text: TextScroll,
+    /// Where the message comes from, recorded in the history.
+    origin: String,
+    /// Current account along with its label, shown in the title.
+    signer: Option<String>,
     open: bool,
     button_cursor: bool, // is cursor on the confirm button?
     status: SignStatus,
@@ -92,6 +100,10 @@ impl SignPopup {
         self.open = false;
     }
 
+    pub fn set_origin(&mut self, origin: impl Into<String>) {
+        self.origin = origin.into();
+    }
+
     pub fn set_text(&mut self, text: &str) {
         self.text.text = text.to_string();
         self.reset();
@@ -115,45 +127,59 @@ impl SignPopup {
     {
         let mut result = Actions::default();
 
+        self.signer = ss
+            .current_account
+            .map(|account| ss.labeled_address(&account).to_string());
         self.text.handle_event(event.key_event(), area);
 
         match event {
-            Event::Input(key_event) => {
-                if key_event.kind == KeyEventKind::Press {
-                    match self.status {
-                        SignStatus::Idle => match key_event.code {
-                            KeyCode::Left => {
-                                self.button_cursor = false;
-                            }
-                            KeyCode::Right => {
-                                self.button_cursor = true;
-                            }
-                            KeyCode::Enter => {
-                                if self.button_cursor {
-                                    self.status = SignStatus::Signing;
-                                    self.sign_thread = Some(sign_thread(&self.text.text, tr, ss)?);
-                                } else {
-                                    self.close();
-                                    on_event(SignPopupEvent::Rejected)?;
-                                }
-                            }
-                            KeyCode::Esc => {
-                                self.close();
-                                on_event(SignPopupEvent::EscapedBeforeSigning)?;
-                            }
-                            _ => {}
-                        },
-                        SignStatus::Signing => {}
-                        SignStatus::Done | SignStatus::Failed => {
-                            if key_event.code == KeyCode::Esc {
+            Event::Input(key_event) if key_event.kind == KeyEventKind::Press => match self.status {
+                SignStatus::Idle => match key_event.code {
+                    KeyCode::Left => {
+                        self.button_cursor = false;
+                    }
+                    KeyCode::Right => {
+                        self.button_cursor = true;
+                    }
+                    KeyCode::Enter => {
+                        if self.button_cursor {
+                            let sender_account = ss.try_current_account()?;
+                            reject_if_watch_only(&sender_account, || {
                                 self.close();
-                                on_event(SignPopupEvent::EscapedAfterSigning)?;
-                            }
+                                on_event(SignPopupEvent::Rejected)
+                            })?;
+                            AccountManager::ensure_can_sign(&sender_account)?;
+                            self.status = SignStatus::Signing;
+                            self.sign_thread = Some(sign_thread(&self.text.text, tr, ss)?);
+                        } else {
+                            self.close();
+                            on_event(SignPopupEvent::Rejected)?;
                         }
                     }
+                    KeyCode::Esc => {
+                        self.close();
+                        on_event(SignPopupEvent::EscapedBeforeSigning)?;
+                    }
+                    _ => {}
+                },
+                SignStatus::Signing => {}
+                SignStatus::Done | SignStatus::Failed => {
+                    if key_event.code == KeyCode::Esc {
+                        self.close();
+                        on_event(SignPopupEvent::EscapedAfterSigning)?;
+                    }
                 }
-            }
+            },
             Event::SignResult(signature) => {
+                if matches!(self.status, SignStatus::Signing) {
+                    HistoryStore::load()?.add(HistoryEntry::signature(
+                        HistoryKind::Message,
+                        &self.origin,
+                        ss.try_current_account()?,
+                        signature.to_string(),
+                        self.text.text.clone(),
+                    ))?;
+                }
                 on_event(SignPopupEvent::Signed(*signature))?;
                 self.status = SignStatus::Done;
 
@@ -180,7 +206,10 @@ impl SignPopup {
             Popup.render(area, buf, &theme);
 
             let inner_area = Popup::inner_area(area);
-            let block = Block::bordered().title("Sign Message");
+            let block = Block::bordered().title(match &self.signer {
+                Some(signer) => format!("Sign Message as {signer}"),
+                None => "Sign Message".to_string(),
+            });
             let block_inner_area = block.inner(inner_area);
             block.render(inner_area, buf);
 
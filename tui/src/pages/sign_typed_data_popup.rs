@@ -23,8 +23,17 @@ use ratatui::{
 use serde_json::Value;
 use tokio::task::JoinHandle;
 
-use crate::{app::SharedState, error::FmtError, theme::Theme, traits::Actions, Event};
-use gm_utils::{account::AccountManager, serde::SerdeResponseParse};
+use crate::{
+    app::SharedState, error::FmtError, pages::reject_if_watch_only, theme::Theme, traits::Actions,
+    Event,
+};
+use gm_utils::{
+    account::AccountManager,
+    calldata::CalldataDecoder,
+    disk_storage::DiskStorageInterface,
+    history::{HistoryEntry, HistoryKind, HistoryStore},
+    serde::SerdeResponseParse,
+};
 
 fn spawn_sign_thread(
     digest: B256,
@@ -62,6 +71,10 @@ enum SignStatus {
 pub struct SignTypedDataPopup {
     typed_data_json: Value,
     display: TextScroll,
+    /// Where the typed data comes from, recorded in the history.
+    origin: String,
+    /// Current account along with its label, shown in the title.
+    signer: Option<String>,
     open: bool,
     button_cursor: bool,
     status: SignStatus,
@@ -79,6 +92,8 @@ impl SignTypedDataPopup {
         Self {
             typed_data_json: Value::Null,
             display: TextScroll::default(),
+            origin: String::new(),
+            signer: None,
             open: false,
             button_cursor: false,
             status: SignStatus::Idle,
@@ -99,6 +114,10 @@ impl SignTypedDataPopup {
         self.open = false;
     }
 
+    pub fn set_origin(&mut self, origin: impl Into<String>) {
+        self.origin = origin.into();
+    }
+
     pub fn set_typed_data(&mut self, v: Value) -> crate::Result<()> {
         if v.get("types").is_none() {
             return Err(crate::Error::TypedDataMissingField("types".to_string()));
@@ -119,6 +138,11 @@ impl SignTypedDataPopup {
             Ok(s) => format!("EIP-712 Typed Data:\n\n{s}\n\n"),
             Err(_) => format!("EIP-712 Typed Data (unprintable):\n\n{v}\n\n"),
         };
+        for (path, call) in CalldataDecoder::load()?.describe_json(&v["message"]) {
+            self.display
+                .text
+                .push_str(&format!("Decoded calldata in {path}: {call}\n"));
+        }
         self.typed_data_json = v;
         self.reset();
         Ok(())
@@ -146,6 +170,9 @@ impl SignTypedDataPopup {
     {
         let mut result = Actions::default();
 
+        self.signer = ss
+            .current_account
+            .map(|account| ss.labeled_address(&account).to_string());
         if self.is_open() {
             let area = Popup::inner_area(area).block_inner().margin_down(3);
 
@@ -163,6 +190,12 @@ impl SignTypedDataPopup {
                             }
                             KeyCode::Enter => {
                                 if self.button_cursor {
+                                    let sender_account = ss.try_current_account()?;
+                                    reject_if_watch_only(&sender_account, || {
+                                        self.close();
+                                        on_cancel()
+                                    })?;
+                                    AccountManager::ensure_can_sign(&sender_account)?;
                                     let typed_data = (&self.typed_data_json)
                                         .serde_parse_custom::<TypedData>()?;
                                     let digest = typed_data
@@ -191,6 +224,24 @@ impl SignTypedDataPopup {
                     }
                 }
                 Event::SignResult(signature) => {
+                    if matches!(self.status, SignStatus::Signing) {
+                        let summary = format!(
+                            "{} for {}",
+                            self.typed_data_json["primaryType"]
+                                .as_str()
+                                .unwrap_or_default(),
+                            self.typed_data_json["domain"]["name"]
+                                .as_str()
+                                .unwrap_or("unnamed domain")
+                        );
+                        HistoryStore::load()?.add(HistoryEntry::signature(
+                            HistoryKind::TypedData,
+                            &self.origin,
+                            ss.try_current_account()?,
+                            signature.to_string(),
+                            summary,
+                        ))?;
+                    }
                     on_signature(signature)?;
                     self.status = SignStatus::Done;
 
@@ -218,7 +269,10 @@ impl SignTypedDataPopup {
             Popup.render(area, buf, &theme);
 
             let inner_area = Popup::inner_area(area);
-            let block = Block::bordered().title("Sign EIP-712 Typed Data");
+            let block = Block::bordered().title(match &self.signer {
+                Some(signer) => format!("Sign EIP-712 Typed Data as {signer}"),
+                None => "Sign EIP-712 Typed Data".to_string(),
+            });
             let block_inner_area = block.inner(inner_area);
             block.render(inner_area, buf);
 
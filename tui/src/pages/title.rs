@@ -35,7 +35,7 @@ impl Component for Title {
             "gm {account}",
             account = shared_state
                 .current_account
-                .map(|a| a.to_string())
+                .map(|a| shared_state.labeled_address(&a).to_string())
                 .unwrap_or("wallet".to_string())
         );
 
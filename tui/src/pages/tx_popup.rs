@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc, Arc,
@@ -7,19 +8,20 @@ use std::{
 };
 
 use alloy::{
-    consensus::{SignableTransaction, TxEnvelope, TxType},
+    consensus::TxType,
+    eips::eip7702::Authorization,
     hex,
-    network::TxSignerSync,
-    primitives::{Address, Bytes, FixedBytes},
-    providers::Provider,
-    rlp::{self, BytesMut, Encodable},
+    primitives::{utils::format_units, Address, Bytes, FixedBytes, TxKind, U256},
+    providers::{utils::Eip1559Estimation, Provider},
     rpc::{json_rpc::ErrorPayload, types::TransactionRequest},
-    transports::RpcError,
+    signers::local::PrivateKeySigner,
+    transports::{RpcError, TransportErrorKind},
 };
 use gm_ratatui_extra::{
     act::Act,
     button::Button,
     extensions::{CustomRender, RectExt},
+    input_box::InputBox,
     popup::Popup,
     text_scroll::TextScroll,
     thematize::Thematize,
@@ -31,10 +33,32 @@ use ratatui::{
     widgets::{Block, Widget},
 };
 use serde_json::Value;
-use tokio::task::JoinHandle;
+use tokio::{sync::oneshot, task::JoinHandle};
 
-use crate::{app::SharedState, error::FmtError, theme::Theme, traits::Actions, Event};
-use gm_utils::{account::AccountManager, network::Network};
+use crate::{
+    app::SharedState, error::FmtError, pages::reject_if_watch_only, theme::Theme, traits::Actions,
+    Event,
+};
+
+use super::tx_queue::QueuedTxResult;
+use gm_utils::{
+    account::{metadata::LabeledAddress, AccountManager},
+    calldata::CalldataDecoder,
+    delegation,
+    disk_storage::DiskStorageInterface,
+    gas::{self, FeePreset, FeePresets, GasSettings},
+    history::{HistoryEntry, HistoryStatus, HistoryStore},
+    network::Network,
+    nonce, raw_tx,
+    receipt::{self, ReceiptSummary},
+    recipient::{self, RecipientRisk},
+    safe,
+    simulate::{self, Simulation},
+    tx_type,
+};
+
+/// Gas of a plain transfer, which is what a cancellation sends.
+const CANCEL_GAS_LIMIT: u64 = 21_000;
 
 #[derive(Clone, Default, Debug, PartialEq)]
 pub enum TxStatus {
@@ -49,12 +73,57 @@ pub enum TxStatus {
     Pending(FixedBytes<32>),
     Confirmed(FixedBytes<32>),
     Failed(FixedBytes<32>),
+    /// The empty transfer replacing the transaction got mined.
+    Cancelled(FixedBytes<32>),
+    /// Stored as a transaction of the Safe, it is executed from the Safe page
+    /// once enough owners confirmed it.
+    SafeProposed {
+        id: u32,
+        confirmations: usize,
+        threshold: usize,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GasField {
+    Limit,
+    MaxFee,
+    PriorityFee,
+}
+
+impl GasField {
+    fn label(self) -> &'static str {
+        match self {
+            GasField::Limit => "Gas limit",
+            GasField::MaxFee => "Max fee per gas",
+            GasField::PriorityFee => "Priority fee per gas",
+        }
+    }
+}
+
+/// Field of the gas settings being edited along with its text and cursor.
+#[derive(Debug)]
+struct GasEdit {
+    field: GasField,
+    text: String,
+    cursor: usize,
 }
 
 #[derive(Default, Debug)]
 pub struct TxPopup {
     network: Network,
     tx_req: TransactionRequest,
+    /// Sends from this account instead of the current account.
+    sender: Option<Address>,
+    /// Delegates the sender to this contract with an EIP-7702 authorization
+    /// signed along with the transaction, `Address::ZERO` revokes it.
+    delegation: Option<Address>,
+    /// Function and arguments of the calldata if they could be decoded.
+    decoded_input: Option<String>,
+    /// Where the transaction comes from, recorded in the history.
+    origin: String,
+    /// Labels of the accounts from the shared state, for the addresses shown.
+    labels: HashMap<Address, String>,
     text: TextScroll,
     open: bool,
     button_cursor: bool, // is cursor on the confirm button?
@@ -62,6 +131,40 @@ pub struct TxPopup {
     status: TxStatus,
     send_tx_thread: Option<JoinHandle<()>>,
     watch_tx_thread: Option<JoinHandle<()>>,
+    /// Gas limit and fees the transaction is signed with, `None` until the
+    /// estimate arrives in which case they are estimated while sending.
+    gas: Option<GasSettings>,
+    fee_presets: Option<FeePresets>,
+    gas_error: Option<String>,
+    gas_edit: Option<GasEdit>,
+    /// Set once the estimate is requested, Safe transactions need none.
+    gas_estimate_started: bool,
+    gas_thread: Option<JoinHandle<()>>,
+    /// Outcome of running the transaction before it is signed, started
+    /// along with the gas estimate.
+    simulation: Option<Simulation>,
+    simulation_error: Option<String>,
+    simulation_thread: Option<JoinHandle<()>>,
+    /// Warnings about the recipient, address poisoning targets the ones the
+    /// user sends to.
+    recipient_risks: Option<Result<Vec<RecipientRisk>, String>>,
+    recipient_thread: Option<JoinHandle<()>>,
+    /// Receipt of the mined transaction, formatted once it arrives.
+    receipt: Option<String>,
+    /// Last transaction sent, a replacement reuses its nonce and bumps its
+    /// fees.
+    sent_tx: Option<TransactionRequest>,
+    /// Every hash sent with the nonce and whether it cancels, the watch
+    /// thread follows all of them since any one can get mined.
+    sent_hashes: Vec<(FixedBytes<32>, bool)>,
+    /// Set while a replacement is being sent, true when it cancels.
+    replacing: Option<bool>,
+    replace_error: Option<String>,
+    /// Last failure to check the status of the sent transaction.
+    watch_error: Option<String>,
+    /// Outcome of the request once it was added to the transaction queue
+    /// instead of being sent.
+    queued: Option<oneshot::Receiver<QueuedTxResult>>,
 }
 
 impl TxPopup {
@@ -84,21 +187,76 @@ impl TxPopup {
         self.open = false;
     }
 
+    pub fn set_sender(&mut self, sender: Option<Address>) {
+        self.sender = sender;
+    }
+
+    pub fn set_origin(&mut self, origin: impl Into<String>) {
+        self.origin = origin.into();
+    }
+
+    pub fn set_delegation(&mut self, delegation: Option<Address>) {
+        self.delegation = delegation;
+        self.update_tx_req();
+    }
+
     pub fn set_tx_req(&mut self, network: Network, tx_req: TransactionRequest) {
         self.network = network;
         self.tx_req = tx_req;
-        self.update_tx_req();
+        self.decoded_input = self
+            .tx_req
+            .input
+            .input()
+            .filter(|data| !data.is_empty())
+            .and_then(|data| CalldataDecoder::load().ok()?.describe(data));
         self.reset();
     }
 
     fn update_tx_req(&mut self) {
-        self.text.text = fmt_tx_request(&self.network, &self.tx_req);
+        let mut text = fmt_tx_request(
+            &self.network,
+            &self.tx_req,
+            self.delegation,
+            self.decoded_input.as_deref(),
+            &self.labels,
+        );
+        match &self.recipient_risks {
+            Some(Ok(risks)) if !risks.is_empty() => {
+                text.push_str("\nWarnings:\n");
+                for risk in risks {
+                    text.push_str(&format!("  ! {risk}\n"));
+                }
+            }
+            Some(Err(error)) => text.push_str(&format!("\nRecipient checks failed: {error}\n")),
+            _ => {}
+        }
+        match (&self.simulation, &self.simulation_error) {
+            (Some(simulation), _) => text.push_str(&fmt_simulation(simulation)),
+            (None, Some(error)) => text.push_str(&format!("\nSimulation failed: {error}\n")),
+            (None, None) if self.gas_estimate_started => text.push_str("\nSimulating...\n"),
+            (None, None) => {}
+        }
+        if let Some(receipt) = &self.receipt {
+            text.push_str(receipt);
+        }
+        self.text.text = text;
+    }
+
+    /// Outcome of the request if it was added to the queue, whoever asked for
+    /// the transaction waits on it for the hash.
+    pub fn take_queued(&mut self) -> Option<oneshot::Receiver<QueuedTxResult>> {
+        self.queued.take()
     }
 
     pub fn is_not_sent(&self) -> bool {
         matches!(self.status, TxStatus::NotSent)
     }
 
+    /// Whether the last transaction sent cancels the requested one.
+    fn is_cancelling(&self) -> bool {
+        self.sent_hashes.last().is_some_and(|(_, cancel)| *cancel)
+    }
+
     pub fn is_confirmed(&self) -> bool {
         matches!(self.status, TxStatus::Confirmed(_))
     }
@@ -107,12 +265,128 @@ impl TxPopup {
         self.button_cursor = false;
         self.status = TxStatus::NotSent;
         self.tx_hash = None;
+        self.gas = None;
+        self.fee_presets = None;
+        self.gas_error = None;
+        self.gas_edit = None;
+        self.gas_estimate_started = false;
+        self.sent_tx = None;
+        self.sent_hashes.clear();
+        self.replacing = None;
+        self.replace_error = None;
+        self.watch_error = None;
+        self.simulation = None;
+        self.simulation_error = None;
+        self.recipient_risks = None;
+        self.receipt = None;
         if let Some(thread) = self.send_tx_thread.take() {
             thread.abort();
         }
         if let Some(thread) = self.watch_tx_thread.take() {
             thread.abort();
         }
+        if let Some(thread) = self.gas_thread.take() {
+            thread.abort();
+        }
+        if let Some(thread) = self.simulation_thread.take() {
+            thread.abort();
+        }
+        if let Some(thread) = self.recipient_thread.take() {
+            thread.abort();
+        }
+        self.update_tx_req();
+    }
+
+    fn sender(&self, ss: &SharedState) -> crate::Result<Address> {
+        match self.sender {
+            Some(sender) => Ok(sender),
+            None => ss.try_current_account(),
+        }
+    }
+
+    /// Height of the gas settings shown above the buttons.
+    fn gas_area_height(&self) -> u16 {
+        if self.is_not_sent() && self.gas_estimate_started {
+            4
+        } else {
+            0
+        }
+    }
+
+    fn set_fee_preset(&mut self, preset: FeePreset) {
+        if let (Some(gas), Some(presets)) = (&mut self.gas, &self.fee_presets) {
+            gas.fees = self.network.cap_fees(presets.estimate(preset));
+            gas.preset = Some(preset);
+        }
+    }
+
+    /// Whether the fees are a single gas price, as for legacy transactions.
+    fn uses_gas_price(&self) -> bool {
+        self.gas
+            .as_ref()
+            .is_some_and(|gas| tx_type::uses_gas_price(gas.tx_type))
+    }
+
+    fn start_gas_edit(&mut self, field: GasField) {
+        let Some(gas) = &self.gas else {
+            return;
+        };
+        if field == GasField::PriorityFee && self.uses_gas_price() {
+            return;
+        }
+        let text = match field {
+            GasField::Limit => gas.gas_limit.to_string(),
+            GasField::MaxFee => gas::format_gwei(gas.fees.max_fee_per_gas),
+            GasField::PriorityFee => gas::format_gwei(gas.fees.max_priority_fee_per_gas),
+        };
+        self.gas_error = None;
+        self.gas_edit = Some(GasEdit {
+            field,
+            cursor: text.len(),
+            text,
+        });
+    }
+
+    /// Applies the edited value, the edit stays open if it is invalid.
+    fn apply_gas_edit(&mut self) {
+        let (Some(edit), Some(gas)) = (&self.gas_edit, &mut self.gas) else {
+            return;
+        };
+        let applied = match edit.field {
+            GasField::Limit => edit
+                .text
+                .trim()
+                .parse::<u64>()
+                .map(|gas_limit| gas.gas_limit = gas_limit)
+                .map_err(|_| format!("'{}' is not a valid gas limit.", edit.text)),
+            // The priority fee is part of the max fee, so one bounds the other,
+            // a gas price is both
+            GasField::MaxFee => gas::parse_gwei(&edit.text)
+                .map(|max_fee| {
+                    gas.fees.max_fee_per_gas = max_fee;
+                    gas.fees.max_priority_fee_per_gas = if tx_type::uses_gas_price(gas.tx_type) {
+                        max_fee
+                    } else {
+                        gas.fees.max_priority_fee_per_gas.min(max_fee)
+                    };
+                    gas.preset = None;
+                })
+                .map_err(|err| err.to_string()),
+            GasField::PriorityFee => gas::parse_gwei(&edit.text)
+                .map(|priority_fee| {
+                    gas.fees.max_priority_fee_per_gas = priority_fee;
+                    gas.fees.max_fee_per_gas = gas.fees.max_fee_per_gas.max(priority_fee);
+                    gas.preset = None;
+                })
+                .map_err(|err| err.to_string()),
+        };
+        match applied {
+            Ok(()) => {
+                self.gas_edit = None;
+                self.gas_error = None;
+            }
+            Err(err) => self.gas_error = Some(err),
+        }
     }
 
     pub fn handle_event<F1, F2, F3, F4, F5>(
@@ -139,63 +413,190 @@ impl TxPopup {
     {
         let mut result = Actions::default();
 
+        if self.labels != ss.account_labels {
+            self.labels = ss.account_labels.clone();
+            self.update_tx_req();
+        }
+
+        if self.open && self.is_not_sent() && !self.gas_estimate_started {
+            let sender = self.sender(ss)?;
+            if AccountManager::safe_network(&sender)?.is_none() {
+                self.gas_estimate_started = true;
+                self.gas_thread = Some(estimate_gas_thread(
+                    sender,
+                    self.delegation,
+                    &self.tx_req,
+                    &self.network,
+                    tr,
+                ));
+                self.simulation_thread =
+                    Some(simulate_tx_thread(sender, &self.tx_req, &self.network, tr));
+                self.update_tx_req();
+            }
+        }
+
+        if self.open && self.is_not_sent() && self.recipient_thread.is_none() {
+            let known = recipient::known_addresses(ss.recent_addresses.as_deref())?;
+            self.recipient_thread = Some(check_recipient_thread(
+                self.sender(ss)?,
+                &self.tx_req,
+                &self.network,
+                known,
+                tr,
+            ));
+        }
+
+        if let Some(edit) = &mut self.gas_edit {
+            if let Event::Input(key_event) = event {
+                if key_event.kind == KeyEventKind::Press {
+                    match key_event.code {
+                        KeyCode::Enter => self.apply_gas_edit(),
+                        KeyCode::Esc => {
+                            self.gas_edit = None;
+                            self.gas_error = None;
+                        }
+                        _ => {
+                            InputBox::handle_event(
+                                Some(key_event),
+                                &mut edit.text,
+                                &mut edit.cursor,
+                            );
+                        }
+                    }
+                }
+            }
+            result.ignore_esc();
+            return Ok(result);
+        }
+
         self.text.handle_event(
             event.key_event(),
-            Popup::inner_area(area).block_inner().margin_down(3),
+            Popup::inner_area(area)
+                .block_inner()
+                .margin_down(3 + self.gas_area_height()),
         );
 
         match event {
-            Event::Input(key_event) => {
-                if key_event.kind == KeyEventKind::Press {
-                    match &self.status {
-                        TxStatus::NotSent => match key_event.code {
-                            KeyCode::Left => {
-                                self.button_cursor = false;
-                            }
-                            KeyCode::Right => {
-                                self.button_cursor = true;
+            Event::Input(key_event) if key_event.kind == KeyEventKind::Press => {
+                match &self.status {
+                    TxStatus::NotSent => match key_event.code {
+                        KeyCode::Char('s') => self.set_fee_preset(FeePreset::Slow),
+                        KeyCode::Char('n') => self.set_fee_preset(FeePreset::Normal),
+                        KeyCode::Char('f') => self.set_fee_preset(FeePreset::Fast),
+                        KeyCode::Char('g') => self.start_gas_edit(GasField::Limit),
+                        KeyCode::Char('m') => self.start_gas_edit(GasField::MaxFee),
+                        KeyCode::Char('p') => self.start_gas_edit(GasField::PriorityFee),
+                        // Authorizations are signed along with the transaction
+                        // and Safe transactions are proposed, neither is queued
+                        KeyCode::Char('q') if self.delegation.is_none() => {
+                            let sender = self.sender(ss)?;
+                            if AccountManager::safe_network(&sender)?.is_none() {
+                                let (_, queued) = ss.tx_queue()?.push(
+                                    &self.origin,
+                                    self.network.clone(),
+                                    sender,
+                                    self.tx_req.clone(),
+                                );
+                                self.queued = Some(queued);
+                                self.close();
                             }
-                            KeyCode::Enter => {
-                                if self.button_cursor {
-                                    self.send_tx_thread = Some(send_tx_thread(
+                        }
+                        KeyCode::Left => {
+                            self.button_cursor = false;
+                        }
+                        KeyCode::Right => {
+                            self.button_cursor = true;
+                        }
+                        KeyCode::Enter => {
+                            if self.button_cursor {
+                                let sender_account = self.sender(ss)?;
+                                if AccountManager::safe_network(&sender_account)?.is_some() {
+                                    // Owners stored in gm confirm the proposal right away
+                                    AccountManager::ensure_unlocked()?;
+                                    self.send_tx_thread = Some(propose_safe_tx_thread(
+                                        sender_account,
                                         &self.tx_req,
                                         &self.network,
                                         tr,
-                                        sd,
-                                        ss,
-                                    )?);
+                                    ));
                                     self.status = TxStatus::Signing;
-                                } else {
-                                    self.close();
-                                    on_cancel()?;
+                                    return Ok(result);
                                 }
+                                reject_if_watch_only(&sender_account, || {
+                                    self.close();
+                                    on_cancel()
+                                })?;
+                                AccountManager::ensure_can_sign(&sender_account)?;
+                                self.send_tx_thread = Some(send_tx_thread(
+                                    sender_account,
+                                    self.delegation,
+                                    self.gas,
+                                    &self.tx_req,
+                                    &self.network,
+                                    tr,
+                                    sd,
+                                ));
+                                self.status = TxStatus::Signing;
+                            } else {
+                                self.close();
+                                on_cancel()?;
                             }
+                        }
+                        KeyCode::Esc => {
+                            self.close();
+                            on_esc()?;
+                        }
+                        _ => {}
+                    },
+                    TxStatus::Pending(_) => match key_event.code {
+                        KeyCode::Char(c @ ('u' | 'c')) if self.replacing.is_none() => {
+                            if let Some(sent_tx) = &self.sent_tx {
+                                let sender_account = self.sender(ss)?;
+                                AccountManager::ensure_can_sign(&sender_account)?;
+                                // Speeding up a cancellation still cancels
+                                let cancel = c == 'c' || self.is_cancelling();
+                                self.replacing = Some(cancel);
+                                self.replace_error = None;
+                                self.send_tx_thread = Some(replace_tx_thread(
+                                    sender_account,
+                                    sent_tx,
+                                    cancel,
+                                    &self.network,
+                                    tr,
+                                    sd,
+                                ));
+                            }
+                        }
+                        KeyCode::Esc => {
+                            self.close();
+                            on_esc()?;
+                        }
+                        _ => {}
+                    },
+                    // The transaction may already be broadcast, the popup stays
+                    // until the send finishes so that the nonce and the reply
+                    // to the request follow what happened
+                    TxStatus::Signing => {}
+                    TxStatus::JsonRpcError { .. }
+                    | TxStatus::Confirmed(_)
+                    | TxStatus::Failed(_)
+                    | TxStatus::Cancelled(_)
+                    | TxStatus::SafeProposed { .. } =>
+                    {
+                        #[allow(clippy::single_match)]
+                        match key_event.code {
                             KeyCode::Esc => {
                                 self.close();
                                 on_esc()?;
                             }
                             _ => {}
-                        },
-                        TxStatus::Signing
-                        | TxStatus::JsonRpcError { .. }
-                        | TxStatus::Pending(_)
-                        | TxStatus::Confirmed(_)
-                        | TxStatus::Failed(_) =>
-                        {
-                            #[allow(clippy::single_match)]
-                            match key_event.code {
-                                KeyCode::Esc => {
-                                    self.close();
-                                    on_esc()?;
-                                }
-                                _ => {}
-                            }
                         }
                     }
                 }
             }
             Event::TxUpdate(status) => {
                 self.status = status.clone();
+                self.watch_error = None;
 
                 match status {
                     TxStatus::JsonRpcError {
@@ -204,17 +605,87 @@ impl TxPopup {
                         data, // TODO pass this as an error object
                     } => on_rpc_error(message.clone(), *code, data.clone())?,
                     TxStatus::Pending(tx_hash) => {
-                        on_tx_submit(*tx_hash)?;
+                        let cancel = self.replacing.take().unwrap_or(false);
+                        self.sent_hashes.push((*tx_hash, cancel));
+                        self.record_sent(*tx_hash, cancel)?;
+                        // Whoever asked is answered with the first hash, a
+                        // replacement is followed by the watch thread
+                        if self.sent_hashes.len() == 1 {
+                            on_tx_submit(*tx_hash)?;
+                        }
 
-                        self.watch_tx_thread =
-                            Some(watch_tx_thread(&self.network, tr, sd, *tx_hash)?);
+                        if let Some(thread) = self.watch_tx_thread.take() {
+                            thread.abort();
+                        }
+                        self.watch_tx_thread = Some(watch_tx_thread(
+                            &self.network,
+                            self.sender(ss)?,
+                            tr,
+                            sd,
+                            self.sent_hashes.clone(),
+                        )?);
                     }
-                    TxStatus::Confirmed(tx_hash) | TxStatus::Failed(tx_hash) => {
+                    TxStatus::Confirmed(tx_hash) => {
+                        self.record_mined(*tx_hash, HistoryStatus::Confirmed)?;
                         on_tx_confirm(*tx_hash)?;
                     }
+                    TxStatus::Failed(tx_hash) => {
+                        self.record_mined(*tx_hash, HistoryStatus::Failed)?;
+                        on_tx_confirm(*tx_hash)?;
+                    }
+                    TxStatus::Cancelled(tx_hash) => {
+                        self.record_mined(*tx_hash, HistoryStatus::Confirmed)?;
+                    }
                     _ => {}
                 }
             }
+            Event::TxGasEstimate(estimate, presets, tx_type) => {
+                self.gas = Some(GasSettings::new(
+                    &self.network,
+                    *tx_type,
+                    *estimate,
+                    self.tx_req.gas,
+                    presets,
+                ));
+                self.fee_presets = Some(*presets);
+            }
+            Event::TxGasEstimateError(error) => self.gas_error = Some(error.clone()),
+            Event::TxSimulation(simulation) => {
+                self.simulation = Some(simulation.clone());
+                self.update_tx_req();
+            }
+            Event::TxSimulationError(error) => {
+                self.simulation_error = Some(error.clone());
+                self.update_tx_req();
+            }
+            Event::TxReceipt(receipt)
+                if self
+                    .sent_hashes
+                    .iter()
+                    .any(|(hash, _)| *hash == receipt.hash) =>
+            {
+                self.receipt = Some(fmt_receipt(receipt, &self.network, ss));
+                self.update_tx_req();
+            }
+            Event::TxReceiptError(error) => {
+                self.receipt = Some(format!("\nReceipt could not be fetched: {error}\n"));
+                self.update_tx_req();
+            }
+            Event::TxRecipientRisks(risks) => {
+                self.recipient_risks = Some(Ok(risks.clone()));
+                self.update_tx_req();
+            }
+            Event::TxRecipientRisksError(error) => {
+                self.recipient_risks = Some(Err(error.clone()));
+                self.update_tx_req();
+            }
+            Event::TxSubmitted(sent_tx) => self.sent_tx = Some(*sent_tx.clone()),
+            Event::TxReplaceError(error) => {
+                self.replacing = None;
+                self.replace_error = Some(error.clone());
+            }
+            // The transaction may still get mined, it stays sent
+            Event::TxWatchError(error) => self.watch_error = Some(error.clone()),
             Event::TxError(_) => self.reset(),
             _ => {}
         }
@@ -222,6 +693,47 @@ impl TxPopup {
         Ok(result)
     }
 
+    fn record_sent(&self, tx_hash: FixedBytes<32>, cancel: bool) -> crate::Result<()> {
+        let Some(sent_tx) = &self.sent_tx else {
+            return Ok(());
+        };
+        let mut entry = HistoryEntry::transaction(
+            &self.origin,
+            &self.network,
+            sent_tx.from.unwrap_or_default(),
+            sent_tx.to.and_then(|to| to.to().copied()),
+            sent_tx.value.unwrap_or_default(),
+            sent_tx.nonce,
+            tx_hash,
+        );
+        if !cancel {
+            entry.recipient = recipient::recipient_of(sent_tx).map(|(recipient, _)| recipient);
+        }
+        entry.summary = if cancel {
+            Some("Cancellation".to_string())
+        } else {
+            self.decoded_input
+                .as_ref()
+                .and_then(|input| input.lines().next())
+                .map(str::to_string)
+        };
+        HistoryStore::load()?.add(entry)?;
+        Ok(())
+    }
+
+    /// Sets the status of the mined transaction, the others sent with its
+    /// nonce got replaced.
+    fn record_mined(&self, tx_hash: FixedBytes<32>, status: HistoryStatus) -> crate::Result<()> {
+        let mut store = HistoryStore::load()?;
+        for (hash, _) in &self.sent_hashes {
+            if *hash != tx_hash {
+                store.set_status(*hash, HistoryStatus::Replaced)?;
+            }
+        }
+        store.set_status(tx_hash, status)?;
+        Ok(())
+    }
+
     pub fn render(&self, area: Rect, buf: &mut Buffer, theme: &Theme)
     where
         Self: Sized,
@@ -236,11 +748,18 @@ impl TxPopup {
             let block_inner_area = block.inner(inner_area);
             block.render(inner_area, buf);
 
-            let [text_area, button_area] =
-                Layout::vertical([Constraint::Min(1), Constraint::Length(3)])
-                    .areas(block_inner_area);
+            let [text_area, gas_area, button_area] = Layout::vertical([
+                Constraint::Min(1),
+                Constraint::Length(self.gas_area_height()),
+                // One more line for a failed status check
+                Constraint::Length(3 + u16::from(self.watch_error.is_some())),
+            ])
+            .areas(block_inner_area);
 
             self.text.render(text_area, buf);
+            if self.gas_area_height() > 0 {
+                self.render_gas(gas_area, buf, &theme);
+            }
 
             let [left_area, right_area] =
                 Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -272,8 +791,29 @@ impl TxPopup {
                     "Signing and sending transaction...".render(button_area.margin_top(1), buf);
                 }
                 TxStatus::Pending(tx_hash) => {
-                    format!("Transaction pending... Hash: {tx_hash}")
-                        .render(button_area.margin_top(1), buf);
+                    let hint = match (self.replacing, &self.replace_error) {
+                        (Some(true), _) => "Sending cancellation...".to_string(),
+                        (Some(false), _) => "Sending with higher fees...".to_string(),
+                        (None, Some(error)) => format!("Replacement failed: {error}"),
+                        (None, None) if self.is_cancelling() => {
+                            "u: speed up the cancellation".to_string()
+                        }
+                        (None, None) if self.sent_tx.is_some() => {
+                            "u: speed up, c: cancel".to_string()
+                        }
+                        (None, None) => String::new(),
+                    };
+                    let watch_error = self
+                        .watch_error
+                        .as_ref()
+                        .map(|error| format!("Status check failed, retrying: {error}"))
+                        .unwrap_or_default();
+                    [
+                        format!("Transaction pending... Hash: {tx_hash}"),
+                        hint,
+                        watch_error,
+                    ]
+                    .render(button_area.margin_top(1), buf, false);
                 }
                 TxStatus::Confirmed(tx_hash) => {
                     [
@@ -286,43 +826,249 @@ impl TxPopup {
                     format!("Transaction failed! Hash: {tx_hash}")
                         .render(button_area.margin_top(1), buf);
                 }
+                TxStatus::Cancelled(tx_hash) => {
+                    [
+                        format!("Transaction cancelled! Hash: {tx_hash}"),
+                        "Press ESC to close".to_string(),
+                    ]
+                    .render(button_area.margin_top(1), buf, false);
+                }
+                TxStatus::SafeProposed {
+                    id,
+                    confirmations,
+                    threshold,
+                } => {
+                    [
+                        format!(
+                            "Safe transaction #{id} proposed with {confirmations} of {threshold} confirmations"
+                        ),
+                        "Execute it from the Safe transactions in Accounts".to_string(),
+                    ]
+                    .render(button_area.margin_top(1), buf, false);
+                }
             }
         }
     }
 }
 
-fn fmt_tx_request(network: &Network, tx_req: &TransactionRequest) -> String {
-    format!(
-        "Network: {}\nTo: {:?}\nValue: {}\nData: {:?}\n",
+impl TxPopup {
+    fn render_gas(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        if let Some(edit) = &self.gas_edit {
+            let gwei = "gwei".to_string();
+            InputBox {
+                focus: true,
+                label: match edit.field {
+                    GasField::MaxFee if self.uses_gas_price() => "Gas price",
+                    field => field.label(),
+                },
+                text: &edit.text,
+                empty_text: None,
+                currency: (edit.field != GasField::Limit).then_some(&gwei),
+            }
+            .render(area, buf, &edit.cursor, theme);
+            self.gas_error
+                .as_deref()
+                .unwrap_or("Enter: apply, Esc: discard")
+                .render(area.margin_top(3), buf);
+            return;
+        }
+
+        let lines = match (&self.gas, &self.gas_error) {
+            (Some(gas), _) if tx_type::uses_gas_price(gas.tx_type) => [
+                format!(
+                    "Gas limit: {} | Gas price: {} gwei ({}, {})",
+                    gas.gas_limit,
+                    gas::format_gwei(gas.fees.max_fee_per_gas),
+                    gas.preset
+                        .map(|preset| preset.to_string())
+                        .unwrap_or_else(|| "custom".to_string()),
+                    gas.tx_type
+                ),
+                "s/n/f: slow/normal/fast fees, g: gas limit, m: gas price, q: add to queue"
+                    .to_string(),
+            ],
+            (Some(gas), _) => [
+                format!(
+                    "Gas limit: {} | Max fee: {} gwei | Priority fee: {} gwei ({})",
+                    gas.gas_limit,
+                    gas::format_gwei(gas.fees.max_fee_per_gas),
+                    gas::format_gwei(gas.fees.max_priority_fee_per_gas),
+                    gas.preset
+                        .map(|preset| preset.to_string())
+                        .unwrap_or_else(|| "custom".to_string())
+                ),
+                "s/n/f: slow/normal/fast fees, g: gas limit, m: max fee, p: priority fee, q: add \
+                 to queue"
+                    .to_string(),
+            ],
+            (None, Some(error)) => [
+                format!("Gas estimation failed: {error}"),
+                "The gas is estimated again when the transaction is sent".to_string(),
+            ],
+            (None, None) => ["Estimating gas and fees...".to_string(), String::new()],
+        };
+        lines.render(area.margin_top(1), buf, false);
+    }
+}
+
+fn fmt_tx_request(
+    network: &Network,
+    tx_req: &TransactionRequest,
+    delegation: Option<Address>,
+    decoded_input: Option<&str>,
+    labels: &HashMap<Address, String>,
+) -> String {
+    let labeled = |address: &Address| {
+        LabeledAddress {
+            address,
+            label: labels.get(address),
+        }
+        .to_string()
+    };
+    let mut text = format!(
+        "Network: {}\nTo: {}\nValue: {}\nData: {:?}\n",
         network,
-        tx_req.to.unwrap_or_default(),
+        match tx_req.to.and_then(|to| to.to().copied()) {
+            Some(to) => labeled(&to),
+            None => "new contract".to_string(),
+        },
         tx_req.value.unwrap_or_default(),
         tx_req.input.input().unwrap_or_default()
+    );
+    if let Some(call) = decoded_input {
+        text.push_str(&format!("Call: {call}"));
+    }
+    match delegation {
+        Some(Address::ZERO) => text.push_str("Revokes the EIP-7702 delegation of your account\n"),
+        Some(delegate) => {
+            text.push_str(&format!(
+                "Delegates your account to {} (EIP-7702)\n",
+                labeled(&delegate)
+            ));
+        }
+        None => {}
+    }
+    for auth in tx_req.authorization_list.iter().flatten() {
+        let authority = auth
+            .recover_authority()
+            .map(|authority| labeled(&authority))
+            .unwrap_or_else(|_| "invalid signature".to_string());
+        text.push_str(&format!(
+            "Authorization: {authority} delegates to {} on chain {} with nonce {}\n",
+            labeled(&auth.address),
+            auth.chain_id,
+            auth.nonce
+        ));
+    }
+    text
+}
+
+fn fmt_simulation(simulation: &Simulation) -> String {
+    let mut text = match &simulation.revert_reason {
+        Some(reason) => format!("\nSimulation: the transaction reverts: {reason}\n"),
+        None => "\nSimulation: the transaction succeeds\n".to_string(),
+    };
+    if simulation.balance_changes.is_empty() {
+        text.push_str("No balance changes besides the fee\n");
+    } else {
+        text.push_str("Balance changes besides the fee:\n");
+        for change in &simulation.balance_changes {
+            text.push_str(&format!("  {change}\n"));
+        }
+    }
+    if !simulation.traced {
+        text.push_str("Token transfers are unknown, the node does not support debug_traceCall\n");
+    }
+    text
+}
+
+/// USD price of the native currency of the network from the assets of the
+/// current account.
+fn native_usd_price(network: &Network, ss: &SharedState) -> Option<f64> {
+    ss.assets_read()
+        .ok()
+        .flatten()?
+        .into_iter()
+        .find(|asset| {
+            asset.r#type.network == network.name && asset.r#type.token_address.is_native()
+        })
+        .and_then(|asset| asset.r#type.price.usd_price())
+}
+
+/// Gas used and the fee paid in the native currency and in USD if its price
+/// is known.
+pub(crate) fn fmt_receipt_fee(
+    receipt: &ReceiptSummary,
+    network: &Network,
+    ss: &SharedState,
+) -> String {
+    let fee = format_units(receipt.fee, network.native_decimals.unwrap_or(18))
+        .unwrap_or_else(|_| receipt.fee.to_string());
+    let usd = fee
+        .parse::<f64>()
+        .ok()
+        .zip(native_usd_price(network, ss))
+        .map(|(fee, price)| format!(" (${:.2})", fee * price))
+        .unwrap_or_default();
+    format!(
+        "gas used {} at {} gwei, fee {} {}{usd}",
+        receipt.gas_used,
+        gas::format_gwei(receipt.effective_gas_price),
+        fee.trim_end_matches('0').trim_end_matches('.'),
+        network.symbol.as_deref().unwrap_or("ETH"),
     )
 }
 
+fn fmt_receipt(receipt: &ReceiptSummary, network: &Network, ss: &SharedState) -> String {
+    let mut text = format!("\nReceipt: {}\n", fmt_receipt_fee(receipt, network, ss));
+    if let Some(contract) = receipt.contract_address {
+        text.push_str(&format!("Contract created at {contract}\n"));
+    }
+    if !receipt.token_events.is_empty() {
+        text.push_str("Token events of your account:\n");
+        for event in &receipt.token_events {
+            text.push_str(&format!("  {event}\n"));
+        }
+    }
+    text
+}
+
 pub enum SendTxResult {
-    Submitted(FixedBytes<32>),
+    /// Hash along with the transaction as it was signed.
+    Submitted(FixedBytes<32>, Box<TransactionRequest>),
     JsonRpcError(ErrorPayload),
 }
 
 pub fn send_tx_thread(
+    sender_account: Address,
+    delegation: Option<Address>,
+    gas: Option<GasSettings>,
     tx_req: &TransactionRequest,
     network: &Network,
     tr: &mpsc::Sender<Event>,
     shutdown_signal: &Arc<AtomicBool>,
-    shared_state: &SharedState,
-) -> crate::Result<JoinHandle<()>> {
+) -> JoinHandle<()> {
     let tr = tr.clone();
     let shutdown_signal = shutdown_signal.clone();
-    let sender_account = shared_state.try_current_account()?;
     let network = network.clone();
     let tx_req = tx_req.clone();
-    Ok(tokio::spawn(async move {
-        let _ = match run(sender_account, network, tx_req, shutdown_signal).await {
-            Ok(send_result) => tr.send(Event::TxUpdate(match send_result {
-                SendTxResult::Submitted(hash) => TxStatus::Pending(hash),
-                SendTxResult::JsonRpcError(error_payload) => TxStatus::JsonRpcError {
+    tokio::spawn(async move {
+        let _ = match run(
+            sender_account,
+            delegation,
+            gas,
+            network,
+            tx_req,
+            shutdown_signal,
+        )
+        .await
+        {
+            Ok(SendTxResult::Submitted(hash, sent)) => {
+                let _ = tr.send(Event::TxSubmitted(sent));
+                tr.send(Event::TxUpdate(TxStatus::Pending(hash)))
+            }
+            Ok(SendTxResult::JsonRpcError(error_payload)) => {
+                tr.send(Event::TxUpdate(TxStatus::JsonRpcError {
                     message: error_payload.message.to_string(),
                     code: error_payload.code,
                     data: error_payload.data.and_then(|data| {
@@ -332,13 +1078,15 @@ pub fn send_tx_thread(
                             .and_then(|s| hex::decode(s).ok())
                             .map(Bytes::from)
                     }),
-                },
-            })),
+                }))
+            }
             Err(err) => tr.send(Event::TxError(err.fmt_err("TxSubmitError"))),
         };
 
         async fn run(
             sender_account: Address,
+            delegation: Option<Address>,
+            gas: Option<GasSettings>,
             network: Network,
             mut tx: TransactionRequest,
             shutdown_signal: Arc<AtomicBool>,
@@ -347,116 +1095,407 @@ pub fn send_tx_thread(
 
             let wallet = AccountManager::load_wallet(&sender_account)?;
 
-            let nonce = provider.get_transaction_count(sender_account).await?;
-            tx.nonce = Some(nonce);
-
             // Fetch chain ID
             let chain_id = provider.get_chain_id().await?;
             tx.chain_id = Some(chain_id);
 
-            tx.from = Some(sender_account);
+            // A nonce set by the request is kept, scripts manage their own.
+            // The guard gives the nonce back if this task ends or is aborted
+            // before the transaction was sent.
+            let nonce = match tx.nonce {
+                Some(nonce) => nonce::mark_nonce_used(chain_id, sender_account, nonce)?,
+                None => nonce::reserve_nonce(&provider, sender_account).await?,
+            };
+            tx.nonce = Some(nonce.nonce());
 
-            // Estimate gas fees
-            let fee_estimation = provider.estimate_eip1559_fees().await?;
-            tx.max_priority_fee_per_gas = Some(fee_estimation.max_priority_fee_per_gas);
-            tx.max_fee_per_gas = Some(gm_stamp(fee_estimation.max_fee_per_gas));
+            tx.from = Some(sender_account);
 
-            let estimate_result = provider.estimate_gas(tx.clone()).await;
+            let result = fill_and_send(
+                &provider,
+                &wallet,
+                delegation,
+                gas,
+                &network,
+                tx,
+                &shutdown_signal,
+            )
+            .await;
+            if let Ok(SendTxResult::Submitted(hash, _)) = &result {
+                nonce.record_sent(*hash)?;
+            }
+            result
+        }
+    })
+}
 
-            // Handle an edge case where node errors with "insufficient funds" error during revert
-            let estimate_result = if estimate_result.is_err()
-                && format!("{:?}", &estimate_result).contains("insufficient funds")
-            {
-                // re-estimate wihout gas price fields
-                let mut tx_temp = tx.clone();
-                tx_temp.gas_price = None;
-                tx_temp.max_fee_per_gas = None;
-                tx_temp.max_priority_fee_per_gas = None;
+/// Adds the authorization and the gas settings to a transaction with its
+/// nonce set, then signs and submits it.
+async fn fill_and_send(
+    provider: &impl Provider,
+    wallet: &PrivateKeySigner,
+    delegation: Option<Address>,
+    gas: Option<GasSettings>,
+    network: &Network,
+    mut tx: TransactionRequest,
+    shutdown_signal: &AtomicBool,
+) -> crate::Result<SendTxResult> {
+    let chain_id = tx.chain_id.unwrap_or_default();
+    let nonce = tx.nonce.unwrap_or_default();
 
-                provider.estimate_gas(tx_temp).await
-            } else {
-                estimate_result
-            };
+    if let Some(delegate) = delegation {
+        // The transaction bumps the nonce of the sender before the
+        // authorization is processed
+        tx.authorization_list
+            .get_or_insert_default()
+            .push(delegation::authorize(
+                wallet,
+                Authorization {
+                    chain_id: U256::from(chain_id),
+                    address: delegate,
+                    nonce: nonce + 1,
+                },
+            )?);
+    }
 
+    let gas = match gas {
+        Some(gas) => gas,
+        None => {
+            let tx_type = resolve_tx_type(network, delegation).await?;
+            let presets = gas::fetch_fee_presets(network, tx_type).await?;
             // Bubble up error from estimation to client side
-            let Ok(estimate) = estimate_result else {
-                let err = estimate_result.err().unwrap();
-                return match err {
-                    RpcError::ErrorResp(payload) => Ok(SendTxResult::JsonRpcError(payload.clone())),
-                    _ => Err(crate::Error::from(err)),
-                };
+            let estimate = match estimate_gas(provider, tx.clone()).await {
+                Ok(estimate) => estimate,
+                Err(RpcError::ErrorResp(payload)) => {
+                    return Ok(SendTxResult::JsonRpcError(payload))
+                }
+                Err(err) => return Err(crate::Error::from(err)),
             };
+            GasSettings::new(network, tx_type, estimate, tx.gas, &presets)
+        }
+    };
+    tx.gas = Some(gas.gas_limit);
+    set_fees(&mut tx, gas.tx_type, gas.fees);
 
-            let estimate_plus = estimate * 110 / 100; // TODO allow to configure gas limit)
-            if let Some(gas) = tx.gas {
-                tx.gas = Some(std::cmp::max(gas, estimate_plus));
-            } else {
-                tx.gas = Some(estimate_plus);
-            }
+    if gas.tx_type == TxType::Eip2930 && tx.access_list.is_none() {
+        tx.access_list = Some(
+            tx_type::create_access_list(provider, &tx)
+                .await
+                .unwrap_or_default(),
+        );
+    }
 
-            tx.transaction_type = Some(2); // EIP-1559 transaction type
+    sign_and_send(provider, wallet, tx, shutdown_signal).await
+}
 
-            let mut tx = tx
-                .transaction_type(TxType::Eip1559.into())
-                .build_typed_tx()
-                .map_err(|tx| crate::Error::TxTypeNotSpecified(Box::new(tx)))?
-                .eip1559()
-                .ok_or(crate::Error::TxTypeIsNotEip1559)?
-                .clone();
+/// Type the transaction is signed with, authorizations are only carried by
+/// EIP-7702 transactions.
+pub(crate) async fn resolve_tx_type(
+    network: &Network,
+    delegation: Option<Address>,
+) -> crate::Result<TxType> {
+    Ok(match delegation {
+        Some(_) => TxType::Eip7702,
+        None => tx_type::resolve_tx_type(network).await?,
+    })
+}
 
-            // Sign transaction
-            let signature = wallet.sign_transaction_sync(&mut tx)?;
-            let tx_signed = SignableTransaction::into_signed(tx, signature);
+/// Sets the fees and the type they belong to, the gas price is the max fee
+/// for the types priced with one.
+fn set_fees(tx: &mut TransactionRequest, tx_type: TxType, fees: Eip1559Estimation) {
+    tx.transaction_type = Some(tx_type.into());
+    if tx_type::uses_gas_price(tx_type) {
+        tx.gas_price = Some(gm_stamp(fees.max_fee_per_gas));
+        tx.max_fee_per_gas = None;
+        tx.max_priority_fee_per_gas = None;
+    } else {
+        tx.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas);
+        tx.max_fee_per_gas = Some(gm_stamp(fees.max_fee_per_gas));
+        tx.gas_price = None;
+    }
+}
 
-            // Encode transaction
-            let mut out = BytesMut::new();
-            let tx_typed = TxEnvelope::Eip1559(tx_signed);
-            tx_typed.encode(&mut out);
-            let out = rlp::decode_exact::<Bytes>(out)?;
+/// Signs the filled transaction and submits it.
+async fn sign_and_send(
+    provider: &impl Provider,
+    wallet: &PrivateKeySigner,
+    tx: TransactionRequest,
+    shutdown_signal: &AtomicBool,
+) -> crate::Result<SendTxResult> {
+    let sent = Box::new(tx.clone());
 
-            if shutdown_signal.load(Ordering::Relaxed) {
-                return Err(crate::Error::Abort("shutdown signal received"));
+    // Sign transaction with the type the fees were set for
+    let out = raw_tx::encode_raw_tx(&raw_tx::sign_tx(wallet, tx)?);
+
+    if shutdown_signal.load(Ordering::Relaxed) {
+        return Err(crate::Error::Abort("shutdown signal received"));
+    }
+
+    // Submit transaction
+    match provider.send_raw_transaction(&out).await {
+        Ok(result) => Ok(SendTxResult::Submitted(*result.tx_hash(), sent)),
+        Err(send_err) => match &send_err {
+            RpcError::ErrorResp(payload) => Ok(SendTxResult::JsonRpcError(payload.clone())),
+            _ => Err(crate::Error::from(send_err)),
+        },
+    }
+}
+
+/// Sends a transaction with the nonce of the pending one and higher fees,
+/// either the same transaction to speed it up or an empty transfer to the
+/// sender itself to cancel it.
+pub fn replace_tx_thread(
+    sender_account: Address,
+    pending: &TransactionRequest,
+    cancel: bool,
+    network: &Network,
+    tr: &mpsc::Sender<Event>,
+    shutdown_signal: &Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    let tr = tr.clone();
+    let shutdown_signal = shutdown_signal.clone();
+    let network = network.clone();
+    let pending = pending.clone();
+    tokio::spawn(async move {
+        let _ = match run(sender_account, pending, cancel, network, shutdown_signal).await {
+            Ok(SendTxResult::Submitted(hash, sent)) => {
+                let _ = tr.send(Event::TxSubmitted(sent));
+                tr.send(Event::TxUpdate(TxStatus::Pending(hash)))
             }
+            Ok(SendTxResult::JsonRpcError(error_payload)) => {
+                tr.send(Event::TxReplaceError(error_payload.message.to_string()))
+            }
+            Err(err) => tr.send(Event::TxReplaceError(err.to_string())),
+        };
 
-            // Submit transaction
-            match provider.send_raw_transaction(&out).await {
-                Ok(result) => Ok(SendTxResult::Submitted(*result.tx_hash())),
-                Err(send_err) => match &send_err {
-                    RpcError::ErrorResp(payload) => Ok(SendTxResult::JsonRpcError(payload.clone())),
-                    _ => Err(crate::Error::from(send_err)),
+        async fn run(
+            sender_account: Address,
+            pending: TransactionRequest,
+            cancel: bool,
+            network: Network,
+            shutdown_signal: Arc<AtomicBool>,
+        ) -> crate::Result<SendTxResult> {
+            let provider = network.get_provider()?;
+            let wallet = AccountManager::load_wallet(&sender_account)?;
+
+            // The replacement is priced the same way as the pending transaction
+            let tx_type = match pending.transaction_type.map(TxType::try_from) {
+                Some(Ok(tx_type)) => tx_type,
+                _ if pending.gas_price.is_some() => TxType::Legacy,
+                _ => TxType::Eip1559,
+            };
+            let current = gas::fetch_fee_presets(&network, tx_type)
+                .await
+                .ok()
+                .map(|presets| network.cap_fees(presets.estimate(FeePreset::Fast)));
+            let fees = gas::bump_fees(
+                match pending.gas_price {
+                    Some(gas_price) => Eip1559Estimation {
+                        max_fee_per_gas: gas_price,
+                        max_priority_fee_per_gas: gas_price,
+                    },
+                    None => Eip1559Estimation {
+                        max_fee_per_gas: pending.max_fee_per_gas.unwrap_or_default(),
+                        max_priority_fee_per_gas: pending
+                            .max_priority_fee_per_gas
+                            .unwrap_or_default(),
+                    },
                 },
+                current,
+            );
+
+            let mut tx = if cancel {
+                TransactionRequest {
+                    from: Some(sender_account),
+                    to: Some(TxKind::Call(sender_account)),
+                    value: Some(U256::ZERO),
+                    nonce: pending.nonce,
+                    chain_id: pending.chain_id,
+                    gas: Some(CANCEL_GAS_LIMIT),
+                    ..Default::default()
+                }
+            } else {
+                pending
+            };
+            // The empty transfer carries no authorization
+            let tx_type = match tx_type {
+                TxType::Eip7702 if cancel => TxType::Eip1559,
+                tx_type => tx_type,
+            };
+            set_fees(&mut tx, tx_type, fees);
+
+            sign_and_send(&provider, &wallet, tx, &shutdown_signal).await
+        }
+    })
+}
+
+pub(crate) async fn estimate_gas(
+    provider: &impl Provider,
+    tx: TransactionRequest,
+) -> Result<u64, RpcError<TransportErrorKind>> {
+    let estimate_result = provider.estimate_gas(tx.clone()).await;
+
+    // Handle an edge case where node errors with "insufficient funds" error during revert
+    if estimate_result.is_err() && format!("{:?}", &estimate_result).contains("insufficient funds")
+    {
+        // re-estimate wihout gas price fields
+        let mut tx_temp = tx;
+        tx_temp.gas_price = None;
+        tx_temp.max_fee_per_gas = None;
+        tx_temp.max_priority_fee_per_gas = None;
+
+        provider.estimate_gas(tx_temp).await
+    } else {
+        estimate_result
+    }
+}
+
+/// Estimates the gas and fetches the fee presets, so that they can be changed
+/// before signing.
+pub fn estimate_gas_thread(
+    sender_account: Address,
+    delegation: Option<Address>,
+    tx_req: &TransactionRequest,
+    network: &Network,
+    tr: &mpsc::Sender<Event>,
+) -> JoinHandle<()> {
+    let tr = tr.clone();
+    let network = network.clone();
+    let mut tx = tx_req.clone();
+    tx.from = Some(sender_account);
+    tokio::spawn(async move {
+        let _ = match run(network, tx, delegation).await {
+            Ok((estimate, presets, tx_type)) => {
+                // The authorization is signed while sending, so it is not
+                // part of the estimate yet
+                let estimate =
+                    estimate + delegation.map_or(0, |_| delegation::PER_AUTHORIZATION_GAS);
+                tr.send(Event::TxGasEstimate(estimate, presets, tx_type))
             }
+            Err(err) => tr.send(Event::TxGasEstimateError(err.to_string())),
+        };
+
+        async fn run(
+            network: Network,
+            tx: TransactionRequest,
+            delegation: Option<Address>,
+        ) -> crate::Result<(u64, FeePresets, TxType)> {
+            let tx_type = resolve_tx_type(&network, delegation).await?;
+            let presets = gas::fetch_fee_presets(&network, tx_type).await?;
+            let estimate = estimate_gas(&network.get_provider()?, tx).await?;
+            Ok((estimate, presets, tx_type))
         }
-    }))
+    })
+}
+
+/// Simulates the transaction so that its effects are known before signing.
+pub fn simulate_tx_thread(
+    sender_account: Address,
+    tx_req: &TransactionRequest,
+    network: &Network,
+    tr: &mpsc::Sender<Event>,
+) -> JoinHandle<()> {
+    let tr = tr.clone();
+    let network = network.clone();
+    let mut tx = tx_req.clone();
+    tx.from = Some(sender_account);
+    tokio::spawn(async move {
+        let _ = match simulate::simulate(&network, tx).await {
+            Ok(simulation) => tr.send(Event::TxSimulation(simulation)),
+            Err(err) => tr.send(Event::TxSimulationError(err.to_string())),
+        };
+    })
+}
+
+/// Checks the recipient against the `known` addresses and its code.
+pub fn check_recipient_thread(
+    sender_account: Address,
+    tx_req: &TransactionRequest,
+    network: &Network,
+    known: Vec<Address>,
+    tr: &mpsc::Sender<Event>,
+) -> JoinHandle<()> {
+    let tr = tr.clone();
+    let network = network.clone();
+    let mut tx = tx_req.clone();
+    tx.from = Some(sender_account);
+    tokio::spawn(async move {
+        let _ = match recipient::check_recipient(&network, &tx, &known).await {
+            Ok(risks) => tr.send(Event::TxRecipientRisks(risks)),
+            Err(err) => tr.send(Event::TxRecipientRisksError(err.to_string())),
+        };
+    })
 }
 
+pub fn propose_safe_tx_thread(
+    safe: Address,
+    tx_req: &TransactionRequest,
+    network: &Network,
+    tr: &mpsc::Sender<Event>,
+) -> JoinHandle<()> {
+    let tr = tr.clone();
+    let network = network.clone();
+    let to = tx_req
+        .to
+        .and_then(|to| to.to().copied())
+        .unwrap_or_default();
+    let value = tx_req.value.unwrap_or_default();
+    let data = tx_req.input.input().cloned().unwrap_or_default();
+    tokio::spawn(async move {
+        let _ = match safe::propose(&network, safe, to, value, data).await {
+            Ok((tx, info)) => tr.send(Event::TxUpdate(TxStatus::SafeProposed {
+                id: tx.id,
+                confirmations: tx.signatures.len(),
+                threshold: info.threshold,
+            })),
+            Err(err) => tr.send(Event::TxError(
+                crate::Error::from(err).fmt_err("SafeProposeError"),
+            )),
+        };
+    })
+}
+
+/// Waits for one of the transactions sharing a nonce to get mined, the
+/// second item of each hash tells if it is a cancellation. The receipt is
+/// then summarized for the account.
 pub fn watch_tx_thread(
     network: &Network,
+    account: Address,
     tr: &mpsc::Sender<Event>,
     shutdown_signal: &Arc<AtomicBool>,
-    tx_hash: FixedBytes<32>,
+    tx_hashes: Vec<(FixedBytes<32>, bool)>,
 ) -> crate::Result<JoinHandle<()>> {
     let tr = tr.clone();
     let shutdown_signal = shutdown_signal.clone();
 
+    let network = network.clone();
     let provider = network.get_provider()?;
     Ok(tokio::spawn(async move {
-        loop {
-            match provider.get_transaction_receipt(tx_hash).await {
-                Ok(result) => {
-                    if let Some(result) = result {
-                        let _ = tr.send(Event::TxUpdate(if result.status() {
-                            TxStatus::Confirmed(tx_hash)
-                        } else {
-                            TxStatus::Failed(tx_hash)
-                        }));
-                        break;
+        'watch: loop {
+            for &(tx_hash, cancel) in &tx_hashes {
+                match provider.get_transaction_receipt(tx_hash).await {
+                    Ok(result) => {
+                        if let Some(result) = result {
+                            let _ = tr.send(Event::TxUpdate(if cancel {
+                                TxStatus::Cancelled(tx_hash)
+                            } else if result.status() {
+                                TxStatus::Confirmed(tx_hash)
+                            } else {
+                                TxStatus::Failed(tx_hash)
+                            }));
+                            let _ = match receipt::summarize_receipt(&network, &result, account)
+                                .await
+                            {
+                                Ok(receipt) => tr.send(Event::TxReceipt(Box::new(receipt))),
+                                Err(err) => tr.send(Event::TxReceiptError(err.to_string())),
+                            };
+                            break 'watch;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tr.send(Event::TxWatchError(
+                            crate::Error::from(e).fmt_err("TxStatusError"),
+                        ));
                     }
-                }
-                Err(e) => {
-                    let _ = tr.send(Event::TxError(
-                        crate::Error::from(e).fmt_err("TxStatusError"),
-                    ));
                 }
             }
 
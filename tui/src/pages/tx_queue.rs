@@ -0,0 +1,624 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    sync::{atomic::AtomicBool, mpsc, Arc},
+};
+
+use alloy::{
+    primitives::{utils::format_ether, Address, Bytes, FixedBytes, U256},
+    rpc::types::TransactionRequest,
+};
+use gm_ratatui_extra::{
+    cursor::Cursor, extensions::CustomRender, select::Select, thematize::Thematize,
+};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    widgets::Widget,
+};
+use tokio::{sync::oneshot, task::JoinHandle};
+
+use crate::{
+    app::SharedState,
+    events::Event,
+    traits::{Actions, Component},
+};
+use gm_utils::{
+    account::AccountManager,
+    disk_storage::DiskStorageInterface,
+    gas::{self, GasSettings},
+    history::{HistoryEntry, HistoryStatus, HistoryStore},
+    network::Network,
+    receipt::ReceiptSummary,
+    recipient,
+};
+
+use super::tx_popup::{self, TxStatus};
+
+/// Outcome of a queued transaction, given back to where it was requested.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueuedTxResult {
+    Submitted(FixedBytes<32>),
+    JsonRpcError {
+        message: String,
+        code: i64,
+        data: Option<Bytes>,
+    },
+    Rejected,
+}
+
+#[derive(Debug)]
+pub struct QueuedTx {
+    pub id: u32,
+    pub origin: String,
+    pub network: Network,
+    pub sender: Address,
+    pub tx_req: TransactionRequest,
+    /// `None` until the estimate arrives, in which case the gas is estimated
+    /// while sending.
+    pub gas: Option<GasSettings>,
+    pub gas_error: Option<String>,
+    pub status: TxStatus,
+    /// Receipt once the transaction is mined.
+    pub receipt: Option<ReceiptSummary>,
+    /// Transaction as it was signed, recorded in the history once sent.
+    sent_tx: Option<TransactionRequest>,
+    reply_to: Option<oneshot::Sender<QueuedTxResult>>,
+}
+
+impl QueuedTx {
+    fn reply(&mut self, result: QueuedTxResult) {
+        // The request may be gone, such as a shell script that exited
+        if let Some(reply_to) = self.reply_to.take() {
+            let _ = reply_to.send(result);
+        }
+    }
+
+    /// Most the transaction can cost, its value and the gas at the max fee.
+    fn max_fee(&self) -> Option<U256> {
+        self.gas
+            .map(|gas| U256::from(gas.gas_limit) * U256::from(gas.fees.max_fee_per_gas))
+    }
+}
+
+impl Display for QueuedTx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "#{} {}: {} ether to {} on {} [{}]",
+            self.id,
+            self.origin,
+            format_ether(self.tx_req.value.unwrap_or_default()),
+            self.tx_req
+                .to
+                .and_then(|to| to.to().map(|to| to.to_string()))
+                .unwrap_or_else(|| "new contract".to_string()),
+            self.network.name,
+            fmt_status(&self.status)
+        )
+    }
+}
+
+fn fmt_status(status: &TxStatus) -> String {
+    match status {
+        TxStatus::NotSent => "queued".to_string(),
+        TxStatus::Signing => "signing".to_string(),
+        TxStatus::JsonRpcError { message, .. } => format!("error: {message}"),
+        TxStatus::Pending(hash) => format!("pending {hash}"),
+        TxStatus::Confirmed(hash) => format!("confirmed {hash}"),
+        TxStatus::Failed(hash) => format!("failed {hash}"),
+        TxStatus::Cancelled(hash) => format!("cancelled {hash}"),
+        TxStatus::SafeProposed { id, .. } => format!("Safe transaction #{id}"),
+    }
+}
+
+/// Transactions collected to be reviewed together and then signed and sent
+/// one after the other.
+#[derive(Debug, Default)]
+pub struct TxQueue {
+    txs: Vec<QueuedTx>,
+}
+
+impl TxQueue {
+    /// Adds the request to the queue, the receiver gets its outcome once it
+    /// is sent or removed.
+    pub fn push(
+        &mut self,
+        origin: &str,
+        network: Network,
+        sender: Address,
+        tx_req: TransactionRequest,
+    ) -> (u32, oneshot::Receiver<QueuedTxResult>) {
+        let id = self.txs.iter().map(|tx| tx.id).max().unwrap_or(0) + 1;
+        let (reply_to, reply) = oneshot::channel();
+        self.txs.push(QueuedTx {
+            id,
+            origin: origin.to_string(),
+            network,
+            sender,
+            tx_req,
+            gas: None,
+            gas_error: None,
+            status: TxStatus::NotSent,
+            receipt: None,
+            sent_tx: None,
+            reply_to: Some(reply_to),
+        });
+        (id, reply)
+    }
+
+    pub fn txs(&self) -> &[QueuedTx] {
+        &self.txs
+    }
+
+    fn get_mut(&mut self, id: u32) -> Option<&mut QueuedTx> {
+        self.txs.iter_mut().find(|tx| tx.id == id)
+    }
+
+    /// Removes and rejects a transaction that was not sent yet, the ones
+    /// being sent or pending are kept. Returns whether it was removed.
+    pub fn remove(&mut self, id: u32) -> bool {
+        let Some(index) = self
+            .txs
+            .iter()
+            .position(|tx| tx.id == id && tx.status == TxStatus::NotSent)
+        else {
+            return false;
+        };
+        let mut tx = self.txs.remove(index);
+        tx.reply(QueuedTxResult::Rejected);
+        true
+    }
+
+    /// Removes the transactions that got mined or could not be sent.
+    pub fn clear_finished(&mut self) {
+        self.txs.retain(|tx| {
+            matches!(
+                tx.status,
+                TxStatus::NotSent | TxStatus::Signing | TxStatus::Pending(_)
+            )
+        });
+    }
+
+    /// Value and max fee of the transactions still to be sent per network,
+    /// along with how many of them have no gas estimate yet.
+    fn totals(&self) -> BTreeMap<String, (U256, U256, usize)> {
+        let mut totals = BTreeMap::<String, (U256, U256, usize)>::new();
+        for tx in self.txs.iter().filter(|tx| tx.status == TxStatus::NotSent) {
+            let (value, fee, unestimated) = totals.entry(tx.network.name.clone()).or_default();
+            *value += tx.tx_req.value.unwrap_or_default();
+            match tx.max_fee() {
+                Some(max_fee) => *fee += max_fee,
+                None => *unestimated += 1,
+            }
+        }
+        totals
+    }
+}
+
+/// Estimates the gas of the queued transactions one after the other.
+fn estimate_queue_thread(
+    txs: Vec<(u32, Address, TransactionRequest, Network)>,
+    tr: &mpsc::Sender<Event>,
+) -> JoinHandle<()> {
+    let tr = tr.clone();
+    tokio::spawn(async move {
+        for (id, sender, tx_req, network) in txs {
+            let _ = match run(sender, tx_req, &network).await {
+                Ok(gas) => tr.send(Event::TxQueueGas(id, gas)),
+                Err(err) => tr.send(Event::TxQueueGasError(id, err.to_string())),
+            };
+        }
+
+        async fn run(
+            sender: Address,
+            tx_req: TransactionRequest,
+            network: &Network,
+        ) -> crate::Result<GasSettings> {
+            let tx_type = tx_popup::resolve_tx_type(network, None).await?;
+            let presets = gas::fetch_fee_presets(network, tx_type).await?;
+            let requested = tx_req.gas;
+            let estimate =
+                tx_popup::estimate_gas(&network.get_provider()?, tx_req.from(sender)).await?;
+            Ok(GasSettings::new(
+                network, tx_type, estimate, requested, &presets,
+            ))
+        }
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct TxQueuePage {
+    cursor: Cursor,
+    /// Queued transaction being signed and sent.
+    sending: Option<u32>,
+    /// Set while all the queued transactions are being sent.
+    send_all: bool,
+    estimate_thread: Option<JoinHandle<()>>,
+    send_thread: Option<JoinHandle<()>>,
+    watch_threads: Vec<JoinHandle<()>>,
+    /// Last failure to check the status of a sent transaction.
+    watch_error: Option<String>,
+    /// Set once the estimates and watches of the queue were started.
+    started: bool,
+}
+
+impl TxQueuePage {
+    fn start(
+        &mut self,
+        tr: &mpsc::Sender<Event>,
+        sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<()> {
+        self.started = true;
+        let queue = ss.tx_queue()?;
+        let unestimated = queue
+            .txs
+            .iter()
+            .filter(|tx| tx.status == TxStatus::NotSent && tx.gas.is_none())
+            .map(|tx| (tx.id, tx.sender, tx.tx_req.clone(), tx.network.clone()))
+            .collect::<Vec<_>>();
+        if !unestimated.is_empty() {
+            self.estimate_thread = Some(estimate_queue_thread(unestimated, tr));
+        }
+        // Transactions sent before the page was left are still followed
+        for tx in &queue.txs {
+            if let TxStatus::Pending(hash) = tx.status {
+                self.watch_threads.push(tx_popup::watch_tx_thread(
+                    &tx.network,
+                    tx.sender,
+                    tr,
+                    sd,
+                    vec![(hash, false)],
+                )?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the next queued transaction, nonces are assigned in order since
+    /// each one is sent after the previous one got submitted.
+    fn send_next(
+        &mut self,
+        tr: &mpsc::Sender<Event>,
+        sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<()> {
+        let mut queue = ss.tx_queue()?;
+        let Some(tx) = queue
+            .txs
+            .iter_mut()
+            .find(|tx| tx.status == TxStatus::NotSent)
+        else {
+            self.send_all = false;
+            return Ok(());
+        };
+        if let Err(err) = AccountManager::ensure_can_sign(&tx.sender) {
+            self.send_all = false;
+            return Err(err.into());
+        }
+        self.send_thread = Some(tx_popup::send_tx_thread(
+            tx.sender,
+            None,
+            tx.gas,
+            &tx.tx_req,
+            &tx.network,
+            tr,
+            sd,
+        ));
+        tx.status = TxStatus::Signing;
+        self.sending = Some(tx.id);
+        Ok(())
+    }
+
+    fn handle_tx_update(
+        &mut self,
+        status: &TxStatus,
+        tr: &mpsc::Sender<Event>,
+        sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<()> {
+        let mut queue = ss.tx_queue()?;
+        match status {
+            TxStatus::Pending(hash) => {
+                let Some(tx) = self.sending.take().and_then(|id| queue.get_mut(id)) else {
+                    return Ok(());
+                };
+                tx.status = status.clone();
+                tx.reply(QueuedTxResult::Submitted(*hash));
+                if let Some(sent_tx) = &tx.sent_tx {
+                    HistoryStore::load()?.add(
+                        HistoryEntry::transaction(
+                            &tx.origin,
+                            &tx.network,
+                            tx.sender,
+                            sent_tx.to.and_then(|to| to.to().copied()),
+                            sent_tx.value.unwrap_or_default(),
+                            sent_tx.nonce,
+                            *hash,
+                        )
+                        .with_recipient(
+                            recipient::recipient_of(sent_tx).map(|(recipient, _)| recipient),
+                        ),
+                    )?;
+                }
+                self.watch_threads.push(tx_popup::watch_tx_thread(
+                    &tx.network,
+                    tx.sender,
+                    tr,
+                    sd,
+                    vec![(*hash, false)],
+                )?);
+                drop(queue);
+                if self.send_all {
+                    self.send_next(tr, sd, ss)?;
+                }
+            }
+            // Later transactions may depend on this one, so the rest waits
+            TxStatus::JsonRpcError {
+                message,
+                code,
+                data,
+            } => {
+                self.send_all = false;
+                if let Some(tx) = self.sending.take().and_then(|id| queue.get_mut(id)) {
+                    tx.status = status.clone();
+                    tx.reply(QueuedTxResult::JsonRpcError {
+                        message: message.clone(),
+                        code: *code,
+                        data: data.clone(),
+                    });
+                }
+            }
+            TxStatus::Confirmed(hash) | TxStatus::Failed(hash) => {
+                let pending = TxStatus::Pending(*hash);
+                if let Some(tx) = queue.txs.iter_mut().find(|tx| tx.status == pending) {
+                    tx.status = status.clone();
+                    HistoryStore::load()?.set_status(
+                        *hash,
+                        if matches!(status, TxStatus::Confirmed(_)) {
+                            HistoryStatus::Confirmed
+                        } else {
+                            HistoryStatus::Failed
+                        },
+                    )?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Component for TxQueuePage {
+    async fn exit_threads(&mut self) {
+        for thread in [self.estimate_thread.take(), self.send_thread.take()]
+            .into_iter()
+            .flatten()
+            .chain(self.watch_threads.drain(..))
+        {
+            thread.abort();
+            let _ = thread.await;
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &Event,
+        _area: Rect,
+        tr: &mpsc::Sender<Event>,
+        sd: &Arc<AtomicBool>,
+        ss: &SharedState,
+    ) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+        if !self.started {
+            self.start(tr, sd, ss)?;
+        }
+
+        let len = ss.tx_queue()?.txs.len();
+        self.cursor.handle(event.key_event(), len);
+
+        match event {
+            Event::Input(key_event) if key_event.kind == KeyEventKind::Press => {
+                match key_event.code {
+                    KeyCode::Char('s') if self.sending.is_none() => {
+                        self.send_all = true;
+                        self.send_next(tr, sd, ss)?;
+                    }
+                    KeyCode::Char('d') if self.sending.is_none() => {
+                        let mut queue = ss.tx_queue()?;
+                        if let Some(id) = queue.txs.get(self.cursor.current).map(|tx| tx.id) {
+                            // Sent transactions stay until they are finished
+                            queue.remove(id);
+                        }
+                    }
+                    KeyCode::Char('c') => ss.tx_queue()?.clear_finished(),
+                    _ => {}
+                }
+            }
+            Event::TxQueueGas(id, gas) => {
+                if let Some(tx) = ss.tx_queue()?.get_mut(*id) {
+                    tx.gas = Some(*gas);
+                }
+            }
+            Event::TxQueueGasError(id, error) => {
+                if let Some(tx) = ss.tx_queue()?.get_mut(*id) {
+                    tx.gas_error = Some(error.clone());
+                }
+            }
+            Event::TxSubmitted(sent_tx) => {
+                if let Some(id) = self.sending {
+                    if let Some(tx) = ss.tx_queue()?.get_mut(id) {
+                        tx.sent_tx = Some(*sent_tx.clone());
+                    }
+                }
+            }
+            Event::TxUpdate(status) => {
+                self.watch_error = None;
+                self.handle_tx_update(status, tr, sd, ss)?;
+            }
+            Event::TxReceipt(receipt) => {
+                let mut queue = ss.tx_queue()?;
+                let mined = [
+                    TxStatus::Confirmed(receipt.hash),
+                    TxStatus::Failed(receipt.hash),
+                ];
+                if let Some(tx) = queue.txs.iter_mut().find(|tx| mined.contains(&tx.status)) {
+                    tx.receipt = Some(*receipt.clone());
+                }
+            }
+            // The watch keeps trying, the transaction stays sent
+            Event::TxWatchError(error) => self.watch_error = Some(error.clone()),
+            // Shown by the app, the transaction can be sent again
+            Event::TxError(_) => {
+                self.send_all = false;
+                if let Some(id) = self.sending.take() {
+                    if let Some(tx) = ss.tx_queue()?.get_mut(id) {
+                        tx.status = TxStatus::NotSent;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Leaving while sending would stop the queue halfway
+        if self.sending.is_some() {
+            result.ignore_esc = true;
+        }
+        Ok(result)
+    }
+
+    fn render_component(&self, area: Rect, buf: &mut Buffer, ss: &SharedState) -> Rect
+    where
+        Self: Sized,
+    {
+        let Ok(queue) = ss.tx_queue() else {
+            return area;
+        };
+        if queue.txs.is_empty() {
+            "No queued transactions, press q in a transaction popup to add one to the queue."
+                .render(area, buf);
+            return area;
+        }
+
+        let [list_area, totals_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(5)]).areas(area);
+
+        let list = queue.txs.iter().map(|tx| tx.to_string()).collect();
+        Select {
+            list: &list,
+            cursor: &self.cursor,
+            focus: true,
+            focus_style: ss.theme.select_focused(),
+        }
+        .render(list_area, buf);
+
+        let totals = queue
+            .totals()
+            .into_iter()
+            .map(|(network, (value, fee, unestimated))| {
+                let mut total = format!(
+                    "{network}: {} ether sent, up to {} ether in fees",
+                    format_ether(value),
+                    format_ether(fee)
+                );
+                if unestimated > 0 {
+                    total.push_str(&format!(" ({unestimated} not estimated yet)"));
+                }
+                total
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        let selected_gas = queue
+            .txs
+            .get(self.cursor.current)
+            .map(|tx| match (&tx.receipt, &tx.gas, &tx.gas_error) {
+                (Some(receipt), _, _) => format!(
+                    "Selected: {}, {} token events of the account",
+                    tx_popup::fmt_receipt_fee(receipt, &tx.network, ss),
+                    receipt.token_events.len()
+                ),
+                (None, Some(gas), _) => format!(
+                    "Selected: gas limit {}, max fee {} gwei",
+                    gas.gas_limit,
+                    gas::format_gwei(gas.fees.max_fee_per_gas)
+                ),
+                (None, None, Some(error)) => {
+                    format!("Selected: gas estimation failed: {error}")
+                }
+                (None, None, None) => "Selected: estimating gas...".to_string(),
+            })
+            .unwrap_or_default();
+        [
+            format!("Total to send: {totals}"),
+            selected_gas,
+            "s: sign and send all, d: remove selected if not sent, c: clear finished".to_string(),
+            self.watch_error
+                .as_ref()
+                .map(|error| format!("Status check failed, retrying: {error}"))
+                .unwrap_or_default(),
+        ]
+        .render(totals_area, buf, false);
+        area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::TxKind;
+    use tokio::sync::oneshot::error::TryRecvError;
+
+    use super::*;
+
+    #[test]
+    fn test_queue_totals_and_replies() {
+        let network = Network {
+            name: "mainnet".to_string(),
+            ..Default::default()
+        };
+        let tx_req = TransactionRequest {
+            to: Some(TxKind::Call(Address::repeat_byte(2))),
+            value: Some(U256::from(5)),
+            ..Default::default()
+        };
+        let mut queue = TxQueue::default();
+        let (first, mut first_reply) =
+            queue.push("Shell", network.clone(), Address::ZERO, tx_req.clone());
+        let (second, mut second_reply) = queue.push("Shell", network, Address::ZERO, tx_req);
+        assert_eq!((first, second), (1, 2));
+
+        queue.get_mut(first).unwrap().gas = Some(GasSettings {
+            gas_limit: 21_000,
+            fees: alloy::providers::utils::Eip1559Estimation {
+                max_fee_per_gas: 2,
+                max_priority_fee_per_gas: 1,
+            },
+            tx_type: alloy::consensus::TxType::Eip1559,
+            preset: None,
+        });
+        assert_eq!(
+            queue.totals()["mainnet"],
+            (U256::from(10), U256::from(42_000), 1)
+        );
+
+        let tx = queue.get_mut(first).unwrap();
+        tx.status = TxStatus::Pending(FixedBytes::repeat_byte(1));
+        tx.reply(QueuedTxResult::Submitted(FixedBytes::repeat_byte(1)));
+        assert_eq!(
+            first_reply.try_recv(),
+            Ok(QueuedTxResult::Submitted(FixedBytes::repeat_byte(1)))
+        );
+        assert_eq!(queue.totals()["mainnet"], (U256::from(5), U256::ZERO, 1));
+
+        assert_eq!(second_reply.try_recv(), Err(TryRecvError::Empty));
+        // The pending transaction will get mined, it cannot be removed
+        assert!(!queue.remove(first));
+        assert!(queue.remove(second));
+        assert_eq!(second_reply.try_recv(), Ok(QueuedTxResult::Rejected));
+
+        queue.get_mut(first).unwrap().status = TxStatus::Confirmed(FixedBytes::repeat_byte(1));
+        queue.clear_finished();
+        assert!(queue.txs().is_empty());
+    }
+}
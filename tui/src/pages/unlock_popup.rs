@@ -0,0 +1,173 @@
+use gm_ratatui_extra::{
+    act::Act,
+    extensions::{CustomRender, RectExt, WidgetHeight},
+    input_box::InputBox,
+    popup::Popup,
+    thematize::Thematize,
+};
+use gm_utils::account::AccountManager;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEventKind},
+    layout::Rect,
+    widgets::{Block, Widget},
+};
+
+use crate::{app::SharedState, traits::Actions, Event};
+
+/// Asks for the key store password, on the first run it also asks to confirm
+/// the password since it becomes the password of the key store.
+#[derive(Default)]
+pub struct UnlockPopup {
+    open: bool,
+    setup: bool,
+    password: String,
+    password_cursor: usize,
+    confirm: String,
+    confirm_cursor: usize,
+    confirm_focused: bool,
+    error: Option<String>,
+}
+
+impl UnlockPopup {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self) -> crate::Result<()> {
+        self.reset();
+        self.setup = AccountManager::needs_password_setup()?;
+        self.open = true;
+        Ok(())
+    }
+
+    pub fn close(&mut self) {
+        self.reset();
+        self.open = false;
+    }
+
+    fn reset(&mut self) {
+        self.password.clear();
+        self.password_cursor = 0;
+        self.confirm.clear();
+        self.confirm_cursor = 0;
+        self.confirm_focused = false;
+        self.error = None;
+    }
+
+    fn submit(&mut self) {
+        if self.password.is_empty() {
+            self.error = Some("Password cannot be empty".to_string());
+            return;
+        }
+
+        if self.setup && self.password != self.confirm {
+            self.error = Some("Passwords do not match".to_string());
+            self.confirm.clear();
+            self.confirm_cursor = 0;
+            return;
+        }
+
+        match AccountManager::unlock(&self.password) {
+            Ok(()) => self.close(),
+            Err(e) => {
+                self.error = Some(e.to_string());
+                self.password.clear();
+                self.password_cursor = 0;
+                self.confirm.clear();
+                self.confirm_cursor = 0;
+                self.confirm_focused = false;
+            }
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> crate::Result<Actions> {
+        let mut result = Actions::default();
+
+        if let Event::Input(key_event) = event {
+            if key_event.kind == KeyEventKind::Press {
+                match key_event.code {
+                    KeyCode::Esc => self.close(),
+                    KeyCode::Tab | KeyCode::Up | KeyCode::Down if self.setup => {
+                        self.confirm_focused = !self.confirm_focused;
+                    }
+                    KeyCode::Enter => {
+                        if self.setup && !self.confirm_focused {
+                            self.confirm_focused = true;
+                        } else {
+                            self.submit();
+                        }
+                    }
+                    _ => {
+                        let (text, cursor) = if self.confirm_focused {
+                            (&mut self.confirm, &mut self.confirm_cursor)
+                        } else {
+                            (&mut self.password, &mut self.password_cursor)
+                        };
+                        InputBox::handle_event(Some(key_event), text, cursor);
+                    }
+                }
+            }
+        }
+
+        result.ignore_esc();
+        Ok(result)
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer, shared_state: &SharedState)
+    where
+        Self: Sized,
+    {
+        if self.is_open() {
+            let theme = shared_state.theme.popup();
+
+            Popup.render(area, buf, &theme);
+
+            let inner_area = Popup::inner_area(area);
+            let block = Block::bordered().title(if self.setup { "Set Password" } else { "Unlock" });
+            let mut area = block.inner(inner_area);
+            block.render(inner_area, buf);
+
+            let intro = if self.setup {
+                "Choose a password to encrypt your accounts. It will be asked before signing."
+            } else {
+                "Your accounts are locked, please enter your password to continue."
+            };
+            let used = [intro.to_string()].render(area, buf, true);
+            area = area.margin_top(used.height);
+
+            // Never render the password itself
+            let masked_password = "*".repeat(self.password.chars().count());
+            let password_box = InputBox {
+                focus: !self.confirm_focused,
+                label: "Password",
+                text: &masked_password,
+                empty_text: None,
+                currency: None,
+            };
+            let height = password_box.height_used(area);
+            password_box.render(area, buf, &self.password_cursor, &theme);
+            area = area.margin_top(height);
+
+            if self.setup {
+                let masked_confirm = "*".repeat(self.confirm.chars().count());
+                let confirm_box = InputBox {
+                    focus: self.confirm_focused,
+                    label: "Confirm Password",
+                    text: &masked_confirm,
+                    empty_text: None,
+                    currency: None,
+                };
+                let height = confirm_box.height_used(area);
+                confirm_box.render(area, buf, &self.confirm_cursor, &theme);
+                area = area.margin_top(height);
+            }
+
+            [
+                self.error.clone().unwrap_or_default(),
+                "Press Enter to continue or Esc to cancel".to_string(),
+            ]
+            .render(area.margin_top(1), buf, true);
+        }
+    }
+}
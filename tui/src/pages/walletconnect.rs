@@ -39,20 +39,25 @@ use crate::{
         sign_popup::{SignPopup, SignPopupEvent},
         sign_typed_data_popup::SignTypedDataPopup,
         tx_popup::TxPopup,
+        tx_queue::QueuedTxResult,
     },
     traits::{Actions, Component},
     Event,
 };
 use gm_utils::network::Network;
 
-fn format_proposal(params: &SessionProposeParams) -> String {
+fn format_proposal(params: &SessionProposeParams, ss: &SharedState) -> String {
     let metadata = &params.proposer.metadata;
     let mut output = format!(
-        "dApp Name: {name}\n{desc}\n{url}\n\nRequested Permissions\n",
+        "dApp Name: {name}\n{desc}\n{url}\n",
         name = metadata.name,
         desc = metadata.description,
         url = metadata.url
     );
+    if let Some(account) = ss.current_account {
+        output.push_str(&format!("\nConnects as {}\n", ss.labeled_address(&account)));
+    }
+    output.push_str("\nRequested Permissions\n");
 
     for (ns_key, ns) in params
         .required_namespaces
@@ -64,6 +69,11 @@ fn format_proposal(params: &SessionProposeParams) -> String {
         if let Some(accounts) = &ns.accounts {
             output.push_str("   - Accounts:\n");
             for a in accounts {
+                // CAIP-10 account ids end with the address
+                let a = match a.rsplit(':').next().and_then(|a| a.parse().ok()) {
+                    Some(address) => ss.labeled_address(&address).to_string(),
+                    None => a.to_string(),
+                };
                 output.push_str(&format!("     • {a}\n"));
             }
         }
@@ -164,6 +174,8 @@ pub struct WalletConnectPage {
     tx_popup: TxPopup,
     sign_popup: SignPopup,
     sign_typed_data_popup: SignTypedDataPopup,
+    /// Name of the dApp of the session, recorded in the history.
+    dapp_name: String,
     watch_thread: Option<JoinHandle<()>>,
     send_thread: Option<JoinHandle<()>>,
     tr_2: Option<Sender<WcEvent>>,
@@ -187,6 +199,7 @@ impl WalletConnectPage {
             tx_popup: TxPopup::default(),
             sign_popup: SignPopup::default(),
             sign_typed_data_popup: SignTypedDataPopup::default(),
+            dapp_name: String::new(),
             watch_thread: None,
             send_thread: None,
             tr_2: None,
@@ -212,9 +225,11 @@ impl WalletConnectPage {
             .ok_or_else(|| crate::Error::ChainIdStripEip155Failed(req.chain_id.clone()))?
             .parse::<u32>()
             .map_err(|_| crate::Error::ChainIdParseFailed(req.chain_id.clone()))?;
+        let origin = format!("WalletConnect: {}", self.dapp_name);
         match &req.request.params {
             SessionRequestData::EthSendTransaction(tx_req) => {
                 let network = Network::from_chain_id(chain_id)?;
+                self.tx_popup.set_origin(origin);
                 self.tx_popup.set_tx_req(
                     network,
                     TransactionRequest {
@@ -231,6 +246,7 @@ impl WalletConnectPage {
                 self.tx_popup.open();
             }
             SessionRequestData::PersonalSign { message, .. } => {
+                self.sign_popup.set_origin(origin);
                 self.sign_popup.set_text(message);
                 self.sign_popup.open();
             }
@@ -242,6 +258,7 @@ impl WalletConnectPage {
                 if let Some(str) = &typed_data.as_str() {
                     typed_data = Value::from_str(str)?;
                 }
+                self.sign_typed_data_popup.set_origin(origin);
                 self.sign_typed_data_popup.set_typed_data(typed_data)?;
                 self.sign_typed_data_popup.open();
             }
@@ -320,9 +337,10 @@ impl Component for WalletConnectPage {
                         .data
                         .as_session_propose()
                         .ok_or(crate::Error::ProposalNotFound)?;
+                    self.dapp_name = proposal.proposer.metadata.name.clone();
 
                     let text = self.confirm_popup.text_mut();
-                    *text = format_proposal(proposal);
+                    *text = format_proposal(proposal, ss);
                     self.confirm_popup.open();
                 }
             }
@@ -509,6 +527,43 @@ impl Component for WalletConnectPage {
                 || Ok(()),
             )?;
             handle_result.merge(r);
+
+            if let Some(queued) = self.tx_popup.take_queued() {
+                let (req, tr_2) = get_req_tr_2()?;
+                let (req, tr_2) = (req.clone(), tr_2.clone());
+                // The dApp gets its reply once the queue sends the transaction
+                tokio::spawn(async move {
+                    let data = match queued.await {
+                        Ok(QueuedTxResult::Submitted(tx_hash)) => {
+                            let _ = tr_2.send(WcEvent::Message(Box::new(req.create_response(
+                                WcData::SessionRequestResponse(Value::String(
+                                    hex::encode_prefixed(tx_hash),
+                                )),
+                                None,
+                            ))));
+                            return;
+                        }
+                        Ok(QueuedTxResult::JsonRpcError {
+                            message,
+                            code,
+                            data,
+                        }) => WcData::Error {
+                            message,
+                            code,
+                            data,
+                        },
+                        Ok(QueuedTxResult::Rejected) | Err(_) => WcData::Error {
+                            message: "User denied tx signing".to_string(),
+                            code: 5000,
+                            data: None,
+                        },
+                    };
+                    let _ = tr_2.send(WcEvent::Message(Box::new(
+                        req.create_response(data, Some(IrnTag::SessionRequestResponse)),
+                    )));
+                });
+                remove_current_request_3 = true;
+            }
         } else if self.sign_popup.is_open() {
             let r = self
                 .sign_popup
@@ -1,33 +1,52 @@
 use alloy::{
     hex,
-    primitives::{keccak256, Address},
+    primitives::{keccak256, Address, B256},
     signers::{
         k256::{
             ecdsa::SigningKey, elliptic_curve::sec1::ToEncodedPoint, AffinePoint, FieldBytes,
             ProjectivePoint, Scalar,
         },
-        local::{MnemonicBuilder, PrivateKeySigner},
+        local::{LocalSignerError, MnemonicBuilder, PrivateKeySigner},
     },
 };
-use coins_bip39::{English, Mnemonic};
-use gm_common::secret::Secret;
-use rand::rngs::OsRng;
+use coins_bip39::{
+    ChineseSimplified, ChineseTraditional, Czech, English, Entropy, French, Italian, Japanese,
+    Korean, Mnemonic, MnemonicError, Portuguese, Spanish, Wordlist, WordlistError,
+};
+use directories::BaseDirs;
+use gm_common::secret::{MnemonicLanguage, MnemonicSecret, Secret, REDACTED};
+use rand::{rngs::OsRng, RngCore};
 use serde::{Deserialize, Serialize};
 use std::{
+    fs,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Mutex,
     },
     time::{Duration, Instant},
 };
+use zeroize::Zeroizing;
+
+use crate::{config::Config, disk_storage::DiskStorageInterface};
+use metadata::AccountMetadataStore;
+use vanity::VanityPattern;
+
+pub mod linux_encrypted;
+pub mod metadata;
+pub mod mining_jobs;
+pub mod slip39;
+pub mod vanity;
 
 pub trait AccountUtils {
-    fn store_mnemonic_wallet(phrase: &str, address: Address) -> crate::Result<()>;
+    fn store_mnemonic_wallet(mnemonic: &MnemonicSecret, address: Address) -> crate::Result<()>;
 
     fn store_private_key(private_key: &FieldBytes, address: Address) -> crate::Result<()>;
 
     fn get_account_list() -> crate::Result<Vec<Address>>;
 
+    fn delete_account(address: &Address) -> crate::Result<()>;
+
     fn get_secret(address: &Address) -> crate::Result<Secret>;
 }
 
@@ -35,16 +54,46 @@ pub struct AccountManager;
 
 impl AccountManager {
     pub fn create_mnemonic_wallet() -> crate::Result<Address> {
-        let phrase = random_mnemonic()?;
-        Self::import_mnemonic_wallet(&phrase)
+        Self::create_mnemonic_wallet_with(DEFAULT_WORD_COUNT, MnemonicLanguage::English, None)
+    }
+
+    /// Creates a mnemonic wallet with the given number of words from the wordlist
+    /// of the language, the optional BIP-39 passphrase acts as the "25th word".
+    pub fn create_mnemonic_wallet_with(
+        word_count: usize,
+        language: MnemonicLanguage,
+        passphrase: Option<String>,
+    ) -> crate::Result<Address> {
+        let mut mnemonic = MnemonicSecret::from_phrase(&random_mnemonic(word_count, language)?);
+        mnemonic.language = language;
+        mnemonic.passphrase = passphrase;
+        Self::import_mnemonic_wallet(&mnemonic)
     }
 
-    pub fn import_mnemonic_wallet(phrase: &str) -> crate::Result<Address> {
-        let address = get_address_from_mnemonic(phrase)?;
-        Self::store_mnemonic_wallet(phrase, address)?;
+    pub fn import_mnemonic_wallet(mnemonic: &MnemonicSecret) -> crate::Result<Address> {
+        let address = get_address_from_mnemonic(mnemonic)?;
+        Self::store_mnemonic_wallet(mnemonic, address)?;
         Ok(address)
     }
 
+    /// Stores the next account of the mnemonic used by `address`, the last index of
+    /// the derivation path is incremented until an account not stored yet is found.
+    pub fn derive_next_account(address: &Address) -> crate::Result<(Address, MnemonicSecret)> {
+        let Secret::Mnemonic(mut mnemonic) = Self::get_secret(address)? else {
+            return Err(crate::Error::NotMnemonicAccount(*address));
+        };
+
+        let accounts = Self::get_account_list()?;
+        loop {
+            mnemonic.derivation_path = next_derivation_path(&mnemonic.derivation_path)?;
+            let next_address = get_address_from_mnemonic(&mnemonic)?;
+            if !accounts.contains(&next_address) {
+                Self::store_mnemonic_wallet(&mnemonic, next_address)?;
+                return Ok((next_address, mnemonic));
+            }
+        }
+    }
+
     pub fn import_private_key(private_key: &str) -> crate::Result<Address> {
         let private_key = hex::decode(private_key)?;
         let address = PrivateKeySigner::from_slice(&private_key)?.address();
@@ -52,141 +101,627 @@ impl AccountManager {
         Ok(address)
     }
 
+    /// Imports a Web3 Secret Storage (keystore v3) JSON file as produced by geth,
+    /// `cast wallet` and MetaMask.
+    pub fn import_keystore(path: &Path, password: &str) -> crate::Result<Address> {
+        let signer = PrivateKeySigner::decrypt_keystore(path, password)
+            .map_err(|e| crate::Error::KeystoreFileImportFailed(path.to_path_buf(), e))?;
+        let address = signer.address();
+        Self::store_private_key(&Zeroizing::new(signer.credential().to_bytes()), address)?;
+        Ok(address)
+    }
+
+    /// Exports the account as a keystore v3 JSON file in the directory, the file is
+    /// named after the address. For mnemonic accounts the derived private key is
+    /// exported. An existing file is never overwritten, it may be the only backup.
+    pub fn export_keystore(
+        address: &Address,
+        dir: &Path,
+        password: &str,
+    ) -> crate::Result<PathBuf> {
+        let name = address.to_string();
+        if dir.join(&name).exists() {
+            return Err(crate::Error::KeystoreFileExists(dir.join(name)));
+        }
+        let signer = Self::load_wallet(address)?;
+
+        fs::create_dir_all(dir)
+            .map_err(|e| crate::Error::CreateDirAllFailed(dir.to_path_buf(), e))?;
+
+        let private_key = Zeroizing::new(signer.credential().to_bytes());
+        PrivateKeySigner::encrypt_keystore(
+            dir,
+            &mut OsRng,
+            private_key.as_slice(),
+            password,
+            Some(&name),
+        )
+        .map_err(|e| crate::Error::KeystoreFileExportFailed(dir.to_path_buf(), e))?;
+
+        Ok(dir.join(name))
+    }
+
+    /// Default location of the keystores managed by `cast wallet`.
+    pub fn foundry_keystores_dir() -> crate::Result<PathBuf> {
+        let dirs = BaseDirs::new().ok_or(crate::Error::BaseDirsFailed)?;
+        Ok(dirs.home_dir().join(".foundry").join("keystores"))
+    }
+
+    /// Whether the user has to choose a password before secrets can be stored. The
+    /// macOS keychain handles authentication on its own so this is always false there.
+    pub fn needs_password_setup() -> crate::Result<bool> {
+        #[cfg(target_os = "macos")]
+        return Ok(false);
+
+        #[cfg(not(target_os = "macos"))]
+        return Ok(!linux_encrypted::LinuxEncrypted::is_password_set()?);
+    }
+
+    pub fn is_unlocked() -> bool {
+        #[cfg(target_os = "macos")]
+        return true;
+
+        #[cfg(not(target_os = "macos"))]
+        return linux_encrypted::LinuxEncrypted::is_unlocked();
+    }
+
+    /// Returns `KeystoreLocked` error if secrets cannot be accessed right now, this
+    /// should be checked before starting any flow that ends up in a signature.
+    pub fn ensure_unlocked() -> crate::Result<()> {
+        if Self::is_unlocked() {
+            Ok(())
+        } else {
+            Err(crate::Error::KeystoreLocked)
+        }
+    }
+
+    /// Sets the password on the first run, otherwise unlocks with the password.
+    pub fn unlock(password: &str) -> crate::Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            let _ = password;
+            return Ok(());
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        if Self::needs_password_setup()? {
+            linux_encrypted::LinuxEncrypted::set_password(password)
+        } else {
+            linux_encrypted::LinuxEncrypted::unlock(password)
+        }
+    }
+
+    pub fn lock() -> crate::Result<()> {
+        #[cfg(target_os = "macos")]
+        return Ok(());
+
+        #[cfg(not(target_os = "macos"))]
+        return linux_encrypted::LinuxEncrypted::lock();
+    }
+
+    /// Keeps the unlocked session alive, should be called on every user input.
+    pub fn record_activity() {
+        #[cfg(not(target_os = "macos"))]
+        linux_encrypted::LinuxEncrypted::record_activity();
+    }
+
+    /// Locks the key store once it was left idle for `timeout`, returns whether
+    /// it got locked. The macOS keychain asks on its own so nothing is locked there.
+    pub fn lock_if_idle(timeout: Duration) -> crate::Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            let _ = timeout;
+            return Ok(false);
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        return linux_encrypted::LinuxEncrypted::lock_if_idle(timeout);
+    }
+
+    /// Adds an account which can be used for viewing the portfolio, but not
+    /// for signing.
+    pub fn add_watch_only(address: &Address, label: &str) -> crate::Result<()> {
+        if Self::get_account_list()?.contains(address) {
+            return Err(crate::Error::AccountAlreadyExists(*address));
+        }
+
+        let mut metadata = AccountMetadataStore::load()?;
+        metadata.add_watch_only(address)?;
+        metadata.set_label(address, label)
+    }
+
+    pub fn is_watch_only(address: &Address) -> crate::Result<bool> {
+        Ok(AccountMetadataStore::load()?.is_watch_only(address))
+    }
+
+    /// Adds a Safe multisig on the network, its transactions are signed by the
+    /// owners stored in gm.
+    pub fn add_safe(address: &Address, network: &str, label: &str) -> crate::Result<()> {
+        if Self::get_account_list()?.contains(address) {
+            return Err(crate::Error::AccountAlreadyExists(*address));
+        }
+
+        let mut metadata = AccountMetadataStore::load()?;
+        metadata.add_safe(address, network)?;
+        metadata.set_label(address, label)
+    }
+
+    /// Network of the Safe, `None` if the account is not a Safe.
+    pub fn safe_network(address: &Address) -> crate::Result<Option<String>> {
+        Ok(AccountMetadataStore::load()?.safe_network(address).cloned())
+    }
+
+    /// Whether the secret of the account is a mnemonic, so that more accounts
+    /// can be derived from it. Accounts stored before this was recorded are
+    /// looked up once while the key store is unlocked.
+    pub fn is_mnemonic(address: &Address) -> crate::Result<bool> {
+        let mut metadata = AccountMetadataStore::load()?;
+        if let Some(mnemonic) = metadata.is_mnemonic(address) {
+            return Ok(mnemonic);
+        }
+        if metadata.is_watch_only(address)
+            || metadata.safe_network(address).is_some()
+            || !Self::is_unlocked()
+        {
+            return Ok(false);
+        }
+        // Unknown until the secret can be read
+        let Ok(secret) = Self::get_secret(address) else {
+            return Ok(false);
+        };
+        let mnemonic = matches!(secret, Secret::Mnemonic(_));
+        metadata.set_secret_stored(address, mnemonic)?;
+        Ok(mnemonic)
+    }
+
+    /// Returns `WatchOnlyAccount` error if there is no secret to sign with.
+    pub fn ensure_can_sign(address: &Address) -> crate::Result<()> {
+        let metadata = AccountMetadataStore::load()?;
+        if metadata.is_watch_only(address) {
+            return Err(crate::Error::WatchOnlyAccount(*address));
+        }
+        if metadata.safe_network(address).is_some() {
+            return Err(crate::Error::SafeAccount(*address));
+        }
+        Self::ensure_unlocked()
+    }
+
+    /// Deletes the account and unsets it as the current account if it was.
+    pub fn remove_account(address: &Address) -> crate::Result<()> {
+        Self::delete_account(address)?;
+
+        let mut config = Config::load()?;
+        if config.current_account == Some(*address) {
+            config.current_account = None;
+            config.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn set_label(address: &Address, label: &str) -> crate::Result<()> {
+        AccountMetadataStore::load()?.set_label(address, label)
+    }
+
+    /// Moves the account one position up or down in the account list.
+    pub fn move_account(address: &Address, up: bool) -> crate::Result<()> {
+        let list = Self::get_account_list()?;
+        AccountMetadataStore::load()?.reorder(&list, address, up)
+    }
+
+    /// Splits the mnemonic of the account into SLIP-39 shares, any `threshold` of
+    /// the shares recover it. Only the entropy of the phrase is shared, so the
+    /// wordlist, derivation path and BIP-39 passphrase have to be kept as well.
+    pub fn slip39_backup(
+        address: &Address,
+        threshold: u8,
+        share_count: u8,
+        passphrase: &str,
+    ) -> crate::Result<Vec<String>> {
+        let Secret::Mnemonic(mnemonic) = Self::get_secret(address)? else {
+            return Err(crate::Error::NotMnemonicAccount(*address));
+        };
+        let entropy = Zeroizing::new(mnemonic_entropy(&mnemonic)?);
+        Ok(slip39::generate_mnemonics(
+            threshold,
+            share_count,
+            &entropy,
+            passphrase,
+        )?)
+    }
+
+    /// Recovers the mnemonic from SLIP-39 shares and imports it, the phrase is
+    /// rebuilt with the wordlist of the given language.
+    pub fn slip39_recover(
+        shares: &[String],
+        passphrase: &str,
+        language: MnemonicLanguage,
+        derivation_path: &str,
+        bip39_passphrase: Option<String>,
+    ) -> crate::Result<Address> {
+        let entropy = Zeroizing::new(slip39::combine_mnemonics(shares, passphrase)?);
+        let phrase = Zeroizing::new(phrase_from_entropy(&entropy, language)?);
+        let mut mnemonic = MnemonicSecret::new(&phrase, derivation_path);
+        mnemonic.language = language;
+        mnemonic.passphrase = bip39_passphrase;
+        Self::import_mnemonic_wallet(&mnemonic)
+    }
+
     pub fn load_wallet(address: &Address) -> crate::Result<PrivateKeySigner> {
         match Self::get_secret(address)? {
-            Secret::Mnemonic(phrase) => get_signer_from_mnemonic(&phrase),
+            Secret::Mnemonic(mnemonic) => get_signer_from_mnemonic(&mnemonic),
             Secret::PrivateKey(private_key) => {
-                Ok(PrivateKeySigner::from_slice(private_key.as_ref())?)
+                Ok(PrivateKeySigner::from_slice(private_key.expose())?)
             }
         }
     }
 }
 
 impl AccountUtils for AccountManager {
-    fn store_mnemonic_wallet(phrase: &str, address: Address) -> crate::Result<()> {
+    /// Stores the secret, a watch-only account of the address can sign from
+    /// now on.
+    fn store_mnemonic_wallet(mnemonic: &MnemonicSecret, address: Address) -> crate::Result<()> {
         #[cfg(target_os = "macos")]
-        return Ok(gm_macos::Macos::store_mnemonic_wallet(phrase, address)?);
+        gm_macos::Macos::store_mnemonic_wallet(mnemonic, address)?;
 
         #[cfg(not(target_os = "macos"))]
-        return linux_insecure::LinuxInsecure::store_mnemonic_wallet(phrase, address);
+        linux_encrypted::LinuxEncrypted::store_mnemonic_wallet(mnemonic, address)?;
+
+        AccountMetadataStore::load()?.set_secret_stored(&address, true)
     }
 
+    /// Stores the secret, a watch-only account of the address can sign from
+    /// now on.
     fn store_private_key(private_key: &FieldBytes, address: Address) -> crate::Result<()> {
         #[cfg(target_os = "macos")]
-        return Ok(gm_macos::Macos::store_private_key(private_key, address)?);
+        gm_macos::Macos::store_private_key(private_key, address)?;
 
         #[cfg(not(target_os = "macos"))]
-        return linux_insecure::LinuxInsecure::store_private_key(private_key, address);
+        linux_encrypted::LinuxEncrypted::store_private_key(private_key, address)?;
+
+        AccountMetadataStore::load()?.set_secret_stored(&address, false)
     }
 
+    /// Accounts in the order chosen by the user, including watch-only accounts.
     fn get_account_list() -> crate::Result<Vec<Address>> {
         #[cfg(target_os = "macos")]
-        return Ok(gm_macos::Macos::get_account_list()?);
+        let mut list = gm_macos::Macos::get_account_list()?;
 
         #[cfg(not(target_os = "macos"))]
-        return linux_insecure::LinuxInsecure::get_account_list();
+        let mut list = linux_encrypted::LinuxEncrypted::get_account_list()?;
+
+        let metadata = AccountMetadataStore::load()?;
+        for address in metadata
+            .watch_only_accounts()
+            .into_iter()
+            .chain(metadata.safe_accounts())
+        {
+            if !list.contains(&address) {
+                list.push(address);
+            }
+        }
+        Ok(metadata.sort(list))
+    }
+
+    /// Deletes the secret as well as the label of the account.
+    fn delete_account(address: &Address) -> crate::Result<()> {
+        let mut metadata = AccountMetadataStore::load()?;
+        if !metadata.is_watch_only(address) && metadata.safe_network(address).is_none() {
+            #[cfg(target_os = "macos")]
+            gm_macos::Macos::delete_account(address)?;
+
+            #[cfg(not(target_os = "macos"))]
+            linux_encrypted::LinuxEncrypted::delete_account(address)?;
+        }
+
+        metadata.remove(address)
     }
 
     fn get_secret(address: &Address) -> crate::Result<Secret> {
+        if Self::is_watch_only(address)? {
+            return Err(crate::Error::WatchOnlyAccount(*address));
+        }
+
         #[cfg(target_os = "macos")]
         return Ok(gm_macos::Macos::get_secret(address)?);
 
         #[cfg(not(target_os = "macos"))]
-        return linux_insecure::LinuxInsecure::get_secret(address);
+        return linux_encrypted::LinuxEncrypted::get_secret(address);
+    }
+}
+
+/// Word counts allowed by BIP-39.
+pub const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+pub const DEFAULT_WORD_COUNT: usize = 24;
+
+/// Operation generic over the BIP-39 wordlist, see [`with_wordlist`].
+trait WordlistOp {
+    type Output;
+
+    fn run<W: Wordlist>(self) -> Self::Output;
+}
+
+/// Runs the operation with the wordlist of the language.
+fn with_wordlist<Op: WordlistOp>(language: MnemonicLanguage, op: Op) -> Op::Output {
+    match language {
+        MnemonicLanguage::English => op.run::<English>(),
+        MnemonicLanguage::ChineseSimplified => op.run::<ChineseSimplified>(),
+        MnemonicLanguage::ChineseTraditional => op.run::<ChineseTraditional>(),
+        MnemonicLanguage::Czech => op.run::<Czech>(),
+        MnemonicLanguage::French => op.run::<French>(),
+        MnemonicLanguage::Italian => op.run::<Italian>(),
+        MnemonicLanguage::Japanese => op.run::<Japanese>(),
+        MnemonicLanguage::Korean => op.run::<Korean>(),
+        MnemonicLanguage::Portuguese => op.run::<Portuguese>(),
+        MnemonicLanguage::Spanish => op.run::<Spanish>(),
+    }
+}
+
+fn random_mnemonic(word_count: usize, language: MnemonicLanguage) -> crate::Result<String> {
+    struct Random(usize);
+
+    impl WordlistOp for Random {
+        type Output = crate::Result<String>;
+
+        fn run<W: Wordlist>(self) -> Self::Output {
+            let mnemonic = Mnemonic::<W>::new_with_count(&mut OsRng, self.0)
+                .map_err(crate::Error::MnemonicGenerationFailed)?;
+            Ok(mnemonic.to_phrase())
+        }
+    }
+
+    with_wordlist(language, Random(word_count))
+}
+
+/// Entropy encoded by the phrase, the checksum is verified as well.
+fn mnemonic_entropy(mnemonic: &MnemonicSecret) -> crate::Result<Vec<u8>> {
+    struct MnemonicEntropy<'a>(&'a str);
+
+    impl WordlistOp for MnemonicEntropy<'_> {
+        type Output = crate::Result<Vec<u8>>;
+
+        fn run<W: Wordlist>(self) -> Self::Output {
+            let phrase = self.0;
+            Mnemonic::<W>::new_from_phrase(phrase).map_err(|_| crate::Error::InvalidMnemonic)?;
+
+            let bits = phrase
+                .split(' ')
+                .map(|word| W::get_index(word).map_err(|_| crate::Error::InvalidMnemonic))
+                .collect::<crate::Result<Vec<_>>>()?
+                .into_iter()
+                .flat_map(|index| (0..11).rev().map(move |i| (index >> i) & 1 == 1))
+                .collect::<Vec<_>>();
+            // The checksum takes one bit of every 33
+            let entropy_bits = bits.len() / 33 * 32;
+            Ok(bits[..entropy_bits]
+                .chunks(8)
+                .map(|chunk| chunk.iter().fold(0, |byte, &bit| byte << 1 | bit as u8))
+                .collect())
+        }
+    }
+
+    with_wordlist(mnemonic.language, MnemonicEntropy(&mnemonic.phrase))
+}
+
+fn phrase_from_entropy(entropy: &[u8], language: MnemonicLanguage) -> crate::Result<String> {
+    struct Phrase<'a>(&'a [u8]);
+
+    impl WordlistOp for Phrase<'_> {
+        type Output = crate::Result<String>;
+
+        fn run<W: Wordlist>(self) -> Self::Output {
+            let entropy = Entropy::from_slice(self.0).map_err(|_| crate::Error::InvalidMnemonic)?;
+            Ok(Mnemonic::<W>::new_from_entropy(entropy).to_phrase())
+        }
     }
+
+    with_wordlist(language, Phrase(entropy))
 }
 
-fn random_mnemonic() -> crate::Result<String> {
-    let mnemonic = Mnemonic::<English>::new_with_count(&mut OsRng, 24)
-        .map_err(crate::Error::MnemonicGenerationFailed)?;
-    Ok(mnemonic.to_phrase())
+fn get_signer_from_mnemonic(mnemonic: &MnemonicSecret) -> crate::Result<PrivateKeySigner> {
+    struct Build<'a>(&'a MnemonicSecret);
+
+    impl WordlistOp for Build<'_> {
+        type Output = crate::Result<PrivateKeySigner>;
+
+        fn run<W: Wordlist>(self) -> Self::Output {
+            let mnemonic = self.0;
+            let mut builder = MnemonicBuilder::<W>::default()
+                .phrase(mnemonic.phrase.as_str())
+                .derivation_path(&mnemonic.derivation_path)
+                .map_err(|_| {
+                    crate::Error::InvalidDerivationPath(mnemonic.derivation_path.clone())
+                })?;
+            if let Some(passphrase) = &mnemonic.passphrase {
+                builder = builder.password(passphrase);
+            }
+            builder
+                .build()
+                .map_err(|e| crate::Error::MnemonicSignerFailed(redact_signer_error(e)))
+        }
+    }
+
+    with_wordlist(mnemonic.language, Build(mnemonic))
 }
 
-fn get_signer_from_mnemonic(phrase: &str) -> crate::Result<PrivateKeySigner> {
-    let signer = MnemonicBuilder::<English>::default()
-        .phrase(phrase)
-        .build()
-        .map_err(crate::Error::MnemonicSignerFailed)?;
-    Ok(signer)
+/// Errors of the mnemonic crates can contain the phrase or a word of it, which
+/// would end up in the error popup.
+fn redact_signer_error(error: LocalSignerError) -> LocalSignerError {
+    match error {
+        LocalSignerError::Bip39Error(MnemonicError::InvalidPhrase(_)) => {
+            LocalSignerError::Bip39Error(MnemonicError::InvalidPhrase(REDACTED.to_string()))
+        }
+        LocalSignerError::Bip39Error(MnemonicError::WordlistError(WordlistError::InvalidWord(
+            _,
+        ))) => LocalSignerError::Bip39Error(MnemonicError::WordlistError(
+            WordlistError::InvalidWord(REDACTED.to_string()),
+        )),
+        error => error,
+    }
 }
 
-fn get_address_from_mnemonic(phrase: &str) -> crate::Result<Address> {
-    let signer = get_signer_from_mnemonic(phrase)?;
+fn get_address_from_mnemonic(mnemonic: &MnemonicSecret) -> crate::Result<Address> {
+    let signer = get_signer_from_mnemonic(mnemonic)?;
     Ok(signer.address())
 }
 
-/// Mines a wallet whose address matches the given masks.
-/// - `mask_a` specifies bits that must be 1 in the 20 byte address.
-/// - `mask_b` specifies bits that must be 0 in the 20 byte address.
+/// Increments the last index of the derivation path, keeping it hardened if it was.
+/// e.g. `m/44'/60'/0'/0/3` becomes `m/44'/60'/0'/0/4`.
+pub fn next_derivation_path(path: &str) -> crate::Result<String> {
+    let invalid = || crate::Error::InvalidDerivationPath(path.to_string());
+
+    let (prefix, last) = path.rsplit_once('/').ok_or_else(invalid)?;
+    let (index, hardened) = match last.strip_suffix('\'') {
+        Some(index) => (index, "'"),
+        None => (last, ""),
+    };
+    let index = index
+        .parse::<u32>()
+        .ok()
+        .and_then(|index| index.checked_add(1))
+        .ok_or_else(invalid)?;
+
+    Ok(format!("{prefix}/{index}{hardened}"))
+}
+
+/// Mines a wallet whose address matches the given pattern.
+/// - `pattern` specifies the hex nibbles the address must have, and for the
+///   letters among them whether they must be upper or lower case in the EIP-55
+///   checksummed address. The checksum case is only checked once the nibbles
+///   match.
 /// - `max_dur` specifies the maximum duration to mine for. If None, mines indefinitely.
-/// - `shutdown_signal` can be used to stop mining from main thread.
+/// - `exit_signal` can be used to stop mining from main thread.
 pub fn mine_wallet(
-    mask_a: Address,
-    mask_b: Address,
+    pattern: VanityPattern,
+    max_dur: Option<Duration>,
+    exit_signal: Arc<AtomicBool>,
+) -> crate::Result<(Option<SigningKey>, u64, Duration)> {
+    Ok(mine_parallel(max_dur, exit_signal, |should_stop| {
+        mine_key(should_stop, |address| pattern.is_match(address))
+    }))
+}
+
+/// Mines a deployer key whose first contract, i.e. `CREATE` with nonce 0,
+/// lands on an address matching the pattern.
+pub fn mine_deployer(
+    pattern: VanityPattern,
     max_dur: Option<Duration>,
     exit_signal: Arc<AtomicBool>,
 ) -> crate::Result<(Option<SigningKey>, u64, Duration)> {
-    let counter = Arc::new(AtomicU64::new(0));
-    let stop = Arc::new(AtomicBool::new(false));
-    let result = Arc::new(Mutex::new(None));
+    Ok(mine_parallel(max_dur, exit_signal, |should_stop| {
+        mine_key(should_stop, |address| {
+            pattern.is_match(&create_addr_nonce_0(address))
+        })
+    }))
+}
+
+/// Mines a salt for which `CREATE2` from the deployer with the init code hash
+/// lands on an address matching the pattern.
+pub fn mine_create2_salt(
+    deployer: Address,
+    init_code_hash: B256,
+    pattern: VanityPattern,
+    max_dur: Option<Duration>,
+    exit_signal: Arc<AtomicBool>,
+) -> crate::Result<(Option<B256>, u64, Duration)> {
+    Ok(mine_parallel(max_dur, exit_signal, |should_stop| {
+        // 0xff ++ deployer ++ salt ++ init_code_hash, salt is changed in place
+        let mut preimage = [0u8; 85];
+        preimage[0] = 0xff;
+        preimage[1..21].copy_from_slice(deployer.as_slice());
+        OsRng.fill_bytes(&mut preimage[21..53]);
+        preimage[53..85].copy_from_slice(init_code_hash.as_slice());
+
+        let mut count = 0;
+        while !should_stop() {
+            let hash = keccak256(preimage);
+            if pattern.is_match(hash[12..32].try_into().unwrap()) {
+                return (Some(B256::from_slice(&preimage[21..53])), count);
+            }
+
+            // Change salt by one
+            let tail = u64::from_be_bytes(preimage[45..53].try_into().unwrap());
+            preimage[45..53].copy_from_slice(&tail.wrapping_add(1).to_be_bytes());
+            count += 1;
+        }
+        (None, count)
+    }))
+}
+
+/// Runs the search on all the threads until one of them finds a match, the
+/// search returns the match if any along with the number of attempts made.
+fn mine_parallel<T, F>(
+    max_dur: Option<Duration>,
+    exit_signal: Arc<AtomicBool>,
+    search: F,
+) -> (Option<T>, u64, Duration)
+where
+    T: Send,
+    F: Fn(&dyn Fn() -> bool) -> (Option<T>, u64) + Sync,
+{
+    let counter = AtomicU64::new(0);
+    let stop = AtomicBool::new(false);
+    let result = Mutex::new(None);
     let start = Instant::now();
 
     rayon::scope(|s| {
         for _ in 0..rayon::current_num_threads() {
-            let counter = Arc::clone(&counter);
-            let stop = Arc::clone(&stop);
-            let result = Arc::clone(&result);
-            let exit_signal = exit_signal.clone();
-            s.spawn(move |_| {
-                // Start with a random private key
-                let initial_random_key = **SigningKey::random(&mut OsRng).as_nonzero_scalar();
-
-                let mut k = initial_random_key;
-                let mut curve_point = ProjectivePoint::GENERATOR * k;
-
-                while !stop.load(Ordering::Relaxed) && !exit_signal.load(Ordering::Relaxed) {
-                    if let Some(max_dur) = max_dur {
-                        if Instant::now().duration_since(start) > max_dur {
-                            break;
-                        }
-                    }
-
-                    let address = eth_addr_from_affine(&curve_point.to_affine());
-                    if addr_match(&address, &mask_a, &mask_b) {
-                        stop.store(true, Ordering::Relaxed);
-                        let mut result = result.lock().unwrap();
-                        *result = Some(SigningKey::from_bytes(&k.to_bytes()).unwrap());
-                        break;
-                    };
-
-                    // Change private key by one
-                    k += Scalar::ONE;
-                    curve_point += ProjectivePoint::GENERATOR;
+            s.spawn(|_| {
+                let should_stop = || {
+                    stop.load(Ordering::Relaxed)
+                        || exit_signal.load(Ordering::Relaxed)
+                        || max_dur.is_some_and(|max_dur| start.elapsed() > max_dur)
+                };
+
+                let (found, count) = search(&should_stop);
+                if let Some(found) = found {
+                    stop.store(true, Ordering::Relaxed);
+                    *result.lock().unwrap() = Some(found);
                 }
-
-                let count_bytes: [u8; 32] = (k - initial_random_key).to_bytes().into();
-                let count = u64::from_be_bytes(count_bytes[24..32].try_into().unwrap());
                 counter.fetch_add(count, Ordering::Relaxed);
             });
         }
     });
 
-    let result = result.lock().unwrap().clone();
-    let counter = counter.load(Ordering::Relaxed);
-    Ok((result, counter, Instant::now().duration_since(start)))
+    let result = result.into_inner().unwrap();
+    (result, counter.into_inner(), start.elapsed())
 }
 
-#[inline(always)]
-fn addr_match(addr: &[u8; 20], mask_a: &[u8; 20], mask_b: &[u8; 20]) -> bool {
-    for i in 0..20 {
-        if (addr[i] & mask_a[i]) != mask_a[i] {
-            return false;
-        }
-        if ((!addr[i]) & mask_b[i]) != mask_b[i] {
-            return false;
+/// Walks private keys from a random one, using point addition instead of a
+/// full scalar multiplication for every key.
+fn mine_key(
+    should_stop: &dyn Fn() -> bool,
+    is_match: impl Fn(&[u8; 20]) -> bool,
+) -> (Option<SigningKey>, u64) {
+    // Start with a random private key
+    let mut k = **SigningKey::random(&mut OsRng).as_nonzero_scalar();
+    let mut curve_point = ProjectivePoint::GENERATOR * k;
+
+    let mut count = 0;
+    while !should_stop() {
+        let address = eth_addr_from_affine(&curve_point.to_affine());
+        if is_match(&address) {
+            return (Some(SigningKey::from_bytes(&k.to_bytes()).unwrap()), count);
         }
+
+        // Change private key by one
+        k += Scalar::ONE;
+        curve_point += ProjectivePoint::GENERATOR;
+        count += 1;
     }
-    true
+    (None, count)
+}
+
+/// Address of the contract deployed by the first transaction of the sender,
+/// i.e. `keccak256(rlp([sender, 0]))`.
+#[inline(always)]
+fn create_addr_nonce_0(sender: &[u8; 20]) -> [u8; 20] {
+    let mut rlp = [0u8; 23];
+    rlp[0] = 0xd6; // list of 22 bytes
+    rlp[1] = 0x94; // string of 20 bytes
+    rlp[2..22].copy_from_slice(sender);
+    rlp[22] = 0x80; // nonce 0
+    keccak256(rlp)[12..32].try_into().unwrap()
 }
 
 #[inline(always)]
@@ -205,18 +740,22 @@ pub mod linux_insecure {
     pub struct LinuxInsecure;
 
     impl AccountUtils for LinuxInsecure {
-        fn store_mnemonic_wallet(phrase: &str, address: Address) -> crate::Result<()> {
-            InsecurePrivateKeyStore::load()?.add(address, Secret::Mnemonic(phrase.to_string()))
+        fn store_mnemonic_wallet(mnemonic: &MnemonicSecret, address: Address) -> crate::Result<()> {
+            InsecurePrivateKeyStore::load()?.add(address, Secret::Mnemonic(mnemonic.clone()))
         }
 
         fn store_private_key(private_key: &FieldBytes, address: Address) -> crate::Result<()> {
-            InsecurePrivateKeyStore::load()?.add(address, Secret::PrivateKey(*private_key))
+            InsecurePrivateKeyStore::load()?.add(address, Secret::from_private_key(private_key))
         }
 
         fn get_account_list() -> crate::Result<Vec<Address>> {
             Ok(InsecurePrivateKeyStore::load()?.list())
         }
 
+        fn delete_account(address: &Address) -> crate::Result<()> {
+            InsecurePrivateKeyStore::load()?.remove(address)
+        }
+
         fn get_secret(address: &Address) -> crate::Result<Secret> {
             InsecurePrivateKeyStore::load()?
                 .find_by_address(address)
@@ -224,7 +763,8 @@ pub mod linux_insecure {
         }
     }
 
-    // TODO remove this once we have implemented a secure store for linux
+    /// Plaintext store used by older versions, it is migrated into the encrypted
+    /// store on the first unlock.
     #[derive(Serialize, Deserialize, Debug, Default)]
     pub struct InsecurePrivateKeyStore {
         pub keys: Vec<(Address, Secret)>,
@@ -241,18 +781,137 @@ pub mod linux_insecure {
             self.save()
         }
 
-        pub fn find_by_address(&self, address: &Address) -> Option<Secret> {
-            self.keys.iter().find_map(|(stored_address, key)| {
-                if stored_address == address {
-                    Some(key.clone())
-                } else {
-                    None
-                }
-            })
+        /// Takes the store so that the secret is moved out instead of cloned, the
+        /// other secrets are zeroed when dropped.
+        pub fn find_by_address(self, address: &Address) -> Option<Secret> {
+            self.keys
+                .into_iter()
+                .find_map(|(stored_address, key)| (&stored_address == address).then_some(key))
         }
 
         pub fn list(self) -> Vec<Address> {
             self.keys.into_iter().map(|(address, _)| address).collect()
         }
+
+        pub fn remove(&mut self, address: &Address) -> crate::Result<()> {
+            let len = self.keys.len();
+            self.keys
+                .retain(|(stored_address, _)| stored_address != address);
+            if self.keys.len() == len {
+                return Err(crate::Error::SecretNotFound(*address));
+            }
+            self.save()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_debug_is_redacted() {
+        let phrase = "test test test test test test test test test test test junk";
+        let mut mnemonic = MnemonicSecret::from_phrase(phrase);
+        mnemonic.passphrase = Some("25th word".to_string());
+        let debug = format!("{:?}", Secret::Mnemonic(mnemonic));
+        assert!(!debug.contains("test") && !debug.contains("25th"));
+
+        let private_key = Secret::from_private_key(&FieldBytes::from([0xab; 32]));
+        assert!(!format!("{private_key:?}").contains("abab"));
+
+        // Serialization is unaffected, it is what the stores encrypt
+        let json = serde_json::to_string(&private_key).unwrap();
+        match serde_json::from_str::<Secret>(&json).unwrap() {
+            Secret::PrivateKey(bytes) => assert_eq!(bytes.expose(), &[0xab; 32]),
+            Secret::Mnemonic(_) => panic!("expected private key"),
+        }
+    }
+
+    #[test]
+    fn test_next_derivation_path() {
+        assert_eq!(
+            next_derivation_path("m/44'/60'/0'/0/0").unwrap(),
+            "m/44'/60'/0'/0/1"
+        );
+        assert_eq!(
+            next_derivation_path("m/44'/60'/9'").unwrap(),
+            "m/44'/60'/10'"
+        );
+        assert!(next_derivation_path("m/44'/60'/0'/0/x").is_err());
+        assert!(next_derivation_path("m").is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_passphrase_and_language() {
+        let mut mnemonic = MnemonicSecret::from_phrase(
+            "test test test test test test test test test test test junk",
+        );
+        assert_eq!(
+            get_address_from_mnemonic(&mnemonic).unwrap(),
+            alloy::primitives::address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+        );
+
+        mnemonic.passphrase = Some("25th word".to_string());
+        assert_ne!(
+            get_address_from_mnemonic(&mnemonic).unwrap(),
+            alloy::primitives::address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+        );
+
+        for word_count in MNEMONIC_WORD_COUNTS {
+            let phrase = random_mnemonic(word_count, MnemonicLanguage::Spanish).unwrap();
+            let mut mnemonic = MnemonicSecret::from_phrase(&phrase);
+            assert!(get_address_from_mnemonic(&mnemonic).is_err());
+
+            mnemonic.language = MnemonicLanguage::Spanish;
+            assert!(get_address_from_mnemonic(&mnemonic).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_entropy_round_trip() {
+        let mnemonic = MnemonicSecret::from_phrase(
+            "test test test test test test test test test test test junk",
+        );
+        let entropy = mnemonic_entropy(&mnemonic).unwrap();
+        assert_eq!(entropy.len(), 16);
+        assert_eq!(
+            phrase_from_entropy(&entropy, MnemonicLanguage::English).unwrap(),
+            mnemonic.phrase
+        );
+
+        for word_count in MNEMONIC_WORD_COUNTS {
+            let mut mnemonic = MnemonicSecret::from_phrase(
+                &random_mnemonic(word_count, MnemonicLanguage::French).unwrap(),
+            );
+            mnemonic.language = MnemonicLanguage::French;
+            let entropy = mnemonic_entropy(&mnemonic).unwrap();
+            assert_eq!(entropy.len(), word_count * 4 / 3);
+            assert_eq!(
+                phrase_from_entropy(&entropy, MnemonicLanguage::French).unwrap(),
+                mnemonic.phrase
+            );
+        }
+    }
+
+    #[test]
+    fn test_mine_create_and_create2() {
+        // Masks which constrain the first nibble to be 0xa
+        let pattern = VanityPattern::from_masks(
+            alloy::primitives::address!("0xa000000000000000000000000000000000000000"),
+            alloy::primitives::address!("0x5000000000000000000000000000000000000000"),
+        );
+        let exit_signal = Arc::new(AtomicBool::new(false));
+
+        let (key, _, _) = mine_deployer(pattern, None, exit_signal.clone()).unwrap();
+        let deployer = Address::from_private_key(&key.unwrap());
+        let contract = deployer.create(0);
+        assert_eq!(contract[0] >> 4, 0xa);
+
+        let init_code_hash = keccak256([0x60, 0x00]);
+        let (salt, _, _) =
+            mine_create2_salt(deployer, init_code_hash, pattern, None, exit_signal).unwrap();
+        let contract = deployer.create2(salt.unwrap(), init_code_hash);
+        assert_eq!(contract[0] >> 4, 0xa);
     }
 }
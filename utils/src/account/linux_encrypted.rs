@@ -0,0 +1,435 @@
+//! Password encrypted secret store for platforms without a system keychain.
+//!
+//! Each secret is encrypted with XChaCha20-Poly1305 using a key derived from the
+//! user password with scrypt. Addresses are stored in plaintext so that the
+//! account list can be displayed without unlocking. The derived key is only kept
+//! in memory while the store is unlocked.
+
+use std::{
+    fs,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use alloy::primitives::{Address, Bytes};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use gm_common::secret::SecretBytes;
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use crate::disk_storage::{DiskStorageInterface, FileFormat};
+
+use super::{linux_insecure::InsecurePrivateKeyStore, *};
+
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+
+/// Plaintext encrypted as the password check value, it is used to tell a wrong
+/// password apart from a corrupted entry.
+const PASSWORD_CHECK: &[u8] = b"gm encrypted private key store";
+
+type SessionKey = SecretBytes<KEY_LEN>;
+
+/// Derived key of the unlocked store, `None` while the store is locked.
+static SESSION_KEY: Mutex<Option<SessionKey>> = Mutex::new(None);
+
+/// Last time the user did something while the store was unlocked, used to lock
+/// the store again after it was left idle.
+static LAST_ACTIVITY: Mutex<Option<Instant>> = Mutex::new(None);
+
+pub struct LinuxEncrypted;
+
+impl AccountUtils for LinuxEncrypted {
+    fn store_mnemonic_wallet(mnemonic: &MnemonicSecret, address: Address) -> crate::Result<()> {
+        EncryptedPrivateKeyStore::load()?.add(address, &Secret::Mnemonic(mnemonic.clone()))
+    }
+
+    fn store_private_key(private_key: &FieldBytes, address: Address) -> crate::Result<()> {
+        EncryptedPrivateKeyStore::load()?.add(address, &Secret::from_private_key(private_key))
+    }
+
+    fn get_account_list() -> crate::Result<Vec<Address>> {
+        let mut list = EncryptedPrivateKeyStore::load()?.list();
+
+        // Accounts which are not yet migrated should still be visible
+        for address in InsecurePrivateKeyStore::load()?.list() {
+            if !list.contains(&address) {
+                list.push(address);
+            }
+        }
+
+        Ok(list)
+    }
+
+    fn delete_account(address: &Address) -> crate::Result<()> {
+        EncryptedPrivateKeyStore::load()?.remove(address)
+    }
+
+    fn get_secret(address: &Address) -> crate::Result<Secret> {
+        EncryptedPrivateKeyStore::load()?.find_by_address(address)
+    }
+}
+
+impl LinuxEncrypted {
+    /// Whether a password was already chosen for the store.
+    pub fn is_password_set() -> crate::Result<bool> {
+        Ok(EncryptedPrivateKeyStore::load()?.kdf.is_some())
+    }
+
+    pub fn is_unlocked() -> bool {
+        SESSION_KEY.lock().map(|key| key.is_some()).unwrap_or(false)
+    }
+
+    /// Sets the password for a fresh store, unlocks it and migrates the
+    /// plaintext entries from the insecure store if any.
+    pub fn set_password(password: &str) -> crate::Result<()> {
+        let mut store = EncryptedPrivateKeyStore::load()?;
+        if store.kdf.is_some() {
+            return Err(crate::Error::KeystorePasswordAlreadySet);
+        }
+
+        let kdf = KdfParams::random();
+        let key = derive_key(password, &kdf)?;
+        store.kdf = Some(kdf);
+        store.password_check = Some(encrypt(&key, PASSWORD_CHECK, &[])?);
+        store.save()?;
+
+        set_session_key(Some(key.clone()))?;
+
+        store.migrate_insecure_store(&key)
+    }
+
+    /// Derives the key from the password and keeps it in memory until `lock`.
+    pub fn unlock(password: &str) -> crate::Result<()> {
+        let mut store = EncryptedPrivateKeyStore::load()?;
+        let kdf = store
+            .kdf
+            .as_ref()
+            .ok_or(crate::Error::KeystorePasswordNotSet)?;
+        let key = derive_key(password, kdf)?;
+
+        let check = store
+            .password_check
+            .as_ref()
+            .ok_or(crate::Error::KeystorePasswordNotSet)?;
+        match decrypt(&key, check, &[]) {
+            Ok(plaintext) if plaintext.as_slice() == PASSWORD_CHECK => {}
+            _ => return Err(crate::Error::KeystoreWrongPassword),
+        }
+
+        set_session_key(Some(key.clone()))?;
+
+        store.migrate_insecure_store(&key)
+    }
+
+    /// Forgets the derived key, secrets cannot be read until unlocked again.
+    pub fn lock() -> crate::Result<()> {
+        set_session_key(None)
+    }
+
+    pub fn record_activity() {
+        if let Ok(mut last_activity) = LAST_ACTIVITY.lock() {
+            *last_activity = Some(Instant::now());
+        }
+    }
+
+    /// Locks the store if there was no activity for `timeout`, returns whether
+    /// it was locked by this call.
+    pub fn lock_if_idle(timeout: Duration) -> crate::Result<bool> {
+        if !Self::is_unlocked() {
+            return Ok(false);
+        }
+
+        let last_activity = *LAST_ACTIVITY
+            .lock()
+            .map_err(|_| crate::Error::KeystoreSessionPoisoned)?;
+        let idle = is_idle(last_activity, Instant::now(), timeout);
+        if idle {
+            Self::lock()?;
+        }
+        Ok(idle)
+    }
+}
+
+/// Whether the store counts as idle at `now`, no recorded activity counts as
+/// idle.
+fn is_idle(last_activity: Option<Instant>, now: Instant, timeout: Duration) -> bool {
+    last_activity.is_none_or(|last_activity| now.duration_since(last_activity) >= timeout)
+}
+
+fn set_session_key(key: Option<SessionKey>) -> crate::Result<()> {
+    let mut session_key = SESSION_KEY
+        .lock()
+        .map_err(|_| crate::Error::KeystoreSessionPoisoned)?;
+    *session_key = key;
+    drop(session_key);
+
+    // The idle time counts from the moment of unlocking
+    LinuxEncrypted::record_activity();
+    Ok(())
+}
+
+fn session_key() -> crate::Result<SessionKey> {
+    SESSION_KEY
+        .lock()
+        .map_err(|_| crate::Error::KeystoreSessionPoisoned)?
+        .clone()
+        .ok_or(crate::Error::KeystoreLocked)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KdfParams {
+    pub salt: Bytes,
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl KdfParams {
+    fn random() -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        // Recommended interactive parameters from the scrypt paper
+        Self {
+            salt: Bytes::from(salt.to_vec()),
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EncryptedBlob {
+    pub nonce: Bytes,
+    pub ciphertext: Bytes,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EncryptedEntry {
+    pub address: Address,
+    pub secret: EncryptedBlob,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct EncryptedPrivateKeyStore {
+    pub kdf: Option<KdfParams>,
+    pub password_check: Option<EncryptedBlob>,
+    #[serde(default)]
+    pub keys: Vec<EncryptedEntry>,
+}
+
+impl DiskStorageInterface for EncryptedPrivateKeyStore {
+    const FILE_NAME: &'static str = "encrypted_private_key_store";
+    const FORMAT: FileFormat = FileFormat::TOML;
+}
+
+impl EncryptedPrivateKeyStore {
+    pub fn add(&mut self, address: Address, secret: &Secret) -> crate::Result<()> {
+        let key = session_key()?;
+        self.keys.push(encrypt_secret(&key, address, secret)?);
+        self.save()
+    }
+
+    pub fn find_by_address(&self, address: &Address) -> crate::Result<Secret> {
+        // Unlocking migrates the plaintext store, so after this every secret is in `keys`
+        let key = session_key()?;
+        let entry = self
+            .keys
+            .iter()
+            .find(|entry| &entry.address == address)
+            .ok_or(crate::Error::SecretNotFound(*address))?;
+
+        decrypt_secret(&key, entry)
+    }
+
+    /// Requires the store to be unlocked, so that accounts cannot be deleted
+    /// without knowing the password.
+    pub fn remove(&mut self, address: &Address) -> crate::Result<()> {
+        session_key()?;
+
+        let len = self.keys.len();
+        self.keys.retain(|entry| &entry.address != address);
+        if self.keys.len() == len {
+            return Err(crate::Error::SecretNotFound(*address));
+        }
+        self.save()
+    }
+
+    pub fn list(self) -> Vec<Address> {
+        self.keys.into_iter().map(|entry| entry.address).collect()
+    }
+
+    /// Moves the secrets from the plaintext store into this store and deletes
+    /// the plaintext file.
+    fn migrate_insecure_store(&mut self, key: &SessionKey) -> crate::Result<()> {
+        let path = InsecurePrivateKeyStore::path()?;
+        if !path.exists() {
+            return Ok(());
+        }
+
+        for (address, secret) in InsecurePrivateKeyStore::load()?.keys {
+            if !self.keys.iter().any(|entry| entry.address == address) {
+                self.keys.push(encrypt_secret(key, address, &secret)?);
+            }
+        }
+        self.save()?;
+
+        // Overwrite the contents before unlinking, as a best effort to not leave
+        // the plaintext secrets around on the disk.
+        let len = fs::metadata(&path)
+            .map_err(|e| crate::Error::FileReadFailed(path.clone(), e))?
+            .len() as usize;
+        fs::write(&path, vec![0u8; len])
+            .map_err(|e| crate::Error::FileWriteFailed(path.clone(), e))?;
+        fs::remove_file(&path).map_err(|e| crate::Error::FileRemoveFailed(path, e))
+    }
+}
+
+fn derive_key(password: &str, kdf: &KdfParams) -> crate::Result<SessionKey> {
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, KEY_LEN)
+        .map_err(|_| crate::Error::KeystoreKdfParamsInvalid)?;
+    let mut key = SessionKey::zero();
+    scrypt::scrypt(password.as_bytes(), &kdf.salt, &params, key.expose_mut())
+        .map_err(|_| crate::Error::KeystoreKdfParamsInvalid)?;
+    Ok(key)
+}
+
+fn encrypt(key: &SessionKey, plaintext: &[u8], aad: &[u8]) -> crate::Result<EncryptedBlob> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext = XChaCha20Poly1305::new(key.expose().into())
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| crate::Error::KeystoreEncryptionFailed)?;
+
+    Ok(EncryptedBlob {
+        nonce: Bytes::from(nonce.to_vec()),
+        ciphertext: Bytes::from(ciphertext),
+    })
+}
+
+fn decrypt(key: &SessionKey, blob: &EncryptedBlob, aad: &[u8]) -> Result<Zeroizing<Vec<u8>>, ()> {
+    if blob.nonce.len() != NONCE_LEN {
+        return Err(());
+    }
+
+    XChaCha20Poly1305::new(key.expose().into())
+        .decrypt(
+            XNonce::from_slice(&blob.nonce),
+            Payload {
+                msg: &blob.ciphertext,
+                aad,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| ())
+}
+
+/// The address is used as associated data so that entries cannot be swapped.
+fn encrypt_secret(
+    key: &SessionKey,
+    address: Address,
+    secret: &Secret,
+) -> crate::Result<EncryptedEntry> {
+    let plaintext = Zeroizing::new(
+        serde_json::to_vec(secret).map_err(|_| crate::Error::KeystoreEncryptionFailed)?,
+    );
+    Ok(EncryptedEntry {
+        address,
+        secret: encrypt(key, &plaintext, address.as_slice())?,
+    })
+}
+
+fn decrypt_secret(key: &SessionKey, entry: &EncryptedEntry) -> crate::Result<Secret> {
+    let plaintext = decrypt(key, &entry.secret, entry.address.as_slice())
+        .map_err(|_| crate::Error::KeystoreDecryptionFailed(entry.address))?;
+    serde_json::from_slice(&plaintext)
+        .map_err(|_| crate::Error::KeystoreDecryptionFailed(entry.address))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+
+    fn cheap_kdf() -> KdfParams {
+        KdfParams {
+            salt: Bytes::from(vec![7u8; SALT_LEN]),
+            log_n: 4,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    #[test]
+    fn test_secret_roundtrip() {
+        let key = derive_key("hunter2", &cheap_kdf()).unwrap();
+        let address = address!("0x000000000000000000000000000000000000dEaD");
+        let phrase = "test test test test test test test test test test test junk";
+
+        let mnemonic = MnemonicSecret::new(phrase, "m/44'/60'/0'/0/7");
+
+        let entry = encrypt_secret(&key, address, &Secret::Mnemonic(mnemonic.clone())).unwrap();
+        assert!(!entry
+            .secret
+            .ciphertext
+            .windows(phrase.len())
+            .any(|w| w == phrase.as_bytes()));
+
+        match decrypt_secret(&key, &entry).unwrap() {
+            Secret::Mnemonic(decrypted) => assert_eq!(decrypted, mnemonic),
+            Secret::PrivateKey(_) => panic!("expected mnemonic"),
+        }
+    }
+
+    #[test]
+    fn test_wrong_password_or_address_fails() {
+        let key = derive_key("hunter2", &cheap_kdf()).unwrap();
+        let wrong_key = derive_key("hunter3", &cheap_kdf()).unwrap();
+        let address = address!("0x000000000000000000000000000000000000dEaD");
+
+        let mut entry = encrypt_secret(
+            &key,
+            address,
+            &Secret::from_private_key(&FieldBytes::from([1u8; 32])),
+        )
+        .unwrap();
+        assert!(decrypt_secret(&wrong_key, &entry).is_err());
+
+        entry.address = Address::ZERO;
+        assert!(decrypt_secret(&key, &entry).is_err());
+    }
+
+    #[test]
+    fn test_is_idle() {
+        let timeout = Duration::from_secs(60);
+        let last_activity = Instant::now();
+
+        assert!(is_idle(None, last_activity, timeout));
+        assert!(!is_idle(Some(last_activity), last_activity, timeout));
+        assert!(!is_idle(
+            Some(last_activity),
+            last_activity + Duration::from_secs(59),
+            timeout
+        ));
+        assert!(is_idle(
+            Some(last_activity),
+            last_activity + timeout,
+            timeout
+        ));
+        assert!(is_idle(Some(last_activity), last_activity, Duration::ZERO));
+    }
+}
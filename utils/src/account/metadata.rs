@@ -0,0 +1,242 @@
+use std::{collections::HashMap, fmt};
+
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::disk_storage::{DiskStorageInterface, FileFormat};
+
+/// Details about the accounts which are not secret, the order of the entries is
+/// the order in which accounts are listed.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct AccountMetadataStore {
+    #[serde(default)]
+    accounts: Vec<AccountMetadata>,
+}
+
+impl DiskStorageInterface for AccountMetadataStore {
+    const FILE_NAME: &'static str = "accounts";
+    const FORMAT: FileFormat = FileFormat::YAML;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AccountMetadata {
+    pub address: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Accounts added by address only, there is no secret stored for them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub watch_only: bool,
+    /// Name of the network of Safe accounts, their owners sign for them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe_network: Option<String>,
+    /// Whether the stored secret is a mnemonic, `None` for accounts stored
+    /// before this was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<bool>,
+}
+
+impl AccountMetadata {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            label: None,
+            watch_only: false,
+            safe_network: None,
+            mnemonic: None,
+        }
+    }
+}
+
+/// Address along with the label if any, formatted as `label (address)`.
+pub struct LabeledAddress<'a> {
+    pub address: &'a Address,
+    pub label: Option<&'a String>,
+}
+
+impl fmt::Display for LabeledAddress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.label {
+            Some(label) => write!(f, "{label} ({})", self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+impl AccountMetadataStore {
+    fn find_mut(&mut self, address: &Address) -> Option<&mut AccountMetadata> {
+        self.accounts
+            .iter_mut()
+            .find(|account| &account.address == address)
+    }
+
+    fn find_or_insert(&mut self, address: &Address) -> &mut AccountMetadata {
+        let index = match self
+            .accounts
+            .iter()
+            .position(|account| &account.address == address)
+        {
+            Some(index) => index,
+            None => {
+                self.accounts.push(AccountMetadata::new(*address));
+                self.accounts.len() - 1
+            }
+        };
+        &mut self.accounts[index]
+    }
+
+    pub fn label(&self, address: &Address) -> Option<&String> {
+        self.accounts
+            .iter()
+            .find(|account| &account.address == address)
+            .and_then(|account| account.label.as_ref())
+    }
+
+    pub fn is_watch_only(&self, address: &Address) -> bool {
+        self.accounts
+            .iter()
+            .any(|account| &account.address == address && account.watch_only)
+    }
+
+    pub fn watch_only_accounts(&self) -> Vec<Address> {
+        self.accounts
+            .iter()
+            .filter(|account| account.watch_only)
+            .map(|account| account.address)
+            .collect()
+    }
+
+    pub fn safe_network(&self, address: &Address) -> Option<&String> {
+        self.accounts
+            .iter()
+            .find(|account| &account.address == address)
+            .and_then(|account| account.safe_network.as_ref())
+    }
+
+    pub fn safe_accounts(&self) -> Vec<Address> {
+        self.accounts
+            .iter()
+            .filter(|account| account.safe_network.is_some())
+            .map(|account| account.address)
+            .collect()
+    }
+
+    pub fn add_safe(&mut self, address: &Address, network: &str) -> crate::Result<()> {
+        self.find_or_insert(address).safe_network = Some(network.to_string());
+        self.save()
+    }
+
+    pub fn add_watch_only(&mut self, address: &Address) -> crate::Result<()> {
+        self.find_or_insert(address).watch_only = true;
+        self.save()
+    }
+
+    pub fn is_mnemonic(&self, address: &Address) -> Option<bool> {
+        self.accounts
+            .iter()
+            .find(|account| &account.address == address)
+            .and_then(|account| account.mnemonic)
+    }
+
+    /// Records the kind of the secret stored for the address, a watch-only
+    /// account of the address can sign from now on.
+    pub fn set_secret_stored(&mut self, address: &Address, mnemonic: bool) -> crate::Result<()> {
+        let account = self.find_or_insert(address);
+        account.watch_only = false;
+        account.mnemonic = Some(mnemonic);
+        self.save()
+    }
+
+    /// Map of all the labels, useful for rendering many addresses.
+    pub fn labels(&self) -> HashMap<Address, String> {
+        self.accounts
+            .iter()
+            .filter_map(|account| Some((account.address, account.label.clone()?)))
+            .collect()
+    }
+
+    /// Sets the label, an empty label removes it.
+    pub fn set_label(&mut self, address: &Address, label: &str) -> crate::Result<()> {
+        let label = label.trim();
+        self.find_or_insert(address).label = if label.is_empty() {
+            None
+        } else {
+            Some(label.to_string())
+        };
+        self.save()
+    }
+
+    pub fn remove(&mut self, address: &Address) -> crate::Result<()> {
+        self.accounts.retain(|account| &account.address != address);
+        self.save()
+    }
+
+    /// Orders the addresses as stored, addresses not known yet are kept at the
+    /// end in the order they were given.
+    pub fn sort(&self, addresses: Vec<Address>) -> Vec<Address> {
+        let mut sorted: Vec<Address> = self
+            .accounts
+            .iter()
+            .map(|account| account.address)
+            .filter(|address| addresses.contains(address))
+            .collect();
+        for address in addresses {
+            if !sorted.contains(&address) {
+                sorted.push(address);
+            }
+        }
+        sorted
+    }
+
+    /// Moves the account one position up (towards the start) or down in the
+    /// given list and persists the resulting order.
+    pub fn reorder(&mut self, list: &[Address], address: &Address, up: bool) -> crate::Result<()> {
+        let mut list = self.sort(list.to_vec());
+        let Some(index) = list.iter().position(|a| a == address) else {
+            return Ok(());
+        };
+        let other = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1).filter(|i| *i < list.len())
+        };
+        if let Some(other) = other {
+            list.swap(index, other);
+        }
+
+        let mut accounts = vec![];
+        for address in list {
+            let metadata = self
+                .find_mut(&address)
+                .cloned()
+                .unwrap_or_else(|| AccountMetadata::new(address));
+            accounts.push(metadata);
+        }
+        // Keep the metadata of accounts which were not in the list
+        for account in &self.accounts {
+            if !accounts.iter().any(|a| a.address == account.address) {
+                accounts.push(account.clone());
+            }
+        }
+        self.accounts = accounts;
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_keeps_unknown_accounts_at_the_end() {
+        let [a, b, c] = [
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+        ];
+        let store = AccountMetadataStore {
+            accounts: vec![AccountMetadata::new(c), AccountMetadata::new(a)],
+        };
+        assert_eq!(store.sort(vec![a, b, c]), vec![c, a, b]);
+        assert_eq!(store.sort(vec![b]), vec![b]);
+    }
+}
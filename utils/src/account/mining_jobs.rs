@@ -0,0 +1,345 @@
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+
+use alloy::{
+    primitives::{Address, B256},
+    signers::k256::FieldBytes,
+};
+use gm_common::secret::Secret;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::{
+    vanity::{VanityFound, VanityMode, VanityPattern},
+    AccountManager, AccountUtils,
+};
+use crate::disk_storage::{DiskStorageInterface, FileFormat};
+
+/// Vanity mining jobs which run in the background, the progress is saved so
+/// that a job continues where it stopped on the next launch.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct MiningJobStore {
+    #[serde(default)]
+    jobs: Vec<MiningJob>,
+    /// Keys found while the key store was locked, they are stored as accounts
+    /// on the next unlock. Until then they are kept in plaintext like the
+    /// entries of the insecure store.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pending_keys: Vec<PendingKey>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct PendingKey {
+    job_id: u32,
+    address: Address,
+    key: Secret,
+}
+
+impl DiskStorageInterface for MiningJobStore {
+    const FILE_NAME: &'static str = "mining_jobs";
+    const FORMAT: FileFormat = FileFormat::YAML;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MiningJob {
+    pub id: u32,
+    pub mode: VanityMode,
+    /// Hex nibble at each index of the address, `.` for any nibble.
+    pub nibbles: String,
+    /// Case of the letter at each index of the checksummed address, `U` for
+    /// upper case, `L` for lower case and `.` for any case.
+    pub checksum_case: String,
+    #[serde(default)]
+    pub attempts: u64,
+    #[serde(default)]
+    pub elapsed_secs: f64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<MiningJobResult>,
+    /// Account of the key found while the key store was locked, it is added
+    /// once the store is unlocked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_account: Option<Address>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "type")]
+pub enum MiningJobResult {
+    /// Key was stored as a new account.
+    Account {
+        address: Address,
+    },
+    Salt {
+        salt: B256,
+        contract: Address,
+    },
+}
+
+impl MiningJob {
+    pub fn new(mode: VanityMode, nibbles: &[Option<u8>; 40], case: &[Option<bool>; 40]) -> Self {
+        Self {
+            id: 0,
+            mode,
+            nibbles: nibbles
+                .iter()
+                .map(|nibble| match nibble {
+                    Some(n) => char::from_digit(*n as u32, 16).unwrap_or('.'),
+                    None => '.',
+                })
+                .collect(),
+            checksum_case: case
+                .iter()
+                .map(|case| match case {
+                    Some(true) => 'U',
+                    Some(false) => 'L',
+                    None => '.',
+                })
+                .collect(),
+            attempts: 0,
+            elapsed_secs: 0.0,
+            paused: false,
+            result: None,
+            pending_account: None,
+        }
+    }
+
+    pub fn nibbles(&self) -> [Option<u8>; 40] {
+        let mut nibbles = [None; 40];
+        for (nibble, c) in nibbles.iter_mut().zip(self.nibbles.chars()) {
+            *nibble = c.to_digit(16).map(|n| n as u8);
+        }
+        nibbles
+    }
+
+    pub fn checksum_case(&self) -> [Option<bool>; 40] {
+        let mut case = [None; 40];
+        for (case, c) in case.iter_mut().zip(self.checksum_case.chars()) {
+            *case = match c {
+                'U' => Some(true),
+                'L' => Some(false),
+                _ => None,
+            };
+        }
+        case
+    }
+
+    pub fn pattern(&self) -> VanityPattern {
+        VanityPattern::new(&self.nibbles(), &self.checksum_case())
+    }
+
+    /// Pattern as it would appear in the checksummed address.
+    pub fn display_pattern(&self) -> String {
+        self.nibbles
+            .chars()
+            .zip(self.checksum_case.chars())
+            .map(|(n, case)| {
+                if case == 'U' {
+                    n.to_ascii_uppercase()
+                } else {
+                    n
+                }
+            })
+            .collect()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.result.is_some() || self.pending_account.is_some()
+    }
+
+    /// Keys are stored once found, so these jobs only run while the key store
+    /// is unlocked.
+    pub fn needs_key_store(&self) -> bool {
+        !matches!(self.mode, VanityMode::Create2 { .. })
+    }
+
+    pub fn hash_rate(&self) -> Option<f64> {
+        (self.elapsed_secs > 0.0).then(|| self.attempts as f64 / self.elapsed_secs)
+    }
+
+    /// Chance that a match would have been found with the attempts made so far.
+    pub fn chance(&self) -> f64 {
+        let p = self.pattern().probability();
+        -(self.attempts as f64 * (-p).ln_1p()).exp_m1()
+    }
+
+    /// Mines for at most `max_dur`, the caller should record the progress.
+    pub fn mine_chunk(
+        &self,
+        max_dur: Duration,
+        exit_signal: Arc<AtomicBool>,
+    ) -> crate::Result<(Option<VanityFound>, u64, Duration)> {
+        self.mode.mine(self.pattern(), Some(max_dur), exit_signal)
+    }
+
+    /// Stores the mined key as an account, salts only need the contract
+    /// address computed. A key found while the key store is locked is kept
+    /// in `store` until the next unlock, `None` is returned for it.
+    pub fn complete(
+        &self,
+        found: &VanityFound,
+        store: &mut MiningJobStore,
+    ) -> crate::Result<Option<MiningJobResult>> {
+        Ok(Some(match found {
+            VanityFound::Key(key) => {
+                let address = Address::from_private_key(key);
+                let key = Zeroizing::new(key.to_bytes());
+                if !AccountManager::is_unlocked() {
+                    store.add_pending_key(self.id, address, &key)?;
+                    return Ok(None);
+                }
+                AccountManager::store_private_key(&key, address)?;
+                MiningJobResult::Account { address }
+            }
+            VanityFound::Salt(salt) => {
+                let contract = match self.mode {
+                    VanityMode::Create2 {
+                        deployer,
+                        init_code_hash,
+                    } => deployer.create2(salt, init_code_hash),
+                    _ => Address::ZERO,
+                };
+                MiningJobResult::Salt {
+                    salt: *salt,
+                    contract,
+                }
+            }
+        }))
+    }
+}
+
+impl MiningJobStore {
+    pub fn jobs(&self) -> &[MiningJob] {
+        &self.jobs
+    }
+
+    pub fn get(&self, id: u32) -> Option<&MiningJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    fn find_mut(&mut self, id: u32) -> Option<&mut MiningJob> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+
+    /// Adds the job to the end of the queue and returns it with its id.
+    pub fn add(&mut self, mut job: MiningJob) -> crate::Result<MiningJob> {
+        job.id = self.jobs.iter().map(|job| job.id + 1).max().unwrap_or(1);
+        self.jobs.push(job.clone());
+        self.save()?;
+        Ok(job)
+    }
+
+    pub fn remove(&mut self, id: u32) -> crate::Result<()> {
+        self.jobs.retain(|job| job.id != id);
+        self.save()
+    }
+
+    pub fn set_paused(&mut self, id: u32, paused: bool) -> crate::Result<()> {
+        if let Some(job) = self.find_mut(id) {
+            job.paused = paused;
+        }
+        self.save()
+    }
+
+    fn add_pending_key(
+        &mut self,
+        id: u32,
+        address: Address,
+        key: &FieldBytes,
+    ) -> crate::Result<()> {
+        if let Some(job) = self.find_mut(id) {
+            job.pending_account = Some(address);
+        }
+        self.pending_keys.push(PendingKey {
+            job_id: id,
+            address,
+            key: Secret::from_private_key(key),
+        });
+        self.save()
+    }
+
+    pub fn has_pending_keys(&self) -> bool {
+        !self.pending_keys.is_empty()
+    }
+
+    /// Stores the keys found while the key store was locked as accounts,
+    /// returns the jobs which are done now. The store has to be unlocked.
+    pub fn store_pending_keys(&mut self) -> crate::Result<Vec<MiningJob>> {
+        let mut done = vec![];
+        while let Some(pending) = self.pending_keys.first() {
+            let Secret::PrivateKey(key) = &pending.key else {
+                self.pending_keys.remove(0);
+                continue;
+            };
+            let (id, address) = (pending.job_id, pending.address);
+            AccountManager::store_private_key(FieldBytes::from_slice(key.expose()), address)?;
+            self.pending_keys.remove(0);
+            // The key is kept even if the job was removed in the meantime
+            if let Some(job) = self.find_mut(id) {
+                job.pending_account = None;
+                job.result = Some(MiningJobResult::Account { address });
+                done.push(job.clone());
+            }
+            self.save()?;
+        }
+        Ok(done)
+    }
+
+    /// First job which is neither done nor paused, jobs are mined one at a
+    /// time since each of them uses all the cores.
+    pub fn next_job(&self, key_store_unlocked: bool) -> Option<&MiningJob> {
+        self.jobs.iter().find(|job| {
+            !job.is_done() && !job.paused && (key_store_unlocked || !job.needs_key_store())
+        })
+    }
+
+    /// Adds the progress of a chunk, the job may have been paused or removed
+    /// in the meantime so only the progress is written.
+    pub fn record_progress(
+        &mut self,
+        id: u32,
+        attempts: u64,
+        elapsed: Duration,
+        result: Option<MiningJobResult>,
+    ) -> crate::Result<Option<MiningJob>> {
+        let Some(job) = self.find_mut(id) else {
+            return Ok(None);
+        };
+        job.attempts += attempts;
+        job.elapsed_secs += elapsed.as_secs_f64();
+        if result.is_some() {
+            job.result = result;
+        }
+        let job = job.clone();
+        self.save()?;
+        Ok(Some(job))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_round_trip() {
+        let mut nibbles = [None; 40];
+        nibbles[0] = Some(0xd);
+        nibbles[1] = Some(0xe);
+        nibbles[39] = Some(0x7);
+        let mut case = [None; 40];
+        case[0] = Some(true);
+        case[1] = Some(false);
+
+        let job = MiningJob::new(VanityMode::Deployer, &nibbles, &case);
+        assert_eq!(job.nibbles, format!("de{}7", ".".repeat(37)));
+        assert_eq!(job.display_pattern(), format!("De{}7", ".".repeat(37)));
+        assert_eq!(job.nibbles(), nibbles);
+        assert_eq!(job.checksum_case(), case);
+        assert_eq!(job.pattern(), VanityPattern::new(&nibbles, &case));
+
+        let yaml = serde_yaml::to_string(&job).unwrap();
+        assert_eq!(serde_yaml::from_str::<MiningJob>(&yaml).unwrap(), job);
+    }
+}
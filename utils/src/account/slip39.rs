@@ -0,0 +1,557 @@
+//! SLIP-39 Shamir backup, a secret is split into mnemonic shares and any
+//! threshold number of them recover it.
+//! Spec: https://github.com/satoshilabs/slips/blob/master/slip-0039.md
+
+use std::collections::BTreeMap;
+
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, RngCore};
+use sha2::Sha256;
+
+const WORDLIST: &str = include_str!("slip39_wordlist.txt");
+
+const RADIX_BITS: usize = 10;
+const METADATA_LENGTH_WORDS: usize = 7;
+const CHECKSUM_LENGTH_WORDS: usize = 3;
+const MIN_STRENGTH_BYTES: usize = 16;
+const DIGEST_LENGTH_BYTES: usize = 4;
+const MAX_SHARE_COUNT: u8 = 16;
+const BASE_ITERATION_COUNT: u32 = 10000;
+const ROUND_COUNT: u8 = 4;
+/// Exponent used for new shares, each step doubles the PBKDF2 iterations.
+const ITERATION_EXPONENT: u8 = 1;
+const SECRET_INDEX: u8 = 255;
+const DIGEST_INDEX: u8 = 254;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Slip39Error {
+    #[error("'{0}' is not a SLIP-39 word.")]
+    UnknownWord(String),
+
+    #[error("Share has an invalid number of words.")]
+    InvalidLength,
+
+    #[error("Share checksum is invalid, please check the words.")]
+    InvalidChecksum,
+
+    #[error("Share has invalid padding.")]
+    InvalidPadding,
+
+    #[error("Share has a group threshold larger than the group count.")]
+    InvalidGroupThreshold,
+
+    #[error("Threshold must be between 1 and the share count, and at most 16 shares are allowed.")]
+    InvalidThreshold,
+
+    #[error("A threshold of 1 is only allowed with a single share.")]
+    SingleShareThreshold,
+
+    #[error("Secret must be at least 128 bits long and a multiple of 16 bits.")]
+    InvalidSecretLength,
+
+    #[error("Passphrase may only contain printable ASCII characters.")]
+    InvalidPassphrase,
+
+    #[error("Shares belong to different backups.")]
+    MismatchedShares,
+
+    #[error("The same share was entered more than once.")]
+    DuplicateShare,
+
+    #[error("Not enough shares to recover the secret.")]
+    InsufficientShares,
+
+    #[error("Shares could not be combined, the digest does not match.")]
+    InvalidDigest,
+}
+
+type Result<T> = std::result::Result<T, Slip39Error>;
+
+/// A single share as encoded in the mnemonic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Share {
+    pub identifier: u16,
+    pub extendable: bool,
+    pub iteration_exponent: u8,
+    pub group_index: u8,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub member_index: u8,
+    pub member_threshold: u8,
+    pub value: Vec<u8>,
+}
+
+impl Share {
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self> {
+        let words = mnemonic
+            .split_whitespace()
+            .map(word_index)
+            .collect::<Result<Vec<_>>>()?;
+        if words.len() < METADATA_LENGTH_WORDS + MIN_STRENGTH_BYTES * 8 / RADIX_BITS + 1 {
+            return Err(Slip39Error::InvalidLength);
+        }
+
+        let id_exp = (words[0] as u32) << RADIX_BITS | words[1] as u32;
+        let extendable = (id_exp >> 4) & 1 == 1;
+        if rs1024_polymod(customization(extendable), &words) != 1 {
+            return Err(Slip39Error::InvalidChecksum);
+        }
+
+        let params = (words[2] as u32) << RADIX_BITS | words[3] as u32;
+        let nibble = |i: u32| ((params >> (4 * i)) & 0xf) as u8;
+        let share = Self {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xf) as u8,
+            group_index: nibble(4),
+            group_threshold: nibble(3) + 1,
+            group_count: nibble(2) + 1,
+            member_index: nibble(1),
+            member_threshold: nibble(0) + 1,
+            value: words_to_bytes(&words[4..words.len() - CHECKSUM_LENGTH_WORDS])?,
+        };
+        if share.group_threshold > share.group_count {
+            return Err(Slip39Error::InvalidGroupThreshold);
+        }
+        Ok(share)
+    }
+
+    pub fn to_mnemonic(&self) -> String {
+        let id_exp = (self.identifier as u32) << 5
+            | (self.extendable as u32) << 4
+            | self.iteration_exponent as u32;
+        let params = (self.group_index as u32) << 16
+            | (self.group_threshold as u32 - 1) << 12
+            | (self.group_count as u32 - 1) << 8
+            | (self.member_index as u32) << 4
+            | (self.member_threshold as u32 - 1);
+
+        let mut words = vec![
+            (id_exp >> RADIX_BITS) as u16,
+            (id_exp & 0x3ff) as u16,
+            (params >> RADIX_BITS) as u16,
+            (params & 0x3ff) as u16,
+        ];
+        words.extend(bytes_to_words(&self.value));
+
+        let checksum = rs1024_polymod(
+            customization(self.extendable),
+            &[words.as_slice(), &[0; CHECKSUM_LENGTH_WORDS]].concat(),
+        ) ^ 1;
+        words.extend(
+            (0..CHECKSUM_LENGTH_WORDS)
+                .rev()
+                .map(|i| ((checksum >> (RADIX_BITS * i)) & 0x3ff) as u16),
+        );
+
+        let wordlist: Vec<&str> = WORDLIST.lines().collect();
+        words
+            .iter()
+            .map(|&index| wordlist[index as usize])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Splits the secret into `share_count` mnemonics using a single group, any
+/// `threshold` of them recover the secret along with the passphrase.
+pub fn generate_mnemonics(
+    threshold: u8,
+    share_count: u8,
+    secret: &[u8],
+    passphrase: &str,
+) -> Result<Vec<String>> {
+    if secret.len() < MIN_STRENGTH_BYTES || !secret.len().is_multiple_of(2) {
+        return Err(Slip39Error::InvalidSecretLength);
+    }
+    if threshold == 0 || threshold > share_count || share_count > MAX_SHARE_COUNT {
+        return Err(Slip39Error::InvalidThreshold);
+    }
+    if threshold == 1 && share_count > 1 {
+        return Err(Slip39Error::SingleShareThreshold);
+    }
+    let passphrase = check_passphrase(passphrase)?;
+
+    let identifier = (OsRng.next_u32() & 0x7fff) as u16;
+    let extendable = false;
+    let encrypted = feistel(
+        secret,
+        passphrase,
+        ITERATION_EXPONENT,
+        identifier,
+        extendable,
+        true,
+    );
+
+    Ok(split_secret(threshold, share_count, &encrypted)
+        .into_iter()
+        .map(|(member_index, value)| {
+            Share {
+                identifier,
+                extendable,
+                iteration_exponent: ITERATION_EXPONENT,
+                group_index: 0,
+                group_threshold: 1,
+                group_count: 1,
+                member_index,
+                member_threshold: threshold,
+                value,
+            }
+            .to_mnemonic()
+        })
+        .collect())
+}
+
+/// Recovers the secret from the mnemonics, shares of multiple groups are
+/// supported as long as enough groups are complete.
+pub fn combine_mnemonics(mnemonics: &[String], passphrase: &str) -> Result<Vec<u8>> {
+    let passphrase = check_passphrase(passphrase)?;
+    let shares = mnemonics
+        .iter()
+        .map(|mnemonic| Share::from_mnemonic(mnemonic))
+        .collect::<Result<Vec<_>>>()?;
+    let Some(first) = shares.first() else {
+        return Err(Slip39Error::InsufficientShares);
+    };
+
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+    for share in &shares {
+        if share.identifier != first.identifier
+            || share.extendable != first.extendable
+            || share.iteration_exponent != first.iteration_exponent
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.value.len() != first.value.len()
+        {
+            return Err(Slip39Error::MismatchedShares);
+        }
+
+        let group = groups.entry(share.group_index).or_default();
+        if group
+            .iter()
+            .any(|other| other.member_threshold != share.member_threshold)
+        {
+            return Err(Slip39Error::MismatchedShares);
+        }
+        if group
+            .iter()
+            .any(|other| other.member_index == share.member_index)
+        {
+            return Err(Slip39Error::DuplicateShare);
+        }
+        group.push(share);
+    }
+
+    let group_secrets = groups
+        .into_iter()
+        .filter(|(_, members)| members.len() >= members[0].member_threshold as usize)
+        .take(first.group_threshold as usize)
+        .map(|(group_index, members)| {
+            let threshold = members[0].member_threshold;
+            let members = members
+                .iter()
+                .take(threshold as usize)
+                .map(|share| (share.member_index, share.value.clone()))
+                .collect::<Vec<_>>();
+            Ok((group_index, recover_secret(threshold, &members)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if group_secrets.len() < first.group_threshold as usize {
+        return Err(Slip39Error::InsufficientShares);
+    }
+
+    let encrypted = recover_secret(first.group_threshold, &group_secrets)?;
+    Ok(feistel(
+        &encrypted,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+        false,
+    ))
+}
+
+fn check_passphrase(passphrase: &str) -> Result<&[u8]> {
+    if passphrase.bytes().all(|b| (32..=126).contains(&b)) {
+        Ok(passphrase.as_bytes())
+    } else {
+        Err(Slip39Error::InvalidPassphrase)
+    }
+}
+
+fn word_index(word: &str) -> Result<u16> {
+    let word = word.to_lowercase();
+    WORDLIST
+        .lines()
+        .position(|w| w == word)
+        .map(|index| index as u16)
+        .ok_or(Slip39Error::UnknownWord(word))
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        b"shamir_extendable"
+    } else {
+        b"shamir"
+    }
+}
+
+fn rs1024_polymod(customization: &[u8], words: &[u16]) -> u32 {
+    const GEN: [u32; 10] = [
+        0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+        0x21b1f890, 0x3f3f120,
+    ];
+    let values = customization
+        .iter()
+        .map(|&b| b as u32)
+        .chain(words.iter().map(|&w| w as u32));
+
+    let mut chk = 1;
+    for value in values {
+        let b = chk >> 20;
+        chk = (chk & 0xfffff) << 10 ^ value;
+        for (i, gen) in GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+/// Words of the share value, padded with leading zero bits.
+fn bytes_to_words(bytes: &[u8]) -> Vec<u16> {
+    let padding = (RADIX_BITS - bytes.len() * 8 % RADIX_BITS) % RADIX_BITS;
+    let bits = std::iter::repeat_n(false, padding)
+        .chain(
+            bytes
+                .iter()
+                .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1)),
+        )
+        .collect::<Vec<_>>();
+    bits.chunks(RADIX_BITS)
+        .map(|chunk| chunk.iter().fold(0, |word, &bit| word << 1 | bit as u16))
+        .collect()
+}
+
+fn words_to_bytes(words: &[u16]) -> Result<Vec<u8>> {
+    let padding = words.len() * RADIX_BITS % 16;
+    if padding > 8 {
+        return Err(Slip39Error::InvalidLength);
+    }
+    let bits = words
+        .iter()
+        .flat_map(|w| (0..RADIX_BITS).rev().map(move |i| (w >> i) & 1 == 1))
+        .collect::<Vec<_>>();
+    if bits[..padding].iter().any(|&bit| bit) {
+        return Err(Slip39Error::InvalidPadding);
+    }
+    Ok(bits[padding..]
+        .chunks(8)
+        .map(|chunk| chunk.iter().fold(0, |byte, &bit| byte << 1 | bit as u8))
+        .collect())
+}
+
+/// Four round Feistel network keyed with PBKDF2 of the passphrase, the same
+/// rounds in reverse order decrypt.
+fn feistel(
+    secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    encrypt: bool,
+) -> Vec<u8> {
+    let mut salt = vec![];
+    if !extendable {
+        salt.extend(customization(false));
+        salt.extend(identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+
+    let (left, right) = secret.split_at(secret.len() / 2);
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    for round in 0..ROUND_COUNT {
+        let i = if encrypt {
+            round
+        } else {
+            ROUND_COUNT - 1 - round
+        };
+        let mut password = vec![i];
+        password.extend(passphrase);
+        let mut f = vec![0; right.len()];
+        pbkdf2::pbkdf2_hmac::<Sha256>(
+            &password,
+            &[&salt, right.as_slice()].concat(),
+            iterations,
+            &mut f,
+        );
+
+        let next = left.iter().zip(&f).map(|(l, f)| l ^ f).collect();
+        left = std::mem::replace(&mut right, next);
+    }
+    [right, left].concat()
+}
+
+/// Exponent and logarithm tables of GF(256) with the Rijndael polynomial.
+const GF_TABLES: ([u8; 255], [u8; 256]) = {
+    let mut exp = [0; 255];
+    let mut log = [0; 256];
+    let mut poly: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = poly as u8;
+        log[poly as usize] = i as u8;
+        poly ^= poly << 1;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11b;
+        }
+        i += 1;
+    }
+    (exp, log)
+};
+
+/// Value at `x` of the polynomials going through the shares, one per byte.
+fn interpolate(shares: &[(u8, Vec<u8>)], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = shares.iter().find(|(share_x, _)| *share_x == x) {
+        return value.clone();
+    }
+    let (exp, log) = GF_TABLES;
+    let log_prod: usize = shares
+        .iter()
+        .map(|(sx, _)| log[(sx ^ x) as usize] as usize)
+        .sum();
+
+    let mut result = vec![0; shares[0].1.len()];
+    for (share_x, value) in shares {
+        let log_denominator: usize = shares
+            .iter()
+            .map(|(other_x, _)| log[(share_x ^ other_x) as usize] as usize)
+            .sum();
+        let log_basis = (log_prod + 255 * shares.len() * 2
+            - log[(share_x ^ x) as usize] as usize
+            - log_denominator)
+            % 255;
+        for (r, &v) in result.iter_mut().zip(value) {
+            if v != 0 {
+                *r ^= exp[(log[v as usize] as usize + log_basis) % 255];
+            }
+        }
+    }
+    result
+}
+
+fn create_digest(random: &[u8], secret: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(random).expect("HMAC accepts any key length");
+    mac.update(secret);
+    mac.finalize().into_bytes()[..DIGEST_LENGTH_BYTES].to_vec()
+}
+
+fn split_secret(threshold: u8, share_count: u8, secret: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    if threshold == 1 {
+        return (0..share_count).map(|i| (i, secret.to_vec())).collect();
+    }
+
+    let random_bytes = |len: usize| {
+        let mut bytes = vec![0; len];
+        OsRng.fill_bytes(&mut bytes);
+        bytes
+    };
+    let random_share_count = threshold - 2;
+    let mut shares: Vec<(u8, Vec<u8>)> = (0..random_share_count)
+        .map(|i| (i, random_bytes(secret.len())))
+        .collect();
+
+    // The digest lets the recovery detect wrong shares
+    let random = random_bytes(secret.len() - DIGEST_LENGTH_BYTES);
+    let mut base = shares.clone();
+    base.push((
+        DIGEST_INDEX,
+        [create_digest(&random, secret), random].concat(),
+    ));
+    base.push((SECRET_INDEX, secret.to_vec()));
+
+    for i in random_share_count..share_count {
+        shares.push((i, interpolate(&base, i)));
+    }
+    shares
+}
+
+fn recover_secret(threshold: u8, shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>> {
+    if threshold == 1 {
+        return Ok(shares[0].1.clone());
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX);
+    let digest_share = interpolate(shares, DIGEST_INDEX);
+    let (digest, random) = digest_share.split_at(DIGEST_LENGTH_BYTES);
+    if digest != create_digest(random, &secret) {
+        return Err(Slip39Error::InvalidDigest);
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::hex;
+
+    #[test]
+    fn test_wordlist() {
+        let words: Vec<&str> = WORDLIST.lines().collect();
+        assert_eq!(words.len(), 1 << RADIX_BITS);
+        assert!(words.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_vectors() {
+        // From the SLIP-39 test vectors
+        let mnemonics = ["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard".to_string()];
+        assert_eq!(
+            hex::encode(combine_mnemonics(&mnemonics, "TREZOR").unwrap()),
+            "bb54aac4b89dc868ba37d9cc21b2cece"
+        );
+
+        let mnemonics = ["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney".to_string()];
+        assert!(matches!(
+            combine_mnemonics(&mnemonics, "TREZOR"),
+            Err(Slip39Error::InvalidChecksum)
+        ));
+
+        let mnemonics = [
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed".to_string(),
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking".to_string(),
+        ];
+        assert_eq!(
+            hex::encode(combine_mnemonics(&mnemonics, "TREZOR").unwrap()),
+            "b43ceb7e57a0ea8766221624d01b0864"
+        );
+        assert!(matches!(
+            combine_mnemonics(&mnemonics[..1], "TREZOR"),
+            Err(Slip39Error::InsufficientShares)
+        ));
+    }
+
+    #[test]
+    fn test_split_and_combine() {
+        let secret =
+            hex::decode("0c94f2c1d5f1ae1b7ff5d5d1b4cf7dd6a0ed0a8e0b3c4a7dbe7df1a1a2b3c4d5")
+                .unwrap();
+        let mnemonics = generate_mnemonics(3, 5, &secret, "pass").unwrap();
+        assert_eq!(mnemonics.len(), 5);
+        assert_eq!(mnemonics[0].split(' ').count(), 33);
+
+        let subset = [
+            mnemonics[4].clone(),
+            mnemonics[0].clone(),
+            mnemonics[2].clone(),
+        ];
+        assert_eq!(combine_mnemonics(&subset, "pass").unwrap(), secret);
+        // A wrong passphrase gives a different secret instead of an error
+        assert_ne!(combine_mnemonics(&subset, "").unwrap(), secret);
+        assert!(matches!(
+            combine_mnemonics(&subset[..2], "pass"),
+            Err(Slip39Error::InsufficientShares)
+        ));
+    }
+}
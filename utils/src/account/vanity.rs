@@ -0,0 +1,229 @@
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+
+use alloy::{
+    hex,
+    primitives::{keccak256, Address, B256},
+    signers::k256::ecdsa::SigningKey,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{mine_create2_salt, mine_deployer, mine_wallet};
+
+/// What the pattern is matched against.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum VanityMode {
+    /// Address of the wallet itself.
+    Wallet,
+    /// Address of the first contract deployed by the wallet (`CREATE` at nonce 0).
+    Deployer,
+    /// Address of a `CREATE2` deployment, only the salt is mined.
+    Create2 {
+        deployer: Address,
+        init_code_hash: B256,
+    },
+}
+
+impl VanityMode {
+    pub fn mine(
+        self,
+        pattern: VanityPattern,
+        max_dur: Option<Duration>,
+        exit_signal: Arc<AtomicBool>,
+    ) -> crate::Result<(Option<VanityFound>, u64, Duration)> {
+        Ok(match self {
+            VanityMode::Wallet => {
+                let (key, counter, duration) = mine_wallet(pattern, max_dur, exit_signal)?;
+                (key.map(VanityFound::Key), counter, duration)
+            }
+            VanityMode::Deployer => {
+                let (key, counter, duration) = mine_deployer(pattern, max_dur, exit_signal)?;
+                (key.map(VanityFound::Key), counter, duration)
+            }
+            VanityMode::Create2 {
+                deployer,
+                init_code_hash,
+            } => {
+                let (salt, counter, duration) =
+                    mine_create2_salt(deployer, init_code_hash, pattern, max_dur, exit_signal)?;
+                (salt.map(VanityFound::Salt), counter, duration)
+            }
+        })
+    }
+}
+
+/// Match found by [`VanityMode::mine`].
+pub enum VanityFound {
+    Key(SigningKey),
+    Salt(B256),
+}
+
+/// What a vanity address has to look like: the hex nibbles it must have and the
+/// letters whose case is fixed in the EIP-55 checksummed address.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VanityPattern {
+    /// Bits which must be one.
+    mask_a: [u8; 20],
+    /// Bits which must be zero.
+    mask_b: [u8; 20],
+    /// Case of the letter at each hex index, `Some(true)` for upper case.
+    checksum_case: [Option<bool>; 40],
+    has_checksum_case: bool,
+}
+
+impl VanityPattern {
+    /// Pattern from the nibble at each hex index, cases are only kept for the
+    /// letters since digits do not have a case.
+    pub fn new(nibbles: &[Option<u8>; 40], checksum_case: &[Option<bool>; 40]) -> Self {
+        let mut mask_a = [0; 20];
+        let mut mask_b = [0; 20];
+        let mut case = [None; 40];
+
+        for (i, &nibble) in nibbles.iter().enumerate() {
+            if let Some(n) = nibble {
+                mask_a[i / 2] |= n << ((1 - i % 2) * 4);
+                mask_b[i / 2] |= (0xf ^ n) << ((1 - i % 2) * 4);
+                if n >= 10 {
+                    case[i] = checksum_case[i];
+                }
+            }
+        }
+
+        Self {
+            mask_a,
+            mask_b,
+            checksum_case: case,
+            has_checksum_case: case.iter().any(Option::is_some),
+        }
+    }
+
+    /// Pattern which only constrains bits, `mask_a` has the bits which must be
+    /// one and `mask_b` the bits which must be zero.
+    pub fn from_masks(mask_a: Address, mask_b: Address) -> Self {
+        Self {
+            mask_a: mask_a.into(),
+            mask_b: mask_b.into(),
+            checksum_case: [None; 40],
+            has_checksum_case: false,
+        }
+    }
+
+    /// Pattern which can never match, useful for measuring the hash rate.
+    pub fn unmatchable() -> Self {
+        Self::from_masks(Address::ZERO, Address::repeat_byte(0xff))
+    }
+
+    #[inline(always)]
+    pub fn is_match(&self, addr: &[u8; 20]) -> bool {
+        for ((byte, mask_a), mask_b) in addr.iter().zip(&self.mask_a).zip(&self.mask_b) {
+            if (byte & mask_a) != *mask_a {
+                return false;
+            }
+            if ((!byte) & mask_b) != *mask_b {
+                return false;
+            }
+        }
+
+        // Checksum needs another hash, so it is checked only after the nibbles
+        !self.has_checksum_case || self.is_checksum_case_match(addr)
+    }
+
+    fn is_checksum_case_match(&self, addr: &[u8; 20]) -> bool {
+        let hash = keccak256(hex::encode(addr).as_bytes());
+        self.checksum_case.iter().enumerate().all(|(i, case)| {
+            let Some(upper) = case else {
+                return true;
+            };
+            let nibble = (hash[i / 2] >> ((1 - i % 2) * 4)) & 0xf;
+            (nibble >= 8) == *upper
+        })
+    }
+
+    /// Number of bits fixed by the pattern, a case counts as one bit since half
+    /// of the letters are upper case in a checksummed address.
+    pub fn fixed_bits(&self) -> u32 {
+        let mask_bits: u32 = self
+            .mask_a
+            .iter()
+            .zip(self.mask_b)
+            .map(|(a, b)| (a | b).count_ones())
+            .sum();
+        let case_bits = self.checksum_case.iter().filter(|c| c.is_some()).count() as u32;
+        mask_bits + case_bits
+    }
+
+    /// Probability that a random address matches.
+    pub fn probability(&self) -> f64 {
+        0.5_f64.powi(self.fixed_bits() as i32)
+    }
+
+    /// Mean number of attempts needed for a match.
+    pub fn expected_attempts(&self) -> f64 {
+        1.0 / self.probability()
+    }
+
+    /// Number of attempts after which a match has been found with the given
+    /// probability, e.g. 0.5 for the median.
+    pub fn attempts_for_probability(&self, probability: f64) -> f64 {
+        let p = self.probability();
+        if p >= 1.0 {
+            return 1.0;
+        }
+        // Geometric distribution, ln_1p keeps precision for tiny probabilities
+        (-probability).ln_1p() / (-p).ln_1p()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_case() {
+        // EIP-55 test vector
+        let address: Address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            .parse()
+            .unwrap();
+        let hex = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        let nibbles: Vec<Option<u8>> = hex
+            .chars()
+            .map(|c| Some(c.to_digit(16).unwrap() as u8))
+            .collect();
+        let nibbles: [Option<u8>; 40] = nibbles.try_into().unwrap();
+
+        let mut case = [None; 40];
+        case[1] = Some(false); // a
+        case[2] = Some(true); // A
+        let pattern = VanityPattern::new(&nibbles, &case);
+        assert!(pattern.is_match(&address.into()));
+
+        case[1] = Some(true);
+        let pattern = VanityPattern::new(&nibbles, &case);
+        assert!(!pattern.is_match(&address.into()));
+    }
+
+    #[test]
+    fn test_expected_attempts() {
+        let mut nibbles = [None; 40];
+        nibbles[0] = Some(0xa);
+        nibbles[1] = Some(0xb);
+        let pattern = VanityPattern::new(&nibbles, &[None; 40]);
+        assert_eq!(pattern.expected_attempts(), 256.0);
+
+        let mut case = [None; 40];
+        case[0] = Some(true);
+        let pattern = VanityPattern::new(&nibbles, &case);
+        assert_eq!(pattern.expected_attempts(), 512.0);
+
+        let median = pattern.attempts_for_probability(0.5);
+        assert!((354.0..356.0).contains(&median));
+
+        assert_eq!(
+            VanityPattern::new(&[None; 40], &[None; 40]).expected_attempts(),
+            1.0
+        );
+    }
+}
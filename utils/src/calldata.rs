@@ -0,0 +1,388 @@
+//! Decoding of calldata into the function called and its arguments, so that
+//! transactions are not signed blindly.
+//!
+//! Functions come from the ABI bundled with gm and from the ABI files
+//! in `~/.gm/abi` and the `abi_dirs` of the config, which can be plain ABI
+//! arrays or Foundry artifacts.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use alloy::{
+    dyn_abi::{DynSolValue, JsonAbiExt},
+    hex,
+    json_abi::{Function, JsonAbi, Param},
+    primitives::Selector,
+};
+use serde_json::Value;
+
+use crate::{
+    config::Config,
+    disk_storage::{gm_dir, DiskStorageInterface},
+};
+
+/// ABI of common token, router, multicall, smart account and Safe functions.
+const BUNDLED_ABI: &str = include_str!("calldata/signatures.json");
+
+/// Calldata nested in arguments is decoded up to this depth.
+const MAX_NESTING: usize = 4;
+
+#[derive(Debug, Default)]
+pub struct CalldataDecoder {
+    functions: HashMap<Selector, Vec<Function>>,
+}
+
+/// Decoder of the last `load` along with the directories it was read from.
+static LOADED: Mutex<Option<(Vec<PathBuf>, Arc<CalldataDecoder>)>> = Mutex::new(None);
+
+impl CalldataDecoder {
+    /// Decoder with only the bundled ABI.
+    pub fn bundled() -> Self {
+        let mut decoder = Self::default();
+        if let Ok(abi) = serde_json::from_str::<JsonAbi>(BUNDLED_ABI) {
+            for function in abi.functions() {
+                decoder.add_function(function.clone());
+            }
+        }
+        decoder
+    }
+
+    /// Decoder with the bundled ABI and the ABI files of the user,
+    /// which take precedence. Files that are not ABIs are skipped. The
+    /// directories are only read again once the config lists other ones.
+    pub fn load() -> crate::Result<Arc<Self>> {
+        let mut dirs = vec![gm_dir()?.join("abi")];
+        dirs.extend(Config::load()?.abi_dirs);
+
+        let mut loaded = LOADED.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((loaded_dirs, decoder)) = loaded.as_ref() {
+            if loaded_dirs == &dirs {
+                return Ok(decoder.clone());
+            }
+        }
+        let mut decoder = Self::bundled();
+        for dir in &dirs {
+            decoder.add_abi_dir(dir);
+        }
+        let decoder = Arc::new(decoder);
+        *loaded = Some((dirs, decoder.clone()));
+        Ok(decoder)
+    }
+
+    fn add_function(&mut self, function: Function) {
+        let functions = self.functions.entry(function.selector()).or_default();
+        if !functions
+            .iter()
+            .any(|f| f.signature() == function.signature())
+        {
+            functions.push(function);
+        }
+    }
+
+    pub fn add_abi(&mut self, abi: &JsonAbi) {
+        for function in abi.functions() {
+            // Insert first so that the names of the user's ABI are shown
+            let functions = self.functions.entry(function.selector()).or_default();
+            functions.retain(|f| f.signature() != function.signature());
+            functions.insert(0, function.clone());
+        }
+    }
+
+    fn add_abi_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            // Symlinked directories are skipped as they may form a cycle
+            if file_type.is_dir() {
+                // Foundry keeps the whole compiler output there
+                if path.file_name().is_some_and(|name| name != "build-info") {
+                    self.add_abi_dir(&path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(abi) = read_abi_file(&path) {
+                    self.add_abi(&abi);
+                }
+            }
+        }
+    }
+
+    /// The function whose parameters decode the calldata and encode back to
+    /// it exactly, which rules out most selector collisions.
+    pub fn decode(&self, calldata: &[u8]) -> Option<(&Function, Vec<DynSolValue>)> {
+        let (selector, args) = calldata.split_first_chunk::<4>()?;
+        self.functions
+            .get(&Selector::from(*selector))?
+            .iter()
+            .find_map(|function| {
+                let values = function.abi_decode_input(args).ok()?;
+                // The encoding is prefixed with the selector
+                let encoded = function.abi_encode_input(&values).ok()?;
+                (encoded == calldata).then_some((function, values))
+            })
+    }
+
+    /// The function called and its arguments one per line, calls in `bytes`
+    /// arguments such as the ones of multicall are decoded and indented.
+    pub fn describe(&self, calldata: &[u8]) -> Option<String> {
+        let mut out = String::new();
+        self.describe_call(calldata, 0, &mut out)?;
+        Some(out)
+    }
+
+    /// Descriptions of the hex strings in a JSON value that are calldata, such
+    /// as the `data` of a Safe transaction in EIP-712 typed data, along with
+    /// their paths.
+    pub fn describe_json(&self, value: &Value) -> Vec<(String, String)> {
+        let mut found = Vec::new();
+        self.describe_json_at(String::new(), value, &mut found);
+        found
+    }
+
+    fn describe_json_at(&self, path: String, value: &Value, found: &mut Vec<(String, String)>) {
+        match value {
+            Value::String(s) if s.starts_with("0x") => {
+                if let Some(description) = hex::decode(s).ok().and_then(|b| self.describe(&b)) {
+                    found.push((path, description));
+                }
+            }
+            Value::Array(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    self.describe_json_at(format!("{path}[{i}]"), value, found);
+                }
+            }
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    self.describe_json_at(path, value, found);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn describe_call(&self, calldata: &[u8], depth: usize, out: &mut String) -> Option<()> {
+        let (function, values) = self.decode(calldata)?;
+        out.push_str(&function.signature());
+        out.push('\n');
+        for (param, value) in function.inputs.iter().zip(&values) {
+            self.describe_value(&param_name(param), param, value, depth + 1, out);
+        }
+        Some(())
+    }
+
+    fn describe_value(
+        &self,
+        name: &str,
+        param: &Param,
+        value: &DynSolValue,
+        depth: usize,
+        out: &mut String,
+    ) {
+        let indent = "  ".repeat(depth);
+        match value {
+            DynSolValue::Tuple(values) => {
+                out.push_str(&format!("{indent}{name}:\n"));
+                for (i, value) in values.iter().enumerate() {
+                    match param.components.get(i) {
+                        Some(component) => self.describe_value(
+                            &param_name(component),
+                            component,
+                            value,
+                            depth + 1,
+                            out,
+                        ),
+                        None => self.describe_value(&i.to_string(), param, value, depth + 1, out),
+                    }
+                }
+            }
+            DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+                if values.is_empty() {
+                    out.push_str(&format!("{indent}{name}: []\n"));
+                }
+                for (i, value) in values.iter().enumerate() {
+                    self.describe_value(&format!("{name}[{i}]"), param, value, depth, out);
+                }
+            }
+            DynSolValue::Bytes(bytes) => {
+                if depth <= MAX_NESTING {
+                    let mut nested = String::new();
+                    if self.describe_call(bytes, depth, &mut nested).is_some() {
+                        out.push_str(&format!("{indent}{name}: {nested}"));
+                        return;
+                    }
+                }
+                out.push_str(&format!(
+                    "{indent}{name}: {}\n",
+                    hex::encode_prefixed(bytes)
+                ));
+            }
+            value => out.push_str(&format!("{indent}{name}: {}\n", fmt_value(value))),
+        }
+    }
+}
+
+fn param_name(param: &Param) -> String {
+    if param.name.is_empty() {
+        param.ty.clone()
+    } else {
+        param.name.clone()
+    }
+}
+
+fn fmt_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Address(address) => address.to_string(),
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::String(s) => format!("{s:?}"),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes),
+        DynSolValue::Function(function) => hex::encode_prefixed(function),
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values)
+        | DynSolValue::CustomStruct { tuple: values, .. } => format!(
+            "[{}]",
+            values.iter().map(fmt_value).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// ABI of a file holding either the ABI array or an artifact with an `abi`
+/// field, like the ones of Foundry and Hardhat.
+fn read_abi_file(path: &Path) -> Option<JsonAbi> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut value = serde_json::from_str::<Value>(&content).ok()?;
+    if let Some(abi) = value.get_mut("abi") {
+        value = abi.take();
+    }
+    serde_json::from_value(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{address, Address, U256},
+        sol,
+        sol_types::SolCall,
+    };
+
+    use super::*;
+
+    sol! {
+        function transfer(address to, uint256 amount);
+        function multicall(bytes[] data);
+        function stake(uint256 amount, bool lock);
+    }
+
+    #[test]
+    fn test_bundled_abi_parses() {
+        let abi = serde_json::from_str::<JsonAbi>(BUNDLED_ABI).unwrap();
+        let decoder = CalldataDecoder::bundled();
+        assert_eq!(
+            decoder.functions.values().map(Vec::len).sum::<usize>(),
+            abi.functions().count()
+        );
+    }
+
+    #[test]
+    fn test_describe_nested_multicall() {
+        let to = address!("0x63c0c19a282a1b52b07dd5a65b58948a07dae32b");
+        let transfer = transferCall {
+            to,
+            amount: U256::from(1000),
+        }
+        .abi_encode();
+        let calldata = multicallCall {
+            data: vec![transfer.into(), vec![0xde, 0xad].into()],
+        }
+        .abi_encode();
+
+        assert_eq!(
+            CalldataDecoder::bundled().describe(&calldata).unwrap(),
+            format!(
+                "multicall(bytes[])\n  data[0]: transfer(address,uint256)\n    to: {to}\n    \
+                 amount: 1000\n  data[1]: 0xdead\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_user_abi() {
+        let calldata = stakeCall {
+            amount: U256::from(5),
+            lock: true,
+        }
+        .abi_encode();
+        let mut decoder = CalldataDecoder::bundled();
+        assert_eq!(decoder.describe(&calldata), None);
+
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{"type":"function","name":"stake","stateMutability":"nonpayable","outputs":[],
+                "inputs":[{"name":"amount","type":"uint256"},{"name":"lock","type":"bool"}]}]"#,
+        )
+        .unwrap();
+        decoder.add_abi(&abi);
+        assert_eq!(
+            decoder.describe(&calldata).unwrap(),
+            "stake(uint256,bool)\n  amount: 5\n  lock: true\n"
+        );
+
+        let typed_data = serde_json::json!({
+            "message": { "to": Address::ZERO, "data": hex::encode_prefixed(&calldata) }
+        });
+        assert_eq!(
+            decoder.describe_json(&typed_data),
+            vec![(
+                "message.data".to_string(),
+                decoder.describe(&calldata).unwrap()
+            )]
+        );
+
+        // Trailing bytes do not match the signature
+        let mut padded = calldata.clone();
+        padded.extend_from_slice(Address::ZERO.as_slice());
+        assert_eq!(decoder.describe(&padded), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_abi_dir_skips_symlinked_dirs() {
+        let dir = std::env::temp_dir().join(format!("gm-abi-{}", std::process::id()));
+        fs::create_dir_all(dir.join("out")).unwrap();
+        fs::write(
+            dir.join("out/Staking.json"),
+            r#"{"abi":[{"type":"function","name":"stake","stateMutability":"nonpayable",
+                "outputs":[],"inputs":[{"name":"amount","type":"uint256"},
+                {"name":"lock","type":"bool"}]}]}"#,
+        )
+        .unwrap();
+        // Would never finish if the link was followed
+        std::os::unix::fs::symlink(&dir, dir.join("out/loop")).unwrap();
+
+        let mut decoder = CalldataDecoder::default();
+        decoder.add_abi_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let calldata = stakeCall {
+            amount: U256::from(5),
+            lock: true,
+        }
+        .abi_encode();
+        assert!(decoder.describe(&calldata).is_some());
+    }
+}
@@ -1,3 +1,5 @@
+use std::{path::PathBuf, time::Duration};
+
 use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 
@@ -12,8 +14,17 @@ pub struct Config {
     pub alchemy_api_key: Option<String>,
     #[serde(default = "default_theme_name")]
     pub theme_name: String,
+    /// Minutes without input after which the key store is locked, `None` uses
+    /// the default and zero never locks.
+    pub auto_lock_minutes: Option<u64>,
+    /// Directories with ABI files, such as the `out` directory of a Foundry
+    /// project, used along with `~/.gm/abi` to decode calldata.
+    #[serde(default)]
+    pub abi_dirs: Vec<PathBuf>,
 }
 
+pub const DEFAULT_AUTO_LOCK_MINUTES: u64 = 5;
+
 fn default_theme_name() -> String {
     "Monochrome".to_string()
 }
@@ -40,6 +51,13 @@ impl Config {
         Ok(())
     }
 
+    pub fn auto_lock_timeout(&self) -> Option<Duration> {
+        match self.auto_lock_minutes.unwrap_or(DEFAULT_AUTO_LOCK_MINUTES) {
+            0 => None,
+            minutes => Some(Duration::from_secs(minutes * 60)),
+        }
+    }
+
     pub fn alchemy_api_key() -> crate::Result<String> {
         Config::load()?
             .alchemy_api_key
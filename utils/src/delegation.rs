@@ -0,0 +1,99 @@
+//! EIP-7702 delegations, an EOA which signed an authorization runs the code
+//! of the contract it delegates to until the delegation is replaced or revoked.
+
+use alloy::{
+    eips::eip7702::{Authorization, SignedAuthorization},
+    primitives::{Address, U256},
+    providers::Provider,
+    signers::SignerSync,
+};
+
+use crate::{account::AccountManager, network::Network};
+
+/// Code of a delegated EOA is this prefix followed by the delegate address.
+pub const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// Intrinsic gas of an authorization, `PER_EMPTY_ACCOUNT_COST` of EIP-7702.
+pub const PER_AUTHORIZATION_GAS: u64 = 25_000;
+
+/// Returns the delegate if the code is a delegation designator.
+pub fn parse_delegation(code: &[u8]) -> Option<Address> {
+    match code.strip_prefix(&DELEGATION_PREFIX) {
+        Some(delegate) if delegate.len() == Address::len_bytes() => {
+            Some(Address::from_slice(delegate))
+        }
+        _ => None,
+    }
+}
+
+/// Delegation of the account along with its nonce on the network.
+pub async fn fetch_delegation(
+    network: &Network,
+    account: Address,
+) -> crate::Result<(Option<Address>, u64)> {
+    let provider = network.get_provider()?;
+    let code = provider.get_code_at(account).await?;
+    let nonce = provider.get_transaction_count(account).await?;
+    Ok((parse_delegation(&code), nonce))
+}
+
+pub fn authorize(
+    signer: &impl SignerSync,
+    authorization: Authorization,
+) -> crate::Result<SignedAuthorization> {
+    let signature = signer.sign_hash_sync(&authorization.signature_hash())?;
+    Ok(authorization.into_signed(signature))
+}
+
+/// Signs an authorization with the stored key of the account, delegating to
+/// `Address::ZERO` revokes the delegation.
+pub fn sign_authorization(
+    account: &Address,
+    chain_id: u64,
+    delegate: Address,
+    nonce: u64,
+) -> crate::Result<SignedAuthorization> {
+    AccountManager::ensure_can_sign(account)?;
+    authorize(
+        &AccountManager::load_wallet(account)?,
+        Authorization {
+            chain_id: U256::from(chain_id),
+            address: delegate,
+            nonce,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{primitives::address, signers::local::PrivateKeySigner};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_delegation() {
+        let delegate = address!("0x63c0c19a282a1b52b07dd5a65b58948a07dae32b");
+        let code = [DELEGATION_PREFIX.as_slice(), delegate.as_slice()].concat();
+        assert_eq!(parse_delegation(&code), Some(delegate));
+
+        assert_eq!(parse_delegation(&[]), None);
+        assert_eq!(parse_delegation(&code[..22]), None);
+        assert_eq!(parse_delegation(&[0x60; 23]), None);
+    }
+
+    #[test]
+    fn test_authorization_recovers_authority() {
+        let signer = PrivateKeySigner::random();
+        let signed = authorize(
+            &signer,
+            Authorization {
+                chain_id: U256::from(1),
+                address: Address::repeat_byte(1),
+                nonce: 7,
+            },
+        )
+        .unwrap();
+        assert_eq!(signed.nonce, 7);
+        assert_eq!(signed.recover_authority().unwrap(), signer.address());
+    }
+}
@@ -12,6 +12,12 @@ pub enum FileFormat {
     YAML,
 }
 
+/// Directory where gm keeps its files.
+pub fn gm_dir() -> crate::Result<PathBuf> {
+    let dirs = BaseDirs::new().ok_or(crate::Error::BaseDirsFailed)?;
+    Ok(dirs.home_dir().join(".gm"))
+}
+
 pub trait DiskStorageInterface
 where
     Self: Sized + Debug + Default + Serialize + DeserializeOwned,
@@ -21,10 +27,7 @@ where
 
     /// Get the path to the file
     fn path() -> crate::Result<PathBuf> {
-        let dirs = BaseDirs::new().ok_or(crate::Error::BaseDirsFailed)?;
-        let path = dirs
-            .home_dir()
-            .join(".gm")
+        let path = gm_dir()?
             .join(Self::FILE_NAME)
             .with_extension(match Self::FORMAT {
                 FileFormat::TOML => "toml".to_string(),
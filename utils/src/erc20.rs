@@ -5,9 +5,14 @@ use alloy::{
 };
 
 sol! {
+    #[sol(rpc)]
     interface IERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+
         function balanceOf(address owner) external view returns (uint256);
         function transfer(address to, uint256 amount) external returns (bool);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
     }
 }
 
@@ -24,3 +29,9 @@ pub fn encode_transfer(to: Address, amount: U256) -> Bytes {
     let transfer_call = IERC20::transferCall { to, amount };
     Bytes::from(transfer_call.abi_encode())
 }
+
+/// Recipient and amount if the calldata is a `transfer` call.
+pub fn decode_transfer(data: &[u8]) -> Option<(Address, U256)> {
+    let call = IERC20::transferCall::abi_decode(data).ok()?;
+    Some((call.to, call.amount))
+}
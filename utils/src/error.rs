@@ -28,9 +28,122 @@ pub enum UtilsError {
     #[error("Failed to create signer from mnemonic. (Error: {0:?})")]
     MnemonicSignerFailed(alloy::signers::local::LocalSignerError),
 
+    #[error("Mnemonic phrase is invalid.")]
+    InvalidMnemonic,
+
+    #[error(transparent)]
+    Slip39(#[from] crate::account::slip39::Slip39Error),
+
+    #[error("Account {0} is not derived from a mnemonic.")]
+    NotMnemonicAccount(Address),
+
+    #[error("Derivation path '{0}' is invalid.")]
+    InvalidDerivationPath(String),
+
     #[error("Secret not found for account {0}.")]
     SecretNotFound(alloy::primitives::Address),
 
+    #[error("Account {0} is watch-only, it cannot sign transactions or messages.")]
+    WatchOnlyAccount(Address),
+
+    #[error("Account {0} already exists.")]
+    AccountAlreadyExists(Address),
+
+    #[error("Account {0} is a Safe, messages cannot be signed by it directly.")]
+    SafeAccount(Address),
+
+    #[error("There is no Safe deployed at {0} on {1}.")]
+    SafeNotDeployed(Address, String),
+
+    #[error("Safe {safe} was added on {expected}, its transactions cannot be proposed on {got}.")]
+    SafeWrongNetwork {
+        safe: Address,
+        expected: String,
+        got: String,
+    },
+
+    #[error("Safe {0} returned an invalid threshold {1}, it may not be a Safe.")]
+    SafeInvalidThreshold(Address, alloy::primitives::U256),
+
+    #[error("Safe {0} is version {1}, only Safe 1.3.0 and later are supported.")]
+    SafeVersionNotSupported(Address, String),
+
+    #[error(
+        "Network {network} is configured with chain id {expected} but its RPC reports {got}, \
+         Safe signatures would not be valid."
+    )]
+    SafeChainIdMismatch {
+        network: String,
+        expected: u32,
+        got: u64,
+    },
+
+    #[error("Safe transaction #{0} not found.")]
+    SafeTransactionNotFound(u32),
+
+    #[error("Fee history is not available on {0}, the network may not support EIP-1559.")]
+    FeeHistoryUnavailable(String),
+
+    #[error("The latest block of {0} was not found.")]
+    LatestBlockNotFound(String),
+
+    #[error("Transaction type is not specified in the request: {0:?}.")]
+    TxTypeNotSpecified(Box<alloy::rpc::types::TransactionRequest>),
+
+    #[error("Transaction type {0:?} is not supported.")]
+    TxTypeNotSupported(alloy::consensus::TxType),
+
+    #[error("Raw transaction could not be decoded. (Error: {0})")]
+    RawTxDecodeFailed(alloy::eips::eip2718::Eip2718Error),
+
+    #[error("Sender of the raw transaction {0} could not be recovered from its signature.")]
+    RawTxSenderRecoveryFailed(alloy::primitives::B256),
+
+    #[error("Raw transaction has no chain id, select the network to broadcast it on.")]
+    RawTxWithoutChainId,
+
+    #[error("Chain id {0} of the raw transaction is too large for any known network.")]
+    RawTxChainIdTooLarge(u64),
+
+    #[error("'{0}' is not a valid amount of gwei.")]
+    InvalidGwei(String),
+
+    #[error("Key store is locked, please unlock it with your password.")]
+    KeystoreLocked,
+
+    #[error("Key store password is not set yet.")]
+    KeystorePasswordNotSet,
+
+    #[error("Key store password is already set.")]
+    KeystorePasswordAlreadySet,
+
+    #[error("Wrong password for the key store.")]
+    KeystoreWrongPassword,
+
+    #[error("Invalid key derivation parameters in the key store.")]
+    KeystoreKdfParamsInvalid,
+
+    #[error("Failed to encrypt secret for the key store.")]
+    KeystoreEncryptionFailed,
+
+    #[error("Failed to decrypt secret for account {0}, the key store might be corrupted.")]
+    KeystoreDecryptionFailed(alloy::primitives::Address),
+
+    #[error("Key store session is poisoned, please restart gm.")]
+    KeystoreSessionPoisoned,
+
+    #[error("Nonces of the sent transactions are poisoned, please restart gm.")]
+    NoncesPoisoned,
+
+    #[error("Failed to import keystore file {0}, please check the password. (Error: {1:?})")]
+    KeystoreFileImportFailed(PathBuf, alloy::signers::local::LocalSignerError),
+
+    #[error("Failed to export keystore file to {0}. (Error: {1:?})")]
+    KeystoreFileExportFailed(PathBuf, alloy::signers::local::LocalSignerError),
+
+    #[error("Keystore file {0} already exists, move it away or choose another directory.")]
+    KeystoreFileExists(PathBuf),
+
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
 
@@ -76,6 +189,9 @@ pub enum UtilsError {
     #[error("Failed to write to the file: {0}. (Error: {1:?})")]
     FileWriteFailed(PathBuf, std::io::Error),
 
+    #[error("Failed to remove the file: {0}. (Error: {1:?})")]
+    FileRemoveFailed(PathBuf, std::io::Error),
+
     #[error("Parsing the toml file failed: {0}. (Error: {1:?})")]
     TomlParsingFailed(PathBuf, toml::de::Error),
 
@@ -91,6 +207,15 @@ pub enum UtilsError {
     #[error(transparent)]
     AlloySolTypes(#[from] alloy::sol_types::Error),
 
+    #[error(transparent)]
+    AlloyTransport(#[from] alloy::transports::TransportError),
+
+    #[error(transparent)]
+    AlloyContract(#[from] alloy::contract::Error),
+
+    #[error(transparent)]
+    AlloySigner(#[from] alloy::signers::Error),
+
     #[error("Rpc URL not found for network {network} with chain id {chain_id}. Please add it in the networks.")]
     RpcUrlNotFound { network: String, chain_id: u32 },
 
@@ -118,6 +243,10 @@ pub enum UtilsError {
 }
 
 impl UtilsError {
+    pub fn is_keystore_locked(&self) -> bool {
+        matches!(self, Self::KeystoreLocked)
+    }
+
     pub fn is_connect(&self) -> bool {
         match self {
             Self::ReqwestFailed { inner, .. } => inner.is_connect(),
@@ -0,0 +1,306 @@
+//! Gas limit and fee presets, from `eth_feeHistory` for EIP-1559 transactions
+//! and from `eth_gasPrice` for the ones priced with a gas price.
+
+use std::fmt::Display;
+
+use alloy::{
+    consensus::TxType,
+    eips::BlockNumberOrTag,
+    primitives::{
+        utils::{format_units, parse_units},
+        U256,
+    },
+    providers::{utils::Eip1559Estimation, Provider},
+    rpc::types::FeeHistory,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{network::Network, tx_type};
+
+/// Blocks looked back at for the priority fees paid.
+pub const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Gas limit as a percent of the estimate, unless the network sets it.
+pub const DEFAULT_GAS_LIMIT_PERCENT: u64 = 110;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePreset {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl FeePreset {
+    pub const ALL: [FeePreset; 3] = [FeePreset::Slow, FeePreset::Normal, FeePreset::Fast];
+
+    /// Percentile of the priority fees paid in the recent blocks.
+    pub fn percentile(self) -> f64 {
+        match self {
+            FeePreset::Slow => 10.0,
+            FeePreset::Normal => 50.0,
+            FeePreset::Fast => 90.0,
+        }
+    }
+}
+
+impl Display for FeePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeePreset::Slow => write!(f, "slow"),
+            FeePreset::Normal => write!(f, "normal"),
+            FeePreset::Fast => write!(f, "fast"),
+        }
+    }
+}
+
+/// Base fee of the next block and the priority fee of every preset. Gas
+/// prices are priority fees over a zero base fee, so that the max fee of the
+/// estimate is the gas price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeePresets {
+    pub base_fee: u128,
+    pub priority_fees: [u128; 3],
+}
+
+impl FeePresets {
+    pub fn from_history(history: &FeeHistory) -> Option<Self> {
+        let base_fee = history.next_block_base_fee()?;
+
+        let mut priority_fees = [0; 3];
+        for (i, priority_fee) in priority_fees.iter_mut().enumerate() {
+            let mut rewards = history
+                .reward
+                .iter()
+                .flatten()
+                .filter_map(|block| block.get(i).copied())
+                .collect::<Vec<_>>();
+            rewards.sort_unstable();
+            *priority_fee = rewards.get(rewards.len() / 2).copied().unwrap_or_default();
+        }
+
+        Some(Self {
+            base_fee,
+            priority_fees,
+        })
+    }
+
+    /// Presets around the gas price suggested by the node.
+    pub fn from_gas_price(gas_price: u128) -> Self {
+        Self {
+            base_fee: 0,
+            priority_fees: [
+                gas_price - gas_price / 10,
+                gas_price,
+                gas_price + gas_price / 4,
+            ],
+        }
+    }
+
+    /// The max fee leaves room for the base fee to double, which takes at
+    /// least six full blocks.
+    pub fn estimate(&self, preset: FeePreset) -> Eip1559Estimation {
+        let index = FeePreset::ALL
+            .iter()
+            .position(|p| *p == preset)
+            .unwrap_or(1);
+        let max_priority_fee_per_gas = self.priority_fees[index];
+        Eip1559Estimation {
+            max_fee_per_gas: self.base_fee * 2 + max_priority_fee_per_gas,
+            max_priority_fee_per_gas,
+        }
+    }
+}
+
+pub async fn fetch_fee_presets(network: &Network, tx_type: TxType) -> crate::Result<FeePresets> {
+    let provider = network.get_provider()?;
+    if tx_type::uses_gas_price(tx_type) {
+        return Ok(FeePresets::from_gas_price(provider.get_gas_price().await?));
+    }
+    let history = provider
+        .get_fee_history(
+            FEE_HISTORY_BLOCKS,
+            BlockNumberOrTag::Latest,
+            &FeePreset::ALL.map(FeePreset::percentile),
+        )
+        .await?;
+    FeePresets::from_history(&history)
+        .ok_or_else(|| crate::Error::FeeHistoryUnavailable(network.name.clone()))
+}
+
+/// Gas limit and fees a transaction is signed with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasSettings {
+    pub gas_limit: u64,
+    /// The gas price is the max fee for the types priced with one.
+    pub fees: Eip1559Estimation,
+    pub tx_type: TxType,
+    /// `None` once a fee is edited by hand.
+    pub preset: Option<FeePreset>,
+}
+
+impl GasSettings {
+    /// Settings from the defaults of the network, the gas limit requested by
+    /// a dApp is kept if it is higher than the estimate.
+    pub fn new(
+        network: &Network,
+        tx_type: TxType,
+        estimate: u64,
+        requested_gas_limit: Option<u64>,
+        presets: &FeePresets,
+    ) -> Self {
+        let preset = network.fee_preset.unwrap_or_default();
+        Self {
+            gas_limit: network
+                .gas_limit(estimate)
+                .max(requested_gas_limit.unwrap_or_default()),
+            fees: network.cap_fees(presets.estimate(preset)),
+            tx_type,
+            preset: Some(preset),
+        }
+    }
+}
+
+/// Fees of a transaction replacing a pending one with the same nonce, nodes
+/// only accept it if both fees are at least 10% higher. The current fees are
+/// used instead if the network got more expensive since.
+pub fn bump_fees(
+    previous: Eip1559Estimation,
+    current: Option<Eip1559Estimation>,
+) -> Eip1559Estimation {
+    let bump = |fee: u128| fee + fee / 8 + 1;
+    let current = current.unwrap_or(Eip1559Estimation {
+        max_fee_per_gas: 0,
+        max_priority_fee_per_gas: 0,
+    });
+    let max_priority_fee_per_gas =
+        bump(previous.max_priority_fee_per_gas).max(current.max_priority_fee_per_gas);
+    Eip1559Estimation {
+        max_fee_per_gas: bump(previous.max_fee_per_gas)
+            .max(current.max_fee_per_gas)
+            .max(max_priority_fee_per_gas),
+        max_priority_fee_per_gas,
+    }
+}
+
+pub fn parse_gwei(gwei: &str) -> crate::Result<u128> {
+    parse_units(gwei.trim(), "gwei")
+        .ok()
+        .filter(|wei| !wei.is_negative())
+        .and_then(|wei| u128::try_from(wei.get_absolute()).ok())
+        .ok_or_else(|| crate::Error::InvalidGwei(gwei.to_string()))
+}
+
+pub fn format_gwei(wei: u128) -> String {
+    let gwei = format_units(U256::from(wei), "gwei").unwrap_or_default();
+    match gwei.split_once('.') {
+        Some((int, frac)) if frac.trim_end_matches('0').is_empty() => int.to_string(),
+        Some((int, frac)) => format!("{int}.{}", frac.trim_end_matches('0')),
+        None => gwei,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_from_history() {
+        let history = FeeHistory {
+            base_fee_per_gas: vec![10, 12, 14],
+            reward: Some(vec![vec![1, 5, 9], vec![3, 7, 20], vec![2, 6, 11]]),
+            ..Default::default()
+        };
+        let presets = FeePresets::from_history(&history).unwrap();
+        assert_eq!(presets.base_fee, 14);
+        assert_eq!(presets.priority_fees, [2, 6, 11]);
+
+        let fast = presets.estimate(FeePreset::Fast);
+        assert_eq!(fast.max_priority_fee_per_gas, 11);
+        assert_eq!(fast.max_fee_per_gas, 39);
+
+        assert_eq!(FeePresets::from_history(&FeeHistory::default()), None);
+    }
+
+    #[test]
+    fn test_network_defaults() {
+        let presets = FeePresets {
+            base_fee: 10_000_000_000,
+            priority_fees: [1, 2, 3],
+        };
+        let default = GasSettings::new(&Network::default(), TxType::Eip1559, 21000, None, &presets);
+        assert_eq!(default.gas_limit, 23100);
+        assert_eq!(default.preset, Some(FeePreset::Normal));
+        assert_eq!(default.fees.max_fee_per_gas, 20_000_000_002);
+
+        let network = Network {
+            fee_preset: Some(FeePreset::Slow),
+            gas_limit_percent: Some(150),
+            max_fee_cap_gwei: Some(5.0),
+            ..Default::default()
+        };
+        let capped = GasSettings::new(&network, TxType::Eip1559, 21000, Some(50000), &presets);
+        assert_eq!(capped.gas_limit, 50000);
+        assert_eq!(capped.fees.max_fee_per_gas, 5_000_000_000);
+        assert_eq!(capped.fees.max_priority_fee_per_gas, 1);
+        assert_eq!(
+            GasSettings::new(&network, TxType::Eip1559, 40000, None, &presets).gas_limit,
+            60000
+        );
+    }
+
+    #[test]
+    fn test_gas_price_presets() {
+        let presets = FeePresets::from_gas_price(1_000_000_000);
+        let legacy = GasSettings::new(&Network::default(), TxType::Legacy, 21000, None, &presets);
+        assert_eq!(legacy.fees.max_fee_per_gas, 1_000_000_000);
+        assert_eq!(legacy.fees.max_priority_fee_per_gas, 1_000_000_000);
+        assert_eq!(
+            presets.estimate(FeePreset::Slow).max_fee_per_gas,
+            900_000_000
+        );
+        assert_eq!(
+            presets.estimate(FeePreset::Fast).max_fee_per_gas,
+            1_250_000_000
+        );
+    }
+
+    #[test]
+    fn test_bump_fees() {
+        let previous = Eip1559Estimation {
+            max_fee_per_gas: 800,
+            max_priority_fee_per_gas: 80,
+        };
+        let bumped = bump_fees(previous, None);
+        assert_eq!(bumped.max_fee_per_gas, 901);
+        assert_eq!(bumped.max_priority_fee_per_gas, 91);
+
+        let current = Eip1559Estimation {
+            max_fee_per_gas: 2000,
+            max_priority_fee_per_gas: 50,
+        };
+        let bumped = bump_fees(previous, Some(current));
+        assert_eq!(bumped.max_fee_per_gas, 2000);
+        assert_eq!(bumped.max_priority_fee_per_gas, 91);
+
+        // Zero fees of testnets still go up
+        let zero = Eip1559Estimation {
+            max_fee_per_gas: 0,
+            max_priority_fee_per_gas: 0,
+        };
+        assert_eq!(bump_fees(zero, None).max_fee_per_gas, 1);
+    }
+
+    #[test]
+    fn test_gwei() {
+        assert_eq!(parse_gwei("1.5").unwrap(), 1_500_000_000);
+        assert_eq!(parse_gwei(" 30 ").unwrap(), 30_000_000_000);
+        assert!(parse_gwei("fast").is_err());
+        assert!(parse_gwei("-1").is_err());
+
+        assert_eq!(format_gwei(1_500_000_000), "1.5");
+        assert_eq!(format_gwei(30_000_000_000), "30");
+        assert_eq!(format_gwei(1), "0.000000001");
+    }
+}
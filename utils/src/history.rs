@@ -0,0 +1,355 @@
+//! Record of the transactions and signatures produced by gm.
+
+use std::{
+    fmt::Display,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy::{
+    primitives::{Address, B256, U256},
+    providers::Provider,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    disk_storage::{DiskStorageInterface, FileFormat},
+    network::Network,
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryKind {
+    Transaction,
+    Message,
+    TypedData,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryStatus {
+    Pending,
+    Confirmed,
+    Failed,
+    /// Another transaction with the same nonce got mined.
+    Replaced,
+    /// The node forgot the transaction and its nonce is still unused.
+    Dropped,
+    Signed,
+}
+
+impl Display for HistoryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryStatus::Pending => write!(f, "pending"),
+            HistoryStatus::Confirmed => write!(f, "confirmed"),
+            HistoryStatus::Failed => write!(f, "failed"),
+            HistoryStatus::Replaced => write!(f, "replaced"),
+            HistoryStatus::Dropped => write!(f, "dropped"),
+            HistoryStatus::Signed => write!(f, "signed"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub id: u32,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub kind: HistoryKind,
+    /// Where the request came from, such as a WalletConnect dApp or the shell.
+    pub origin: String,
+    pub network: Option<String>,
+    pub from: Address,
+    pub to: Option<Address>,
+    /// Who receives the funds, the decoded recipient of an ERC-20 transfer
+    /// rather than the token contract in `to`. `None` for other calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<Address>,
+    #[serde(default)]
+    pub value: U256,
+    pub nonce: Option<u64>,
+    pub hash: Option<B256>,
+    pub signature: Option<String>,
+    /// The message signed or the function called.
+    pub summary: Option<String>,
+    pub status: HistoryStatus,
+}
+
+impl HistoryEntry {
+    pub fn transaction(
+        origin: &str,
+        network: &Network,
+        from: Address,
+        to: Option<Address>,
+        value: U256,
+        nonce: Option<u64>,
+        hash: B256,
+    ) -> Self {
+        Self {
+            status: HistoryStatus::Pending,
+            ..Self::signed_transaction(origin, Some(network), from, to, value, nonce, hash)
+        }
+    }
+
+    /// Transaction signed without sending it, such as on an offline machine
+    /// where its chain id may not be a configured network.
+    pub fn signed_transaction(
+        origin: &str,
+        network: Option<&Network>,
+        from: Address,
+        to: Option<Address>,
+        value: U256,
+        nonce: Option<u64>,
+        hash: B256,
+    ) -> Self {
+        Self {
+            id: 0,
+            timestamp: now(),
+            kind: HistoryKind::Transaction,
+            origin: origin.to_string(),
+            network: network.map(|network| network.name.clone()),
+            from,
+            to,
+            recipient: None,
+            value,
+            nonce,
+            hash: Some(hash),
+            signature: None,
+            summary: None,
+            status: HistoryStatus::Signed,
+        }
+    }
+
+    pub fn signature(
+        kind: HistoryKind,
+        origin: &str,
+        from: Address,
+        signature: String,
+        summary: String,
+    ) -> Self {
+        Self {
+            id: 0,
+            timestamp: now(),
+            kind,
+            origin: origin.to_string(),
+            network: None,
+            from,
+            to: None,
+            recipient: None,
+            value: U256::ZERO,
+            nonce: None,
+            hash: None,
+            signature: Some(signature),
+            summary: Some(summary),
+            status: HistoryStatus::Signed,
+        }
+    }
+}
+
+impl HistoryEntry {
+    pub fn with_recipient(mut self, recipient: Option<Address>) -> Self {
+        self.recipient = recipient;
+        self
+    }
+
+    /// Summary line with the addresses formatted by `fmt_address`, e.g. along
+    /// with their labels.
+    pub fn summary_line(&self, fmt_address: impl Fn(&Address) -> String) -> String {
+        let action = match self.kind {
+            HistoryKind::Transaction => format!(
+                "tx on {} from {} to {}",
+                self.network.as_deref().unwrap_or("unknown network"),
+                fmt_address(&self.from),
+                self.to
+                    .map(|to| fmt_address(&to))
+                    .unwrap_or_else(|| "new contract".to_string())
+            ),
+            HistoryKind::Message => format!("message signed by {}", fmt_address(&self.from)),
+            HistoryKind::TypedData => {
+                format!("typed data signed by {}", fmt_address(&self.from))
+            }
+        };
+        format!("{}: {action} [{}]", self.origin, self.status)
+    }
+}
+
+impl Display for HistoryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.summary_line(Address::to_string))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct HistoryStore {
+    #[serde(default)]
+    entries: Vec<HistoryEntry>,
+}
+
+impl DiskStorageInterface for HistoryStore {
+    const FILE_NAME: &'static str = "history";
+    const FORMAT: FileFormat = FileFormat::YAML;
+}
+
+impl HistoryStore {
+    /// Entries with the most recent first.
+    pub fn entries(&self) -> Vec<HistoryEntry> {
+        self.entries.iter().rev().cloned().collect()
+    }
+
+    /// Adds the entry with a fresh id and returns the id.
+    pub fn add(&mut self, mut entry: HistoryEntry) -> crate::Result<u32> {
+        entry.id = self.entries.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+        let id = entry.id;
+        self.entries.push(entry);
+        self.save()?;
+        Ok(id)
+    }
+
+    pub fn set_status(&mut self, hash: B256, status: HistoryStatus) -> crate::Result<()> {
+        self.apply(&[(hash, status)]);
+        self.save()
+    }
+
+    fn apply(&mut self, updates: &[(B256, HistoryStatus)]) {
+        for (hash, status) in updates {
+            for entry in &mut self.entries {
+                if entry.hash == Some(*hash) {
+                    entry.status = *status;
+                }
+            }
+        }
+    }
+
+    pub fn remove(&mut self, id: u32) -> crate::Result<()> {
+        self.entries.retain(|e| e.id != id);
+        self.save()
+    }
+}
+
+/// Status of a pending transaction from the node, `None` while it is still
+/// pending.
+async fn fetch_status(entry: &HistoryEntry) -> crate::Result<Option<HistoryStatus>> {
+    let (Some(hash), Some(network)) = (entry.hash, &entry.network) else {
+        return Ok(None);
+    };
+    let provider = Network::from_name(network)?.get_provider()?;
+    if let Some(receipt) = provider.get_transaction_receipt(hash).await? {
+        return Ok(Some(if receipt.status() {
+            HistoryStatus::Confirmed
+        } else {
+            HistoryStatus::Failed
+        }));
+    }
+    if provider.get_transaction_by_hash(hash).await?.is_some() {
+        return Ok(None);
+    }
+    let nonce = provider.get_transaction_count(entry.from).await?;
+    Ok(Some(match entry.nonce {
+        Some(n) if n < nonce => HistoryStatus::Replaced,
+        _ => HistoryStatus::Dropped,
+    }))
+}
+
+/// Updates the transactions still pending from an earlier run, returns how
+/// many of them changed along with the errors of the ones that could not be
+/// looked up. Those stay pending, e.g. their network may be offline.
+pub async fn refresh_pending() -> crate::Result<(usize, Vec<crate::Error>)> {
+    let pending = HistoryStore::load()?
+        .entries
+        .into_iter()
+        .filter(|e| e.status == HistoryStatus::Pending)
+        .collect::<Vec<_>>();
+
+    let mut updates = Vec::new();
+    let mut errors = Vec::new();
+    for entry in pending {
+        match (entry.hash, fetch_status(&entry).await) {
+            (Some(hash), Ok(Some(status))) => updates.push((hash, status)),
+            (_, Ok(_)) => {}
+            (_, Err(error)) => errors.push(error),
+        }
+    }
+
+    // Loaded again since gm may have sent transactions in the meantime
+    if !updates.is_empty() {
+        let mut store = HistoryStore::load()?;
+        store.apply(&updates);
+        store.save()?;
+    }
+    Ok((updates.len(), errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_and_updates() {
+        let network = Network {
+            name: "mainnet".to_string(),
+            ..Default::default()
+        };
+        let (from, to) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let tx = HistoryEntry::transaction(
+            "Transfer",
+            &network,
+            from,
+            Some(to),
+            U256::from(1),
+            Some(3),
+            B256::repeat_byte(9),
+        );
+        let message = HistoryEntry::signature(
+            HistoryKind::Message,
+            "Shell",
+            from,
+            "0x1234".to_string(),
+            "gm".to_string(),
+        );
+        let offline = HistoryEntry::signed_transaction(
+            "Offline Sign",
+            None,
+            from,
+            None,
+            U256::ZERO,
+            Some(4),
+            B256::repeat_byte(8),
+        );
+        assert_eq!(
+            offline.to_string(),
+            format!("Offline Sign: tx on unknown network from {from} to new contract [signed]")
+        );
+        let mut store = HistoryStore {
+            entries: vec![tx.clone(), message.clone()],
+        };
+        assert_eq!(store.entries(), vec![message.clone(), tx.clone()]);
+        assert_eq!(
+            tx.to_string(),
+            format!("Transfer: tx on mainnet from {from} to {to} [pending]")
+        );
+        assert_eq!(
+            message.to_string(),
+            format!("Shell: message signed by {from} [signed]")
+        );
+        assert_eq!(
+            message.summary_line(|_| "alice".to_string()),
+            "Shell: message signed by alice [signed]"
+        );
+
+        store.apply(&[(B256::repeat_byte(9), HistoryStatus::Replaced)]);
+        assert_eq!(store.entries[0].status, HistoryStatus::Replaced);
+        assert_eq!(store.entries[1].status, HistoryStatus::Signed);
+
+        let yaml = serde_yaml::to_string(&store).unwrap();
+        assert!(yaml.contains("status: replaced"));
+        let parsed: HistoryStore = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.entries, store.entries);
+    }
+}
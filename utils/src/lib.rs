@@ -3,16 +3,27 @@ pub mod address_book;
 pub mod alchemy;
 pub mod alloy;
 pub mod assets;
+pub mod calldata;
 pub mod config;
+pub mod delegation;
 pub mod disk_storage;
 pub mod erc20;
 pub mod error;
+pub mod gas;
+pub mod history;
 pub mod inquire;
 pub mod log;
 pub mod network;
+pub mod nonce;
+pub mod raw_tx;
+pub mod receipt;
+pub mod recipient;
 pub mod reqwest;
+pub mod safe;
 pub mod serde;
+pub mod simulate;
 pub mod text;
+pub mod tx_type;
 
 pub use error::{Result, UtilsError as Error};
 
@@ -2,7 +2,7 @@ use std::{collections::HashMap, fmt::Display};
 
 use alloy::{
     primitives::Address,
-    providers::{Provider, ProviderBuilder},
+    providers::{utils::Eip1559Estimation, Provider, ProviderBuilder},
 };
 use serde::{Deserialize, Serialize};
 use serde_with::{formats::PreferMany, serde_as, skip_serializing_none, OneOrMany};
@@ -10,6 +10,8 @@ use serde_with::{formats::PreferMany, serde_as, skip_serializing_none, OneOrMany
 use crate::{
     config::Config,
     disk_storage::{DiskStorageInterface, FileFormat},
+    gas::{FeePreset, DEFAULT_GAS_LIMIT_PERCENT},
+    tx_type::TxTypeSelection,
 };
 
 #[skip_serializing_none]
@@ -33,6 +35,14 @@ pub struct Network {
     #[serde(default, skip_serializing_if = "is_false")]
     pub is_testnet: bool,
     pub rpc_port: Option<usize>,
+    /// Fees transactions are sent with unless changed before signing.
+    pub fee_preset: Option<FeePreset>,
+    /// Gas limit as a percent of the estimate.
+    pub gas_limit_percent: Option<u64>,
+    /// Upper bound of the max fee per gas of the presets.
+    pub max_fee_cap_gwei: Option<f64>,
+    /// Type of the transactions, detected from the latest block if unset.
+    pub tx_type: Option<TxTypeSelection>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tokens: Vec<Token>,
 }
@@ -100,6 +110,19 @@ impl Network {
             .map(|explorer_url| explorer_url.replace("{}", tx_hash))
     }
 
+    pub fn gas_limit(&self, estimate: u64) -> u64 {
+        estimate * self.gas_limit_percent.unwrap_or(DEFAULT_GAS_LIMIT_PERCENT) / 100
+    }
+
+    pub fn cap_fees(&self, mut fees: Eip1559Estimation) -> Eip1559Estimation {
+        if let Some(cap) = self.max_fee_cap_gwei {
+            let cap = (cap * 1e9) as u128;
+            fees.max_fee_per_gas = fees.max_fee_per_gas.min(cap);
+            fees.max_priority_fee_per_gas = fees.max_priority_fee_per_gas.min(cap);
+        }
+        fees
+    }
+
     pub fn get_provider(&self) -> crate::Result<impl Provider> {
         let rpc_url = self.get_rpc()?;
 
@@ -139,7 +162,7 @@ impl NetworkStore {
                 }
                 map.values().cloned().collect::<Vec<Token>>()
             };
-            tokens.sort_by(|a, b| a.contract_address.cmp(&b.contract_address));
+            tokens.sort_by_key(|a| a.contract_address);
             tokens
         };
 
@@ -171,6 +194,10 @@ impl NetworkStore {
                     explorer_url: new_entry.explorer_url.or(existing.explorer_url),
                     is_testnet: new_entry.is_testnet,
                     rpc_port: new_entry.rpc_port.or(existing.rpc_port),
+                    fee_preset: new_entry.fee_preset.or(existing.fee_preset),
+                    gas_limit_percent: new_entry.gas_limit_percent.or(existing.gas_limit_percent),
+                    max_fee_cap_gwei: new_entry.max_fee_cap_gwei.or(existing.max_fee_cap_gwei),
+                    tx_type: new_entry.tx_type.or(existing.tx_type),
                     tokens: merge_tokens(new_entry.tokens, existing.tokens),
                 }
             } else {
@@ -314,6 +341,10 @@ fn default_networks() -> Vec<Network> {
             explorer_url: None,
             is_testnet: false,
             rpc_port: None,
+            fee_preset: None,
+            gas_limit_percent: None,
+            max_fee_cap_gwei: None,
+            tx_type: None,
             tokens: vec![
                 Token {
                     name: "Wrapped Ether".to_string(),
@@ -363,6 +394,10 @@ fn default_networks() -> Vec<Network> {
             explorer_url: Some("https://arbiscan.io/tx/{}".to_string()),
             is_testnet: false,
             rpc_port: None,
+            fee_preset: None,
+            gas_limit_percent: None,
+            max_fee_cap_gwei: None,
+            tx_type: None,
             tokens: vec![
                 Token {
                     name: "Wrapped Ether".to_string(),
@@ -420,6 +455,10 @@ fn default_networks() -> Vec<Network> {
             explorer_url: None,
             is_testnet: false,
             rpc_port: None,
+            fee_preset: None,
+            gas_limit_percent: None,
+            max_fee_cap_gwei: None,
+            tx_type: None,
             tokens: vec![],
         },
         Network {
@@ -436,6 +475,10 @@ fn default_networks() -> Vec<Network> {
             explorer_url: None,
             is_testnet: false,
             rpc_port: None,
+            fee_preset: None,
+            gas_limit_percent: None,
+            max_fee_cap_gwei: None,
+            tx_type: None,
             tokens: vec![
                 Token {
                     name: "Wrapped Ether".to_string(),
@@ -469,6 +512,10 @@ fn default_networks() -> Vec<Network> {
             explorer_url: None,
             is_testnet: false,
             rpc_port: None,
+            fee_preset: None,
+            gas_limit_percent: None,
+            max_fee_cap_gwei: None,
+            tx_type: None,
             tokens: vec![],
         },
         Network {
@@ -485,6 +532,10 @@ fn default_networks() -> Vec<Network> {
             explorer_url: Some("https://sepolia.etherscan.io/tx/{}".to_string()),
             is_testnet: true,
             rpc_port: None,
+            fee_preset: None,
+            gas_limit_percent: None,
+            max_fee_cap_gwei: None,
+            tx_type: None,
             tokens: vec![],
         },
     ]
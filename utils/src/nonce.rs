@@ -0,0 +1,297 @@
+//! Nonces of the transactions sent by gm, so that transactions sent before
+//! the previous ones are mined do not reuse their nonce.
+//!
+//! Nonces are tracked per chain and account for as long as gm runs. A new
+//! transaction gets the lowest nonce not used by the node or by gm, which
+//! fills the gap left by a transaction that failed to be sent. A nonce is also
+//! given out again once the node no longer knows any of the transactions sent
+//! with it, such as when it was dropped from the mempool.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Mutex,
+};
+
+use alloy::{
+    primitives::{Address, B256},
+    providers::Provider,
+};
+
+/// Nonces used by gm that the node may not have mined yet, along with the
+/// hashes sent with each of them. A nonce has no hash while it is being sent.
+#[derive(Debug, Default)]
+struct NonceManager {
+    used: BTreeMap<(u64, Address), BTreeMap<u64, BTreeSet<B256>>>,
+}
+
+impl NonceManager {
+    const fn new() -> Self {
+        Self {
+            used: BTreeMap::new(),
+        }
+    }
+
+    /// Lowest nonce from the pending count of the node that gm did not use.
+    fn next(&self, key: (u64, Address), pending: u64) -> u64 {
+        let mut nonce = pending;
+        if let Some(used) = self.used.get(&key) {
+            for n in used.range(pending..).map(|(n, _)| n) {
+                if *n != nonce {
+                    break;
+                }
+                nonce += 1;
+            }
+        }
+        nonce
+    }
+
+    fn reserve(&mut self, key: (u64, Address), mined: u64, pending: u64) -> u64 {
+        let used = self.used.entry(key).or_default();
+        // Mined nonces cannot be used again
+        *used = used.split_off(&mined);
+        let nonce = self.next(key, pending);
+        self.mark_used(key, nonce);
+        nonce
+    }
+
+    fn mark_used(&mut self, key: (u64, Address), nonce: u64) {
+        self.used.entry(key).or_default().entry(nonce).or_default();
+    }
+
+    fn record_sent(&mut self, key: (u64, Address), nonce: u64, hash: B256) {
+        self.used
+            .entry(key)
+            .or_default()
+            .entry(nonce)
+            .or_default()
+            .insert(hash);
+    }
+
+    /// Sent nonces from the pending count of the node, which it may have
+    /// dropped.
+    fn sent_from(&self, key: (u64, Address), pending: u64) -> Vec<(u64, Vec<B256>)> {
+        self.used
+            .get(&key)
+            .map(|used| {
+                used.range(pending..)
+                    .filter(|(_, hashes)| !hashes.is_empty())
+                    .map(|(nonce, hashes)| (*nonce, hashes.iter().copied().collect()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Frees a nonce none of whose transactions were sent, a replacement
+    /// that failed leaves the transaction it replaces.
+    fn release(&mut self, key: (u64, Address), nonce: u64) {
+        if let Some(used) = self.used.get_mut(&key) {
+            if used.get(&nonce).is_some_and(|hashes| hashes.is_empty()) {
+                used.remove(&nonce);
+            }
+        }
+    }
+
+    fn forget(&mut self, key: (u64, Address), nonce: u64) {
+        if let Some(used) = self.used.get_mut(&key) {
+            used.remove(&nonce);
+        }
+    }
+}
+
+static NONCES: Mutex<NonceManager> = Mutex::new(NonceManager::new());
+
+/// Nonce and pending count from the node, the pending count includes the
+/// transactions in the mempool that can be mined.
+async fn fetch_counts(provider: &impl Provider, account: Address) -> crate::Result<(u64, u64)> {
+    let mined = provider.get_transaction_count(account).latest().await?;
+    let pending = provider.get_transaction_count(account).pending().await?;
+    Ok((mined, pending.max(mined)))
+}
+
+/// Nonce the next transaction of the account would get, without using it.
+pub async fn next_nonce(provider: &impl Provider, account: Address) -> crate::Result<u64> {
+    let chain_id = provider.get_chain_id().await?;
+    let (_, pending) = fetch_counts(provider, account).await?;
+    let nonces = NONCES.lock().map_err(|_| crate::Error::NoncesPoisoned)?;
+    Ok(nonces.next((chain_id, account), pending))
+}
+
+/// Nonce for a transaction about to be signed, it is not given out again
+/// unless the guard is dropped before a hash was recorded.
+pub async fn reserve_nonce(
+    provider: &impl Provider,
+    account: Address,
+) -> crate::Result<NonceGuard> {
+    let chain_id = provider.get_chain_id().await?;
+    let key = (chain_id, account);
+    let (mined, pending) = fetch_counts(provider, account).await?;
+
+    let sent = NONCES
+        .lock()
+        .map_err(|_| crate::Error::NoncesPoisoned)?
+        .sent_from(key, pending);
+    let mut dropped = vec![];
+    for (nonce, hashes) in sent {
+        let mut known = false;
+        for hash in hashes {
+            if provider.get_transaction_by_hash(hash).await?.is_some() {
+                known = true;
+                break;
+            }
+        }
+        if !known {
+            dropped.push(nonce);
+        }
+    }
+
+    let mut nonces = NONCES.lock().map_err(|_| crate::Error::NoncesPoisoned)?;
+    for nonce in dropped {
+        nonces.forget(key, nonce);
+    }
+    let nonce = nonces.reserve(key, mined, pending);
+    Ok(NonceGuard::new(&NONCES, key, nonce))
+}
+
+/// Records a nonce chosen by the request, such as the one of a replacement.
+pub fn mark_nonce_used(chain_id: u64, account: Address, nonce: u64) -> crate::Result<NonceGuard> {
+    let key = (chain_id, account);
+    let mut nonces = NONCES.lock().map_err(|_| crate::Error::NoncesPoisoned)?;
+    nonces.mark_used(key, nonce);
+    Ok(NonceGuard::new(&NONCES, key, nonce))
+}
+
+/// Nonce used by a transaction being sent. Dropping the guard before
+/// recording a hash gives the nonce back, so that a send which fails or gets
+/// aborted does not leave a gap.
+#[derive(Debug)]
+#[must_use]
+pub struct NonceGuard {
+    nonces: &'static Mutex<NonceManager>,
+    key: (u64, Address),
+    nonce: u64,
+}
+
+impl NonceGuard {
+    fn new(nonces: &'static Mutex<NonceManager>, key: (u64, Address), nonce: u64) -> Self {
+        Self { nonces, key, nonce }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Records the hash sent with the nonce, the nonce is given out again if
+    /// the node drops all the transactions sent with it.
+    pub fn record_sent(self, hash: B256) -> crate::Result<()> {
+        let mut nonces = self
+            .nonces
+            .lock()
+            .map_err(|_| crate::Error::NoncesPoisoned)?;
+        nonces.record_sent(self.key, self.nonce, hash);
+        Ok(())
+    }
+}
+
+impl Drop for NonceGuard {
+    fn drop(&mut self) {
+        // Releasing keeps a nonce that has a hash recorded
+        if let Ok(mut nonces) = self.nonces.lock() {
+            nonces.release(self.key, self.nonce);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_consecutive_nonces() {
+        let key = (1, Address::repeat_byte(1));
+        let mut manager = NonceManager::default();
+
+        // The node does not see the transactions sent right before
+        assert_eq!(manager.reserve(key, 5, 5), 5);
+        assert_eq!(manager.reserve(key, 5, 5), 6);
+        assert_eq!(manager.reserve(key, 5, 6), 7);
+        assert_eq!(manager.next(key, 5), 8);
+
+        // Other accounts and chains are independent
+        assert_eq!(manager.reserve((10, key.1), 0, 0), 0);
+        assert_eq!(manager.reserve((1, Address::ZERO), 3, 3), 3);
+    }
+
+    #[test]
+    fn test_fill_gap_and_prune_mined() {
+        let key = (1, Address::repeat_byte(1));
+        let mut manager = NonceManager::default();
+        for _ in 0..3 {
+            manager.reserve(key, 5, 5);
+        }
+
+        // Sending nonce 6 failed, 7 waits for it in the queue of the node
+        manager.release(key, 6);
+        assert_eq!(manager.reserve(key, 5, 6), 6);
+        assert_eq!(manager.reserve(key, 5, 6), 8);
+
+        // All mined, including a transaction sent by another wallet
+        assert_eq!(manager.reserve(key, 10, 10), 10);
+        assert_eq!(manager.used[&key].keys().collect::<Vec<_>>(), [&10]);
+
+        manager.mark_used(key, 11);
+        assert_eq!(manager.next(key, 10), 12);
+    }
+
+    #[test]
+    fn test_forget_dropped() {
+        let key = (1, Address::repeat_byte(1));
+        let mut manager = NonceManager::default();
+        for _ in 0..3 {
+            manager.reserve(key, 5, 5);
+        }
+        manager.record_sent(key, 5, B256::repeat_byte(5));
+        manager.record_sent(key, 6, B256::repeat_byte(6));
+
+        // Nonce 7 is still being sent, it has no hash to look up
+        assert_eq!(
+            manager.sent_from(key, 5),
+            vec![
+                (5, vec![B256::repeat_byte(5)]),
+                (6, vec![B256::repeat_byte(6)])
+            ]
+        );
+        assert_eq!(
+            manager.sent_from(key, 6),
+            vec![(6, vec![B256::repeat_byte(6)])]
+        );
+
+        // A failed replacement keeps the sent transaction
+        manager.release(key, 5);
+        assert_eq!(manager.next(key, 5), 8);
+
+        // The node dropped nonce 5, the next transaction fills the gap
+        manager.forget(key, 5);
+        assert_eq!(manager.reserve(key, 5, 5), 5);
+    }
+
+    #[test]
+    fn test_guard_releases_unsent_nonce() {
+        static TEST_NONCES: Mutex<NonceManager> = Mutex::new(NonceManager::new());
+        let key = (1, Address::repeat_byte(1));
+        let reserve = || {
+            let nonce = TEST_NONCES.lock().unwrap().reserve(key, 5, 5);
+            NonceGuard::new(&TEST_NONCES, key, nonce)
+        };
+
+        // Sending got aborted before a hash was recorded
+        let guard = reserve();
+        assert_eq!(guard.nonce(), 5);
+        drop(guard);
+        let guard = reserve();
+        assert_eq!(guard.nonce(), 5);
+
+        guard.record_sent(B256::repeat_byte(5)).unwrap();
+        assert_eq!(reserve().nonce(), 6);
+        assert_eq!(reserve().nonce(), 6);
+    }
+}
@@ -0,0 +1,128 @@
+//! Signed transactions in the raw form taken by `eth_sendRawTransaction`.
+//!
+//! Signing needs no RPC access once the request has its nonce, chain id, gas
+//! and fees, so the raw transaction can be produced on an offline machine and
+//! broadcast from another one.
+
+use alloy::{
+    consensus::{
+        transaction::SignerRecoverable, SignableTransaction, Transaction, TxEnvelope, TxType,
+        TypedTransaction,
+    },
+    eips::{Decodable2718, Encodable2718},
+    hex,
+    network::TxSignerSync,
+    primitives::{Address, Bytes, B256},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+    signers::local::PrivateKeySigner,
+};
+
+use crate::network::Network;
+
+/// Signs the request as the transaction type its fields make up.
+pub fn sign_tx(wallet: &PrivateKeySigner, tx: TransactionRequest) -> crate::Result<TxEnvelope> {
+    Ok(
+        match tx
+            .build_typed_tx()
+            .map_err(|tx| crate::Error::TxTypeNotSpecified(Box::new(tx)))?
+        {
+            TypedTransaction::Legacy(mut tx) => {
+                let signature = wallet.sign_transaction_sync(&mut tx)?;
+                TxEnvelope::Legacy(tx.into_signed(signature))
+            }
+            TypedTransaction::Eip2930(mut tx) => {
+                let signature = wallet.sign_transaction_sync(&mut tx)?;
+                TxEnvelope::Eip2930(tx.into_signed(signature))
+            }
+            TypedTransaction::Eip1559(mut tx) => {
+                let signature = wallet.sign_transaction_sync(&mut tx)?;
+                TxEnvelope::Eip1559(tx.into_signed(signature))
+            }
+            TypedTransaction::Eip7702(mut tx) => {
+                let signature = wallet.sign_transaction_sync(&mut tx)?;
+                TxEnvelope::Eip7702(tx.into_signed(signature))
+            }
+            TypedTransaction::Eip4844(_) => {
+                return Err(crate::Error::TxTypeNotSupported(TxType::Eip4844))
+            }
+        },
+    )
+}
+
+pub fn encode_raw_tx(tx: &TxEnvelope) -> Bytes {
+    tx.encoded_2718().into()
+}
+
+/// Decodes a raw transaction from its hex, with or without the 0x prefix.
+pub fn decode_raw_tx(raw: &str) -> crate::Result<TxEnvelope> {
+    let bytes = hex::decode(raw.trim())?;
+    TxEnvelope::decode_2718(&mut bytes.as_slice()).map_err(crate::Error::RawTxDecodeFailed)
+}
+
+pub fn recover_sender(tx: &TxEnvelope) -> crate::Result<Address> {
+    tx.recover_signer()
+        .map_err(|_| crate::Error::RawTxSenderRecoveryFailed(*tx.tx_hash()))
+}
+
+/// Network of the transaction's chain id, transactions signed before EIP-155
+/// have none.
+pub fn network_of(tx: &TxEnvelope) -> crate::Result<Network> {
+    let chain_id = tx.chain_id().ok_or(crate::Error::RawTxWithoutChainId)?;
+    let chain_id =
+        u32::try_from(chain_id).map_err(|_| crate::Error::RawTxChainIdTooLarge(chain_id))?;
+    Network::from_chain_id(chain_id)
+}
+
+pub async fn broadcast_raw_tx(network: &Network, raw: &Bytes) -> crate::Result<B256> {
+    let pending = network.get_provider()?.send_raw_transaction(raw).await?;
+    Ok(*pending.tx_hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{TxKind, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_sign_offline_and_decode() {
+        let wallet = PrivateKeySigner::random();
+        let to = Address::repeat_byte(2);
+        let request = TransactionRequest {
+            to: Some(TxKind::Call(to)),
+            value: Some(U256::from(7)),
+            nonce: Some(3),
+            chain_id: Some(1),
+            gas: Some(21_000),
+            gas_price: Some(2_000_000_000),
+            transaction_type: Some(TxType::Legacy.into()),
+            ..Default::default()
+        };
+
+        let signed = sign_tx(&wallet, request.clone()).unwrap();
+        let raw = encode_raw_tx(&signed);
+        let decoded = decode_raw_tx(&raw.to_string()).unwrap();
+        assert_eq!(decoded, signed);
+        assert_eq!(decoded.tx_type(), TxType::Legacy);
+        assert_eq!(recover_sender(&decoded).unwrap(), wallet.address());
+        assert_eq!(decoded.to(), Some(to));
+        assert_eq!(decoded.nonce(), 3);
+        assert_eq!(decoded.chain_id(), Some(1));
+
+        let eip1559 = TransactionRequest {
+            gas_price: None,
+            max_fee_per_gas: Some(3_000_000_000),
+            max_priority_fee_per_gas: Some(1_000_000_000),
+            transaction_type: Some(TxType::Eip1559.into()),
+            ..request
+        };
+        let signed = sign_tx(&wallet, eip1559).unwrap();
+        let raw = encode_raw_tx(&signed).to_string();
+        let decoded = decode_raw_tx(raw.trim_start_matches("0x")).unwrap();
+        assert_eq!(decoded.tx_type(), TxType::Eip1559);
+        assert_eq!(decoded.max_fee_per_gas(), 3_000_000_000);
+
+        assert!(decode_raw_tx("0x1234").is_err());
+    }
+}
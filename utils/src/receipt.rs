@@ -0,0 +1,365 @@
+//! Receipt of a mined transaction, with the fee it paid and the token events
+//! of the account, to confirm that it did what was meant.
+
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fmt::Display,
+};
+
+use alloy::{
+    primitives::{utils::format_units, Address, Log, B256, U256},
+    rpc::types::TransactionReceipt,
+    sol,
+    sol_types::SolEvent,
+};
+
+use crate::{erc20::IERC20, network::Network};
+
+sol! {
+    interface ITokenEvents {
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+        event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenAmount {
+    /// ERC-20 amount in the smallest unit.
+    Fungible(U256),
+    /// ERC-721 token id.
+    Nft(U256),
+    /// ERC-1155 token id and amount.
+    Multi(U256, U256),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenEventKind {
+    Transfer {
+        from: Address,
+        to: Address,
+        amount: TokenAmount,
+    },
+    Approval {
+        owner: Address,
+        spender: Address,
+        amount: TokenAmount,
+    },
+    ApprovalForAll {
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    },
+}
+
+impl TokenEventKind {
+    fn touches(&self, account: Address) -> bool {
+        match *self {
+            Self::Transfer { from, to, .. } => from == account || to == account,
+            Self::Approval { owner, spender, .. } => owner == account || spender == account,
+            Self::ApprovalForAll {
+                owner, operator, ..
+            } => owner == account || operator == account,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenEvent {
+    pub token: Address,
+    /// Token address if the contract has no symbol.
+    pub symbol: String,
+    pub decimals: u8,
+    pub kind: TokenEventKind,
+}
+
+impl TokenEvent {
+    fn fmt_amount(&self, amount: &TokenAmount) -> String {
+        match amount {
+            TokenAmount::Fungible(value) if *value == U256::MAX => {
+                format!("unlimited {}", self.symbol)
+            }
+            TokenAmount::Fungible(value) => {
+                let value =
+                    format_units(*value, self.decimals).unwrap_or_else(|_| value.to_string());
+                let value = match value.contains('.') {
+                    true => value.trim_end_matches('0').trim_end_matches('.'),
+                    false => &value,
+                };
+                format!("{value} {}", self.symbol)
+            }
+            TokenAmount::Nft(id) => format!("{} #{id}", self.symbol),
+            TokenAmount::Multi(id, value) => format!("{value} of {} #{id}", self.symbol),
+        }
+    }
+}
+
+impl Display for TokenEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            TokenEventKind::Transfer { from, to, amount } => write!(
+                f,
+                "Transfer {} from {from} to {to}",
+                self.fmt_amount(amount)
+            ),
+            TokenEventKind::Approval {
+                owner,
+                spender,
+                amount,
+            } => write!(
+                f,
+                "Approval of {} by {owner} to {spender}",
+                self.fmt_amount(amount)
+            ),
+            TokenEventKind::ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            } => write!(
+                f,
+                "{} all {} of {owner} for {operator}",
+                if *approved {
+                    "Approval"
+                } else {
+                    "Revoked approval"
+                },
+                self.symbol
+            ),
+        }
+    }
+}
+
+/// Token and event of the ERC-20, ERC-721 and ERC-1155 logs, which are told
+/// apart by the number of indexed topics as ERC-20 and ERC-721 share the
+/// `Transfer` and `Approval` signatures.
+pub fn parse_token_logs(logs: &[Log]) -> Vec<(Address, TokenEventKind)> {
+    let mut events = vec![];
+    for log in logs {
+        let topics = log.data.topics();
+        let data = log.data.data.as_ref();
+        let word = |i: usize| U256::from_be_slice(&data[32 * i..32 * (i + 1)]);
+        let kinds = match topics {
+            [signature, from, to] if *signature == IERC20::Transfer::SIGNATURE_HASH => {
+                if data.len() < 32 {
+                    continue;
+                }
+                vec![TokenEventKind::Transfer {
+                    from: Address::from_word(*from),
+                    to: Address::from_word(*to),
+                    amount: TokenAmount::Fungible(word(0)),
+                }]
+            }
+            [signature, from, to, id] if *signature == IERC20::Transfer::SIGNATURE_HASH => {
+                vec![TokenEventKind::Transfer {
+                    from: Address::from_word(*from),
+                    to: Address::from_word(*to),
+                    amount: TokenAmount::Nft((*id).into()),
+                }]
+            }
+            [signature, owner, spender] if *signature == ITokenEvents::Approval::SIGNATURE_HASH => {
+                if data.len() < 32 {
+                    continue;
+                }
+                vec![TokenEventKind::Approval {
+                    owner: Address::from_word(*owner),
+                    spender: Address::from_word(*spender),
+                    amount: TokenAmount::Fungible(word(0)),
+                }]
+            }
+            [signature, owner, spender, id]
+                if *signature == ITokenEvents::Approval::SIGNATURE_HASH =>
+            {
+                vec![TokenEventKind::Approval {
+                    owner: Address::from_word(*owner),
+                    spender: Address::from_word(*spender),
+                    amount: TokenAmount::Nft((*id).into()),
+                }]
+            }
+            [signature, ..] if *signature == ITokenEvents::ApprovalForAll::SIGNATURE_HASH => {
+                let Ok(event) = ITokenEvents::ApprovalForAll::decode_log_data(&log.data) else {
+                    continue;
+                };
+                vec![TokenEventKind::ApprovalForAll {
+                    owner: event.owner,
+                    operator: event.operator,
+                    approved: event.approved,
+                }]
+            }
+            [signature, ..] if *signature == ITokenEvents::TransferSingle::SIGNATURE_HASH => {
+                let Ok(event) = ITokenEvents::TransferSingle::decode_log_data(&log.data) else {
+                    continue;
+                };
+                vec![TokenEventKind::Transfer {
+                    from: event.from,
+                    to: event.to,
+                    amount: TokenAmount::Multi(event.id, event.value),
+                }]
+            }
+            [signature, ..] if *signature == ITokenEvents::TransferBatch::SIGNATURE_HASH => {
+                let Ok(event) = ITokenEvents::TransferBatch::decode_log_data(&log.data) else {
+                    continue;
+                };
+                event
+                    .ids
+                    .into_iter()
+                    .zip(event.values)
+                    .map(|(id, value)| TokenEventKind::Transfer {
+                        from: event.from,
+                        to: event.to,
+                        amount: TokenAmount::Multi(id, value),
+                    })
+                    .collect()
+            }
+            _ => continue,
+        };
+        events.extend(kinds.into_iter().map(|kind| (log.address, kind)));
+    }
+    events
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptSummary {
+    pub hash: B256,
+    pub status: bool,
+    pub gas_used: u64,
+    pub effective_gas_price: u128,
+    /// Fee paid on the network itself, L1 data fees of rollups are not
+    /// included.
+    pub fee: U256,
+    pub contract_address: Option<Address>,
+    /// Token events which the account is part of.
+    pub token_events: Vec<TokenEvent>,
+}
+
+/// Summarizes the receipt for the account, symbols and decimals of the tokens
+/// come from the network config or from the token contract.
+pub async fn summarize_receipt(
+    network: &Network,
+    receipt: &TransactionReceipt,
+    account: Address,
+) -> crate::Result<ReceiptSummary> {
+    let provider = network.get_provider()?;
+    let logs = receipt
+        .inner
+        .logs()
+        .iter()
+        .map(|log| log.inner.clone())
+        .collect::<Vec<_>>();
+
+    let mut tokens = BTreeMap::new();
+    let mut token_events = vec![];
+    for (token, kind) in parse_token_logs(&logs) {
+        if !kind.touches(account) {
+            continue;
+        }
+        if let Entry::Vacant(entry) = tokens.entry(token) {
+            let info = match network.tokens.iter().find(|t| t.contract_address == token) {
+                Some(t) => (t.symbol.clone(), t.decimals),
+                None => {
+                    let contract = IERC20::new(token, &provider);
+                    let symbol = contract.symbol().call().await;
+                    // NFTs have no decimals
+                    let decimals = contract.decimals().call().await;
+                    (
+                        symbol.unwrap_or_else(|_| token.to_string()),
+                        decimals.unwrap_or_default(),
+                    )
+                }
+            };
+            entry.insert(info);
+        }
+        let (symbol, decimals) = tokens[&token].clone();
+        token_events.push(TokenEvent {
+            token,
+            symbol,
+            decimals,
+            kind,
+        });
+    }
+
+    Ok(ReceiptSummary {
+        hash: receipt.transaction_hash,
+        status: receipt.status(),
+        gas_used: receipt.gas_used,
+        effective_gas_price: receipt.effective_gas_price,
+        fee: U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price),
+        contract_address: receipt.contract_address,
+        token_events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, LogData};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_token_logs() {
+        let token = address!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+        let log = |topics: Vec<B256>, data: Vec<u8>| Log {
+            address: token,
+            data: LogData::new_unchecked(topics, data.into()),
+        };
+
+        let logs = vec![
+            log(
+                vec![
+                    IERC20::Transfer::SIGNATURE_HASH,
+                    from.into_word(),
+                    to.into_word(),
+                ],
+                U256::from(1_500_000).to_be_bytes_vec(),
+            ),
+            log(
+                vec![
+                    IERC20::Transfer::SIGNATURE_HASH,
+                    from.into_word(),
+                    to.into_word(),
+                    U256::from(7).into(),
+                ],
+                vec![],
+            ),
+            log(
+                vec![
+                    ITokenEvents::Approval::SIGNATURE_HASH,
+                    from.into_word(),
+                    to.into_word(),
+                ],
+                U256::MAX.to_be_bytes_vec(),
+            ),
+            log(vec![B256::ZERO], vec![]),
+        ];
+        let events = parse_token_logs(&logs);
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[1].1,
+            TokenEventKind::Transfer {
+                from,
+                to,
+                amount: TokenAmount::Nft(U256::from(7)),
+            }
+        );
+        assert!(events[2].1.touches(from));
+        assert!(!events[2].1.touches(token));
+
+        let event = |(token, kind)| TokenEvent {
+            token,
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            kind,
+        };
+        let mut events = events.into_iter().map(event);
+        assert_eq!(
+            events.next().unwrap().to_string(),
+            format!("Transfer 1.5 USDC from {from} to {to}")
+        );
+        assert_eq!(
+            events.nth(1).unwrap().to_string(),
+            format!("Approval of unlimited USDC by {from} to {to}")
+        );
+    }
+}
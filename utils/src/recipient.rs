@@ -0,0 +1,226 @@
+//! Checks on the recipient of a transfer before it is signed.
+//!
+//! Address poisoning sends dust from an address sharing the first and last
+//! characters of one the user pays, hoping that it gets copied from the
+//! history instead. Such an address is never sent to before and looks like a
+//! known one, which is what gets checked here.
+
+use std::fmt::Display;
+
+use alloy::{primitives::Address, providers::Provider, rpc::types::TransactionRequest};
+
+use crate::{
+    account::{AccountManager, AccountUtils},
+    address_book::AddressBookStore,
+    delegation,
+    disk_storage::DiskStorageInterface,
+    erc20,
+    history::HistoryStore,
+    network::Network,
+};
+
+/// Hex characters compared at each end of the address, explorers and wallets
+/// usually show this many when they shorten it.
+pub const LOOKALIKE_CHARS: usize = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecipientRisk {
+    /// Not in the address book, the accounts or anything sent to before.
+    NeverUsed,
+    /// Shares the first and last characters with this known address.
+    Lookalike(Address),
+    /// Funds are sent to a contract that is not a known address.
+    Contract,
+    /// Calldata is sent to an address without code.
+    NotContract,
+    /// Tokens are sent to their own contract, where they are usually stuck.
+    TokenContract,
+}
+
+impl Display for RecipientRisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NeverUsed => write!(
+                f,
+                "Recipient was never sent to before, check the full address"
+            ),
+            Self::Lookalike(known) => write!(
+                f,
+                "Recipient looks like {known} but is a different address, it may be address poisoning"
+            ),
+            Self::Contract => write!(
+                f,
+                "Recipient is a contract, make sure that it can move the funds"
+            ),
+            Self::NotContract => write!(f, "Call is sent to an address without code"),
+            Self::TokenContract => write!(
+                f,
+                "Recipient is the token contract itself, the tokens would likely be lost"
+            ),
+        }
+    }
+}
+
+/// Recipient of the funds moved by the transaction, along with the token for
+/// ERC-20 transfers. `None` for any other contract call and for deployments.
+pub fn recipient_of(tx: &TransactionRequest) -> Option<(Address, Option<Address>)> {
+    recipient_of_call(
+        tx.to?.to().copied(),
+        tx.input
+            .input()
+            .map(|data| data.as_ref())
+            .unwrap_or_default(),
+    )
+}
+
+/// Same as [`recipient_of`] for a call to `to` with the `input` data.
+pub fn recipient_of_call(to: Option<Address>, input: &[u8]) -> Option<(Address, Option<Address>)> {
+    let to = to?;
+    match input.is_empty() {
+        true => Some((to, None)),
+        false => erc20::decode_transfer(input).map(|(recipient, _)| (recipient, Some(to))),
+    }
+}
+
+/// Whether both addresses show the same when shortened, case is ignored as
+/// checksums are easily overlooked.
+pub fn is_lookalike(a: &Address, b: &Address) -> bool {
+    let (a, b) = (alloy::hex::encode(a), alloy::hex::encode(b));
+    a != b
+        && a[..LOOKALIKE_CHARS] == b[..LOOKALIKE_CHARS]
+        && a[a.len() - LOOKALIKE_CHARS..] == b[b.len() - LOOKALIKE_CHARS..]
+}
+
+/// Addresses of the address book and the accounts along with the recipients
+/// from the history, which only change when the user does something.
+pub fn stored_addresses() -> crate::Result<Vec<Address>> {
+    let mut known: Vec<Address> = AddressBookStore::load()?
+        .list()
+        .iter()
+        .map(|entry| entry.address)
+        .collect();
+    known.extend(AccountManager::get_account_list()?);
+    known.extend(
+        HistoryStore::load()?
+            .entries()
+            .into_iter()
+            .filter_map(|entry| entry.recipient),
+    );
+    known.sort();
+    known.dedup();
+    Ok(known)
+}
+
+/// The [`stored_addresses`] and the `recent` ones found on chain.
+pub fn known_addresses(recent: Option<&[Address]>) -> crate::Result<Vec<Address>> {
+    Ok(with_recent(stored_addresses()?, recent))
+}
+
+pub fn with_recent(mut known: Vec<Address>, recent: Option<&[Address]>) -> Vec<Address> {
+    known.extend(recent.unwrap_or_default());
+    known.sort();
+    known.dedup();
+    known
+}
+
+/// Checks that need no RPC access.
+pub fn check_known(
+    recipient: Address,
+    token: Option<Address>,
+    known: &[Address],
+) -> Vec<RecipientRisk> {
+    let mut risks = vec![];
+    if token == Some(recipient) {
+        risks.push(RecipientRisk::TokenContract);
+    }
+    if !known.contains(&recipient) {
+        risks.push(RecipientRisk::NeverUsed);
+        risks.extend(
+            known
+                .iter()
+                .filter(|address| is_lookalike(&recipient, address))
+                .map(|address| RecipientRisk::Lookalike(*address)),
+        );
+    }
+    risks
+}
+
+async fn is_contract(network: &Network, address: Address) -> crate::Result<bool> {
+    let code = network.get_provider()?.get_code_at(address).await?;
+    // A delegated EOA is still an EOA
+    Ok(!code.is_empty() && delegation::parse_delegation(&code).is_none())
+}
+
+/// Checks the recipient of the transaction, including whether it has code on
+/// the network.
+pub async fn check_recipient(
+    network: &Network,
+    tx: &TransactionRequest,
+    known: &[Address],
+) -> crate::Result<Vec<RecipientRisk>> {
+    match recipient_of(tx) {
+        Some((recipient, token)) => {
+            let mut risks = check_known(recipient, token, known);
+            if token != Some(recipient)
+                && !known.contains(&recipient)
+                && is_contract(network, recipient).await?
+            {
+                risks.push(RecipientRisk::Contract);
+            }
+            Ok(risks)
+        }
+        None => match tx.to.and_then(|to| to.to().copied()) {
+            Some(to) if tx.from != Some(to) && !is_contract(network, to).await? => {
+                Ok(vec![RecipientRisk::NotContract])
+            }
+            _ => Ok(vec![]),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_check_known() {
+        let known = address!("0x1234567890abcdef1234567890abcdef12345678");
+        let poisoned = address!("0x1234000000000000000000000000000000005678");
+        let token = address!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+
+        assert!(is_lookalike(&known, &poisoned));
+        assert!(!is_lookalike(&known, &known));
+        assert!(!is_lookalike(&known, &token));
+
+        assert_eq!(check_known(known, None, &[known]), vec![]);
+        assert_eq!(
+            check_known(poisoned, None, &[known]),
+            vec![RecipientRisk::NeverUsed, RecipientRisk::Lookalike(known)]
+        );
+        assert_eq!(
+            check_known(token, Some(token), &[token]),
+            vec![RecipientRisk::TokenContract]
+        );
+
+        let transfer = TransactionRequest::default()
+            .to(token)
+            .input(erc20::encode_transfer(poisoned, U256::from(1)).into());
+        assert_eq!(recipient_of(&transfer), Some((poisoned, Some(token))));
+        let plain = TransactionRequest::default().to(known).value(U256::from(1));
+        assert_eq!(recipient_of(&plain), Some((known, None)));
+        let call = TransactionRequest::default()
+            .to(token)
+            .input(erc20::encode_balance_of(known).into());
+        assert_eq!(recipient_of(&call), None);
+        assert_eq!(
+            recipient_of_call(Some(token), &erc20::encode_transfer(known, U256::from(1))),
+            Some((known, Some(token)))
+        );
+        assert_eq!(
+            with_recent(vec![token, known], Some(&[known, poisoned])),
+            vec![poisoned, known, token]
+        );
+    }
+}
@@ -0,0 +1,525 @@
+//! Safe (formerly Gnosis Safe) multisig accounts.
+//!
+//! Transactions of a Safe are proposed and stored locally, they are confirmed
+//! with EIP-712 signatures of the owners whose keys are stored in gm, and once
+//! the threshold is met one of those owners executes it on-chain.
+
+use alloy::{
+    primitives::{Address, Bytes, B256, U256},
+    providers::Provider,
+    signers::SignerSync,
+    sol,
+    sol_types::{eip712_domain, SolCall, SolStruct},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{metadata::AccountMetadataStore, AccountManager, AccountUtils},
+    disk_storage::{DiskStorageInterface, FileFormat},
+    network::Network,
+};
+
+sol! {
+    #[allow(clippy::too_many_arguments)]
+    #[sol(rpc)]
+    interface ISafe {
+        function getOwners() external view returns (address[] memory);
+        function getThreshold() external view returns (uint256);
+        function nonce() external view returns (uint256);
+        function VERSION() external view returns (string memory);
+        function execTransaction(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            bytes memory signatures
+        ) external payable returns (bool success);
+    }
+
+    /// The struct signed by the owners, see `Safe.getTransactionHash`.
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+}
+
+/// On-chain state of a Safe.
+#[derive(Debug, Clone)]
+pub struct SafeInfo {
+    pub owners: Vec<Address>,
+    pub threshold: usize,
+    pub nonce: U256,
+    /// Chain id reported by the RPC, which the signatures are made for.
+    pub chain_id: u64,
+}
+
+impl SafeInfo {
+    /// Fails if the RPC is on another chain than the network is configured
+    /// with, or if the Safe is older than 1.3.0 whose signatures have a
+    /// different EIP-712 domain.
+    pub async fn fetch(network: &Network, safe: Address) -> crate::Result<Self> {
+        let provider = network.get_provider()?;
+        let chain_id = provider.get_chain_id().await?;
+        if chain_id != u64::from(network.chain_id) {
+            return Err(crate::Error::SafeChainIdMismatch {
+                network: network.name.clone(),
+                expected: network.chain_id,
+                got: chain_id,
+            });
+        }
+        if provider.get_code_at(safe).await?.is_empty() {
+            return Err(crate::Error::SafeNotDeployed(safe, network.name.clone()));
+        }
+
+        let contract = ISafe::new(safe, &provider);
+        let version = contract.VERSION().call().await?;
+        if !has_chain_id_domain(&version) {
+            return Err(crate::Error::SafeVersionNotSupported(safe, version));
+        }
+        Ok(Self {
+            owners: contract.getOwners().call().await?,
+            threshold: {
+                let threshold = contract.getThreshold().call().await?;
+                usize::try_from(threshold)
+                    .map_err(|_| crate::Error::SafeInvalidThreshold(safe, threshold))?
+            },
+            nonce: contract.nonce().call().await?,
+            chain_id,
+        })
+    }
+
+    /// Owners whose keys are stored in gm, watch-only accounts cannot sign.
+    pub fn local_owners(&self) -> crate::Result<Vec<Address>> {
+        let metadata = AccountMetadataStore::load()?;
+        let accounts = AccountManager::get_account_list()?;
+        Ok(self
+            .owners
+            .iter()
+            .filter(|owner| {
+                accounts.contains(owner)
+                    && !metadata.is_watch_only(owner)
+                    && metadata.safe_network(owner).is_none()
+            })
+            .copied()
+            .collect())
+    }
+}
+
+/// Whether the Safe version signs with the `{chainId, verifyingContract}`
+/// domain, which Safe uses since 1.3.0.
+fn has_chain_id_domain(version: &str) -> bool {
+    let mut parts = version.split(['.', '+']).map(str::parse::<u32>);
+    match (parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor))) => (major, minor) >= (1, 3),
+        _ => false,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SafeSignature {
+    pub owner: Address,
+    pub signature: Bytes,
+}
+
+/// A transaction of a Safe along with the owner signatures collected so far.
+/// Only plain calls are proposed, so there are no gas refunds or delegate calls.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SafeTransaction {
+    pub id: u32,
+    pub safe: Address,
+    pub network: String,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub nonce: U256,
+    #[serde(default)]
+    pub signatures: Vec<SafeSignature>,
+    /// Hash of the transaction which executed it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executed: Option<B256>,
+}
+
+impl SafeTransaction {
+    pub fn new(
+        safe: Address,
+        network: &Network,
+        to: Address,
+        value: U256,
+        data: Bytes,
+        nonce: U256,
+    ) -> Self {
+        Self {
+            id: 0,
+            safe,
+            network: network.name.clone(),
+            to,
+            value,
+            data,
+            nonce,
+            signatures: vec![],
+            executed: None,
+        }
+    }
+
+    fn safe_tx(&self) -> SafeTx {
+        SafeTx {
+            to: self.to,
+            value: self.value,
+            data: self.data.clone(),
+            operation: 0,
+            safeTxGas: U256::ZERO,
+            baseGas: U256::ZERO,
+            gasPrice: U256::ZERO,
+            gasToken: Address::ZERO,
+            refundReceiver: Address::ZERO,
+            nonce: self.nonce,
+        }
+    }
+
+    /// The EIP-712 hash which the owners sign, same as `Safe.getTransactionHash`.
+    pub fn hash(&self, chain_id: u64) -> B256 {
+        let domain = eip712_domain! {
+            chain_id: chain_id,
+            verifying_contract: self.safe,
+        };
+        self.safe_tx().eip712_signing_hash(&domain)
+    }
+
+    pub fn is_signed_by(&self, owner: &Address) -> bool {
+        self.signatures.iter().any(|s| &s.owner == owner)
+    }
+
+    /// Signs the hash with the stored key of the owner, the key store has to
+    /// be unlocked.
+    pub fn confirm(&mut self, owner: Address, chain_id: u64) -> crate::Result<()> {
+        if self.is_signed_by(&owner) {
+            return Ok(());
+        }
+        let signature =
+            AccountManager::load_wallet(&owner)?.sign_hash_sync(&self.hash(chain_id))?;
+        self.signatures.push(SafeSignature {
+            owner,
+            signature: Bytes::from(signature.as_bytes().to_vec()),
+        });
+        Ok(())
+    }
+
+    /// Signatures concatenated in the ascending order of the owners, which is
+    /// the order `Safe.checkNSignatures` expects.
+    pub fn encoded_signatures(&self) -> Bytes {
+        let mut signatures = self.signatures.clone();
+        signatures.sort_by_key(|s| s.owner);
+        signatures
+            .into_iter()
+            .flat_map(|s| s.signature.to_vec())
+            .collect()
+    }
+
+    /// Calldata of `execTransaction` on the Safe, any account can send it.
+    pub fn exec_calldata(&self) -> Bytes {
+        let tx = self.safe_tx();
+        Bytes::from(
+            ISafe::execTransactionCall {
+                to: tx.to,
+                value: tx.value,
+                data: tx.data,
+                operation: tx.operation,
+                safeTxGas: tx.safeTxGas,
+                baseGas: tx.baseGas,
+                gasPrice: tx.gasPrice,
+                gasToken: tx.gasToken,
+                refundReceiver: tx.refundReceiver,
+                signatures: self.encoded_signatures(),
+            }
+            .abi_encode(),
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SafeTxStore {
+    #[serde(default)]
+    transactions: Vec<SafeTransaction>,
+}
+
+impl DiskStorageInterface for SafeTxStore {
+    const FILE_NAME: &'static str = "safe_transactions";
+    const FORMAT: FileFormat = FileFormat::YAML;
+}
+
+impl SafeTxStore {
+    /// Transactions of the Safe on the network, a Safe at the same address on
+    /// another chain is another account.
+    pub fn transactions_of(&self, safe: &Address, network: &str) -> Vec<SafeTransaction> {
+        self.transactions
+            .iter()
+            .filter(|tx| &tx.safe == safe && tx.network == network)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(&self, id: u32) -> Option<&SafeTransaction> {
+        self.transactions.iter().find(|tx| tx.id == id)
+    }
+
+    /// Nonce for a new transaction, proposals which are not executed yet are
+    /// queued after each other.
+    pub fn next_nonce(&self, safe: &Address, network: &str, onchain_nonce: U256) -> U256 {
+        self.transactions
+            .iter()
+            .filter(|tx| {
+                &tx.safe == safe
+                    && tx.network == network
+                    && tx.executed.is_none()
+                    && tx.nonce >= onchain_nonce
+            })
+            .map(|tx| tx.nonce + U256::from(1))
+            .max()
+            .unwrap_or(onchain_nonce)
+    }
+
+    /// Adds the transaction with a fresh id and returns the id.
+    pub fn add(&mut self, mut tx: SafeTransaction) -> crate::Result<u32> {
+        tx.id = self.transactions.iter().map(|tx| tx.id).max().unwrap_or(0) + 1;
+        let id = tx.id;
+        self.transactions.push(tx);
+        self.save()?;
+        Ok(id)
+    }
+
+    pub fn update(&mut self, tx: SafeTransaction) -> crate::Result<()> {
+        let entry = self
+            .transactions
+            .iter_mut()
+            .find(|entry| entry.id == tx.id)
+            .ok_or(crate::Error::SafeTransactionNotFound(tx.id))?;
+        *entry = tx;
+        self.save()
+    }
+
+    pub fn remove(&mut self, id: u32) -> crate::Result<()> {
+        self.transactions.retain(|tx| tx.id != id);
+        self.save()
+    }
+}
+
+/// Stores a new transaction of the Safe with the next nonce and confirms it
+/// right away with the owners whose keys are stored in gm. The network has to
+/// be the one the Safe was added on, the signatures are only valid there.
+pub async fn propose(
+    network: &Network,
+    safe: Address,
+    to: Address,
+    value: U256,
+    data: Bytes,
+) -> crate::Result<(SafeTransaction, SafeInfo)> {
+    match AccountManager::safe_network(&safe)? {
+        Some(safe_network) if safe_network == network.name => {}
+        Some(safe_network) => {
+            return Err(crate::Error::SafeWrongNetwork {
+                safe,
+                expected: safe_network,
+                got: network.name.clone(),
+            })
+        }
+        None => {}
+    }
+    let info = SafeInfo::fetch(network, safe).await?;
+
+    let mut store = SafeTxStore::load()?;
+    let nonce = store.next_nonce(&safe, &network.name, info.nonce);
+    let mut tx = SafeTransaction::new(safe, network, to, value, data, nonce);
+    for owner in info.local_owners()? {
+        tx.confirm(owner, info.chain_id)?;
+    }
+    tx.id = store.add(tx.clone())?;
+
+    Ok((tx, info))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{address, b256, keccak256},
+        signers::local::PrivateKeySigner,
+        sol_types::SolValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_safe_tx_type_hash() {
+        // SAFE_TX_TYPEHASH of the Safe contracts
+        assert_eq!(
+            keccak256(SafeTx::eip712_encode_type().as_bytes()),
+            b256!("0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8")
+        );
+    }
+
+    #[test]
+    fn test_hash_matches_contract_encoding() {
+        // `Safe.getTransactionHash` with the constants of the contract
+        let domain_separator_typehash =
+            b256!("0x47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218");
+        let safe_tx_typehash =
+            b256!("0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8");
+        assert_eq!(
+            keccak256("EIP712Domain(uint256 chainId,address verifyingContract)"),
+            domain_separator_typehash
+        );
+
+        let safe = address!("0x000000000000000000000000000000000000dEaD");
+        let tx = SafeTransaction::new(
+            safe,
+            &Network::default(),
+            Address::repeat_byte(7),
+            U256::from(1),
+            Bytes::from(vec![0xa9, 0x05, 0x9c, 0xbb]),
+            U256::from(3),
+        );
+
+        let domain_separator =
+            keccak256((domain_separator_typehash, U256::from(100), safe).abi_encode());
+        // A call operation, no gas refund
+        let safe_tx_hash = keccak256(
+            (
+                safe_tx_typehash,
+                tx.to,
+                tx.value,
+                keccak256(&tx.data),
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                Address::ZERO,
+                Address::ZERO,
+                tx.nonce,
+            )
+                .abi_encode(),
+        );
+        let expected = keccak256(
+            [
+                &[0x19, 0x01][..],
+                domain_separator.as_slice(),
+                safe_tx_hash.as_slice(),
+            ]
+            .concat(),
+        );
+        assert_eq!(tx.hash(100), expected);
+    }
+
+    #[test]
+    fn test_chain_id_domain_versions() {
+        for version in ["1.3.0", "1.3.0+L2", "1.4.1", "2.0.0"] {
+            assert!(has_chain_id_domain(version), "{version}");
+        }
+        for version in ["1.0.0", "1.1.1", "1.2.0", "", "unknown"] {
+            assert!(!has_chain_id_domain(version), "{version}");
+        }
+    }
+
+    #[test]
+    fn test_signatures_are_sorted_by_owner() {
+        let tx = SafeTransaction {
+            signatures: vec![
+                SafeSignature {
+                    owner: Address::repeat_byte(2),
+                    signature: Bytes::from(vec![2; 65]),
+                },
+                SafeSignature {
+                    owner: Address::repeat_byte(1),
+                    signature: Bytes::from(vec![1; 65]),
+                },
+            ],
+            ..SafeTransaction::new(
+                address!("0x000000000000000000000000000000000000dEaD"),
+                &Network::default(),
+                Address::ZERO,
+                U256::ZERO,
+                Bytes::new(),
+                U256::ZERO,
+            )
+        };
+
+        let encoded = tx.encoded_signatures();
+        assert_eq!(encoded.len(), 130);
+        assert!(encoded[..65].iter().all(|b| *b == 1));
+        assert!(encoded[65..].iter().all(|b| *b == 2));
+    }
+
+    #[test]
+    fn test_signature_recovers_owner() {
+        let signer = PrivateKeySigner::random();
+        let tx = SafeTransaction::new(
+            address!("0x000000000000000000000000000000000000dEaD"),
+            &Network::default(),
+            Address::repeat_byte(7),
+            U256::from(1),
+            Bytes::new(),
+            U256::from(3),
+        );
+
+        let hash = tx.hash(1);
+        assert_ne!(hash, tx.hash(10));
+        let signature = signer.sign_hash_sync(&hash).unwrap();
+        // `checkNSignatures` treats v of 27 and 28 as a plain ECDSA signature
+        assert!(matches!(signature.as_bytes()[64], 27 | 28));
+        assert_eq!(
+            signature.recover_address_from_prehash(&hash).unwrap(),
+            signer.address()
+        );
+    }
+
+    #[test]
+    fn test_next_nonce_queues_pending_transactions() {
+        let safe = Address::repeat_byte(1);
+        let tx = |nonce: u64, executed: bool| SafeTransaction {
+            executed: executed.then_some(B256::ZERO),
+            ..SafeTransaction::new(
+                safe,
+                &Network::default(),
+                Address::ZERO,
+                U256::ZERO,
+                Bytes::new(),
+                U256::from(nonce),
+            )
+        };
+        let store = SafeTxStore {
+            transactions: vec![tx(3, true), tx(4, false), tx(5, false)],
+        };
+        let network = Network::default().name;
+        assert_eq!(
+            store.next_nonce(&safe, &network, U256::from(4)),
+            U256::from(6)
+        );
+        // Stale proposals below the on-chain nonce are ignored
+        assert_eq!(
+            store.next_nonce(&safe, &network, U256::from(7)),
+            U256::from(7)
+        );
+        assert_eq!(
+            store.next_nonce(&Address::ZERO, &network, U256::from(2)),
+            U256::from(2)
+        );
+        // The same Safe address on another chain has its own nonces
+        assert_eq!(
+            store.next_nonce(&safe, "other", U256::from(2)),
+            U256::from(2)
+        );
+        assert!(store.transactions_of(&safe, "other").is_empty());
+        assert_eq!(store.transactions_of(&safe, &network).len(), 3);
+    }
+}
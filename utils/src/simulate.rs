@@ -0,0 +1,297 @@
+//! Simulation of a transaction before it is signed, to show what it does to
+//! the balances of the sender and why it reverts if it would.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use alloy::{
+    eips::BlockId,
+    hex,
+    primitives::{utils::format_units, Address, I256, U256},
+    providers::{ext::DebugApi, Provider},
+    rpc::types::{
+        trace::geth::{
+            CallConfig, CallFrame, CallLogFrame, GethDebugTracingCallOptions,
+            GethDebugTracingOptions,
+        },
+        TransactionRequest,
+    },
+    sol_types::{decode_revert_reason, SolEvent},
+    transports::RpcError,
+};
+
+use crate::{erc20::IERC20, network::Network};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceChange {
+    /// `None` for the native currency.
+    pub token: Option<Address>,
+    pub symbol: String,
+    pub decimals: u8,
+    pub amount: I256,
+}
+
+impl Display for BalanceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.amount.is_negative() { "-" } else { "+" };
+        let amount = format_units(self.amount.unsigned_abs(), self.decimals)
+            .unwrap_or_else(|_| self.amount.unsigned_abs().to_string());
+        let amount = match amount.contains('.') {
+            true => amount.trim_end_matches('0').trim_end_matches('.'),
+            false => &amount,
+        };
+        write!(f, "{sign}{amount} {}", self.symbol)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Simulation {
+    /// Set if the transaction would revert.
+    pub revert_reason: Option<String>,
+    /// Changes of the balances of the sender, excluding the fee.
+    pub balance_changes: Vec<BalanceChange>,
+    /// False if the node does not support `debug_traceCall`, only the value
+    /// sent is known then.
+    pub traced: bool,
+}
+
+/// Runs the transaction with `eth_call` for the revert reason and with the
+/// call tracer for the value and ERC-20 transfers of the sender.
+pub async fn simulate(network: &Network, tx: TransactionRequest) -> crate::Result<Simulation> {
+    let sender = tx.from.unwrap_or_default();
+    let provider = network.get_provider()?;
+
+    let revert_reason = match provider.call(tx.clone()).await {
+        Ok(_) => None,
+        Err(RpcError::ErrorResp(payload)) => Some(match payload.as_revert_data() {
+            Some(data) => decode_revert_reason(&data).unwrap_or_else(|| {
+                format!("{} (data {})", payload.message, hex::encode_prefixed(data))
+            }),
+            None => payload.message.to_string(),
+        }),
+        Err(err) => return Err(err.into()),
+    };
+
+    let trace = provider
+        .debug_trace_call_callframe(
+            tx.clone(),
+            BlockId::latest(),
+            GethDebugTracingCallOptions::new(GethDebugTracingOptions::call_tracer(CallConfig {
+                only_top_call: None,
+                with_log: Some(true),
+            })),
+        )
+        .await;
+
+    let mut deltas = Deltas::default();
+    let traced = match trace {
+        Ok(frame) => {
+            deltas.add_frame(sender, &frame);
+            true
+        }
+        Err(_) => {
+            let value = tx.value.unwrap_or_default();
+            deltas.native = I256::ZERO.saturating_sub(I256::try_from(value).unwrap_or(I256::MAX));
+            false
+        }
+    };
+    if revert_reason.is_some() {
+        deltas = Deltas::default();
+    }
+
+    let mut balance_changes = Vec::new();
+    if !deltas.native.is_zero() {
+        balance_changes.push(BalanceChange {
+            token: None,
+            symbol: network.symbol.clone().unwrap_or_else(|| "ETH".to_string()),
+            decimals: network.native_decimals.unwrap_or(18),
+            amount: deltas.native,
+        });
+    }
+    for (token, amount) in deltas.tokens {
+        if amount.is_zero() {
+            continue;
+        }
+        let (symbol, decimals) = match network.tokens.iter().find(|t| t.contract_address == token) {
+            Some(t) => (t.symbol.clone(), t.decimals),
+            None => {
+                let contract = IERC20::new(token, &provider);
+                match (
+                    contract.symbol().call().await,
+                    contract.decimals().call().await,
+                ) {
+                    (Ok(symbol), Ok(decimals)) => (symbol, decimals),
+                    _ => (token.to_string(), 0),
+                }
+            }
+        };
+        balance_changes.push(BalanceChange {
+            token: Some(token),
+            symbol,
+            decimals,
+            amount,
+        });
+    }
+
+    Ok(Simulation {
+        revert_reason,
+        balance_changes,
+        traced,
+    })
+}
+
+/// Balance changes of an account summed over the frames which did not revert.
+#[derive(Debug, Default)]
+struct Deltas {
+    native: I256,
+    tokens: BTreeMap<Address, I256>,
+}
+
+impl Deltas {
+    fn add_frame(&mut self, account: Address, frame: &CallFrame) {
+        if frame.error.is_some() {
+            return;
+        }
+
+        // Delegate calls report the value of the call they run in
+        if !matches!(frame.typ.as_str(), "DELEGATECALL" | "STATICCALL") {
+            let value = I256::try_from(frame.value.unwrap_or_default()).unwrap_or(I256::MAX);
+            if frame.from == account {
+                self.native = self.native.saturating_sub(value);
+            }
+            if frame.to == Some(account) {
+                self.native = self.native.saturating_add(value);
+            }
+        }
+
+        for (token, from, to, value) in frame.logs.iter().filter_map(parse_transfer) {
+            let value = I256::try_from(value).unwrap_or(I256::MAX);
+            let delta = self.tokens.entry(token).or_default();
+            if from == account {
+                *delta = delta.saturating_sub(value);
+            }
+            if to == account {
+                *delta = delta.saturating_add(value);
+            }
+        }
+
+        for call in &frame.calls {
+            self.add_frame(account, call);
+        }
+    }
+}
+
+/// Token, sender, recipient and amount of an ERC-20 `Transfer` log, the
+/// ERC-721 event has the same signature with the token id as fourth topic.
+fn parse_transfer(log: &CallLogFrame) -> Option<(Address, Address, Address, U256)> {
+    match log.topics.as_deref()? {
+        [signature, from, to] if *signature == IERC20::Transfer::SIGNATURE_HASH => Some((
+            log.address?,
+            Address::from_word(*from),
+            Address::from_word(*to),
+            U256::try_from_be_slice(log.data.as_ref()?)?,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::B256;
+
+    use super::*;
+
+    fn transfer_log(token: Address, from: Address, to: Address, value: u64) -> CallLogFrame {
+        CallLogFrame {
+            address: Some(token),
+            topics: Some(vec![
+                IERC20::Transfer::SIGNATURE_HASH,
+                from.into_word(),
+                to.into_word(),
+            ]),
+            data: Some(U256::from(value).to_be_bytes_vec().into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_deltas_skip_reverted_frames() {
+        let account = Address::repeat_byte(1);
+        let router = Address::repeat_byte(2);
+        let token = Address::repeat_byte(3);
+
+        let frame = CallFrame {
+            from: account,
+            to: Some(router),
+            value: Some(U256::from(100)),
+            typ: "CALL".to_string(),
+            calls: vec![
+                CallFrame {
+                    from: router,
+                    to: Some(token),
+                    typ: "CALL".to_string(),
+                    logs: vec![transfer_log(token, router, account, 500)],
+                    ..Default::default()
+                },
+                CallFrame {
+                    from: router,
+                    to: Some(account),
+                    value: Some(U256::from(10)),
+                    typ: "CALL".to_string(),
+                    ..Default::default()
+                },
+                CallFrame {
+                    from: router,
+                    to: Some(token),
+                    typ: "CALL".to_string(),
+                    error: Some("execution reverted".to_string()),
+                    logs: vec![transfer_log(token, router, account, 7)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut deltas = Deltas::default();
+        deltas.add_frame(account, &frame);
+        assert_eq!(deltas.native, I256::try_from(-90).unwrap());
+        assert_eq!(deltas.tokens[&token], I256::try_from(500).unwrap());
+    }
+
+    #[test]
+    fn test_parse_transfer() {
+        let (token, from, to) = (
+            Address::repeat_byte(3),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+        );
+        let log = transfer_log(token, from, to, 42);
+        assert_eq!(
+            parse_transfer(&log),
+            Some((token, from, to, U256::from(42)))
+        );
+
+        // ERC-721 transfers have the token id indexed
+        let mut nft = log.clone();
+        nft.topics.as_mut().unwrap().push(B256::with_last_byte(1));
+        assert_eq!(parse_transfer(&nft), None);
+    }
+
+    #[test]
+    fn test_balance_change_display() {
+        let change = BalanceChange {
+            token: None,
+            symbol: "ETH".to_string(),
+            decimals: 18,
+            amount: I256::try_from(-1_500_000_000_000_000_000i128).unwrap(),
+        };
+        assert_eq!(change.to_string(), "-1.5 ETH");
+
+        let change = BalanceChange {
+            token: Some(Address::repeat_byte(3)),
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            amount: I256::try_from(20_000_000).unwrap(),
+        };
+        assert_eq!(change.to_string(), "+20 USDC");
+    }
+}
@@ -0,0 +1,116 @@
+//! Type of the transactions sent on a network. Chains without a base fee
+//! reject EIP-1559 transactions, they take legacy or EIP-2930 ones with a
+//! gas price instead.
+
+use std::fmt::Display;
+
+use alloy::{
+    consensus::TxType,
+    eips::BlockNumberOrTag,
+    providers::Provider,
+    rpc::types::{AccessList, TransactionRequest},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::network::Network;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TxTypeSelection {
+    /// EIP-1559 if the latest block has a base fee, legacy otherwise.
+    #[default]
+    Auto,
+    Legacy,
+    /// Legacy pricing with an access list from `eth_createAccessList`.
+    Eip2930,
+    Eip1559,
+}
+
+impl TxTypeSelection {
+    pub const ALL: [TxTypeSelection; 4] = [
+        TxTypeSelection::Auto,
+        TxTypeSelection::Legacy,
+        TxTypeSelection::Eip2930,
+        TxTypeSelection::Eip1559,
+    ];
+}
+
+impl Display for TxTypeSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxTypeSelection::Auto => write!(f, "auto"),
+            TxTypeSelection::Legacy => write!(f, "legacy"),
+            TxTypeSelection::Eip2930 => write!(f, "eip2930"),
+            TxTypeSelection::Eip1559 => write!(f, "eip1559"),
+        }
+    }
+}
+
+/// Whether the transaction type is priced with a gas price rather than a max
+/// fee and a priority fee.
+pub fn uses_gas_price(tx_type: TxType) -> bool {
+    matches!(tx_type, TxType::Legacy | TxType::Eip2930)
+}
+
+/// Type for a block with the given base fee when the network is set to auto.
+pub fn detected_type(base_fee_per_gas: Option<u64>) -> TxType {
+    match base_fee_per_gas {
+        Some(_) => TxType::Eip1559,
+        None => TxType::Legacy,
+    }
+}
+
+/// Type of the transactions of the network, detected from the header of the
+/// latest block unless the network sets one.
+pub async fn resolve_tx_type(network: &Network) -> crate::Result<TxType> {
+    Ok(match network.tx_type.unwrap_or_default() {
+        TxTypeSelection::Legacy => TxType::Legacy,
+        TxTypeSelection::Eip2930 => TxType::Eip2930,
+        TxTypeSelection::Eip1559 => TxType::Eip1559,
+        TxTypeSelection::Auto => {
+            let block = network
+                .get_provider()?
+                .get_block_by_number(BlockNumberOrTag::Latest)
+                .await?
+                .ok_or_else(|| crate::Error::LatestBlockNotFound(network.name.clone()))?;
+            detected_type(block.header.base_fee_per_gas)
+        }
+    })
+}
+
+/// Access list of the transaction, `None` if the node does not support
+/// `eth_createAccessList`.
+pub async fn create_access_list(
+    provider: &impl Provider,
+    tx: &TransactionRequest,
+) -> Option<AccessList> {
+    provider
+        .create_access_list(tx)
+        .await
+        .ok()
+        .map(|result| result.access_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_config() {
+        assert_eq!(detected_type(Some(7)), TxType::Eip1559);
+        assert_eq!(detected_type(None), TxType::Legacy);
+        assert!(uses_gas_price(TxType::Eip2930));
+        assert!(!uses_gas_price(TxType::Eip7702));
+
+        let network: Network =
+            serde_json::from_str(r#"{"name":"devnet","chain_id":1337,"tx_type":"eip2930"}"#)
+                .unwrap();
+        assert_eq!(network.tx_type, Some(TxTypeSelection::Eip2930));
+        for selection in TxTypeSelection::ALL {
+            assert_eq!(
+                serde_plain::to_string(&selection).unwrap(),
+                selection.to_string()
+            );
+        }
+    }
+}